@@ -32,5 +32,6 @@ npm start
 echo "Compiling and installing Blaze CLI"
 cd $root
 BLAZE_NODE_BRIDGE_BUNDLE_PATH="$root/node/bridge/dist/main.js" \
+    BLAZE_PYTHON_BRIDGE_PATH="$root/python/bridge/src/blaze_python_bridge/main.py" \
     BLAZE_JSON_SCHEMAS_LOCATION="$root/schemas/schemas" \
     cargo +nightly install -Z bindeps --path "$root/cli"
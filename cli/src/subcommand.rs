@@ -12,8 +12,9 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::subcommands::{
-    describe::DescribeCommand, init::InitCommand, render::RenderCommand, rm_cache::RmCacheCommand,
-    run::RunCommand, spawn::SpawnCommand, version::VersionCommand,
+    daemon::DaemonCommand, describe::DescribeCommand, executors::ExecutorsCommand,
+    init::InitCommand, render::RenderCommand, rm_cache::RmCacheCommand, run::RunCommand,
+    spawn::SpawnCommand, version::VersionCommand,
 };
 
 pub trait BlazeSubCommandExecution: Debug {
@@ -41,6 +42,8 @@ impl BlazeSubCommand {
             SubCommandKind::Version => Self(Box::new(VersionCommand::from_arg_matches(args)?)),
             SubCommandKind::Render => Self(Box::new(RenderCommand::from_arg_matches(args)?)),
             SubCommandKind::RmCache => Self(Box::new(RmCacheCommand::from_arg_matches(args)?)),
+            SubCommandKind::Executors => Self(Box::new(ExecutorsCommand::from_arg_matches(args)?)),
+            SubCommandKind::Daemon => Self(Box::new(DaemonCommand::from_arg_matches(args)?)),
         })
     }
 }
@@ -52,6 +55,8 @@ const DESCRIBE: &str = "describe";
 const VERSION: &str = "version";
 const RENDER: &str = "render";
 const RM_CACHE: &str = "rm-cache";
+const EXECUTORS: &str = "executors";
+const DAEMON: &str = "daemon";
 
 #[derive(Debug, EnumIter)]
 pub enum SubCommandKind {
@@ -61,6 +66,8 @@ pub enum SubCommandKind {
     Describe,
     Render,
     RmCache,
+    Executors,
+    Daemon,
     Version,
 }
 
@@ -74,6 +81,8 @@ impl SubCommandKind {
             Self::Version => VersionCommand::augment_args,
             Self::Render => RenderCommand::augment_args,
             Self::RmCache => RmCacheCommand::augment_args,
+            Self::Executors => ExecutorsCommand::augment_args,
+            Self::Daemon => DaemonCommand::augment_args,
         };
         augment_args(clap::Command::new(self.as_str()))
     }
@@ -87,6 +96,8 @@ impl SubCommandKind {
             Self::Version => VERSION,
             Self::Render => RENDER,
             Self::RmCache => RM_CACHE,
+            Self::Executors => EXECUTORS,
+            Self::Daemon => DAEMON,
         }
     }
 }
@@ -109,6 +120,8 @@ impl FromStr for SubCommandKind {
             VERSION => Self::Version,
             RENDER => Self::Render,
             RM_CACHE => Self::RmCache,
+            EXECUTORS => Self::Executors,
+            DAEMON => Self::Daemon,
             _ => bail!("invalid sub command \"{s}\""),
         })
     }
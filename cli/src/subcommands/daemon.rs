@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use blaze_common::error::Result;
+use blaze_core::{daemon, stop_daemon, GlobalOptions};
+use clap::Parser;
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+#[derive(Parser, Debug)]
+#[command(
+    display_name = "daemon",
+    name = "daemon",
+    about("Start a daemon keeping the workspace state in memory."),
+    long_about(
+        "Start a daemon keeping the workspace state in memory, until it is stopped. \
+While the daemon is running, the run command executes targets through it when the --daemon option is used, so that the workspace configuration, projects, input file checksums and executors are not loaded again for each run. \
+The daemon watches the workspace and reloads this state when files change."
+    )
+)]
+pub struct DaemonCommand {
+    #[arg(
+        help = "Stop the running daemon.",
+        long_help = "Stop the daemon running for the workspace, if there is one.",
+        long = "stop"
+    )]
+    stop: bool,
+}
+
+impl BlazeSubCommandExecution for DaemonCommand {
+    fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
+        if !self.stop {
+            return daemon(root, globals);
+        }
+
+        if !stop_daemon(root)? {
+            println!("no daemon is running.");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use blaze_common::error::Result;
+use blaze_core::{update_executors, GlobalOptions};
+use clap::{Parser, Subcommand};
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+#[derive(Debug, Subcommand)]
+pub enum ExecutorsSubcommand {
+    #[command(
+        display_name = "update",
+        name = "update",
+        about = "Update executors and the lockfile.",
+        long_about = "Resolve every executor referenced in the workspace again, ignoring the versions recorded in the `blaze.lock` file. \
+The lockfile is then rewritten with the resolved versions."
+    )]
+    Update,
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    display_name = "executors",
+    name = "executors",
+    about = "Manage custom executors of the workspace.",
+    long_about = "Manage custom executors of the workspace. \
+The next subcommand specifies the operation to perform (for e.g updating the lockfile)."
+)]
+pub struct ExecutorsCommand {
+    #[command(subcommand)]
+    subcommand: ExecutorsSubcommand,
+}
+
+impl BlazeSubCommandExecution for ExecutorsCommand {
+    fn execute(&self, root: &Path, global_options: GlobalOptions) -> Result<()> {
+        match &self.subcommand {
+            ExecutorsSubcommand::Update => update_executors(root, global_options),
+        }
+    }
+}
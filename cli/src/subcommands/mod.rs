@@ -1,5 +1,7 @@
+pub mod daemon;
 pub mod describe;
 pub mod double;
+pub mod executors;
 mod help;
 pub mod init;
 pub mod render;
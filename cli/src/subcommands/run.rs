@@ -1,8 +1,11 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::bail;
 use blaze_common::{error::Result, parallelism::Parallelism, selector::ProjectSelector};
-use blaze_core::{run, GlobalOptions, RunOptions, SelectorSource};
+use blaze_core::{
+    run, run_with_daemon, watch, ExecutedGraph, ExecutionDetails, GlobalOptions, RunOptions,
+    RunOutputFormat, SelectorSource,
+};
 use clap::Parser;
 use possibly::possibly;
 
@@ -54,6 +57,24 @@ Selected projects that don't have any target matching this value will be ignored
     #[command(flatten)]
     selection: SelectionArgs,
 
+    #[arg(
+        help = "Stop executing targets as soon as one of them fails.",
+        long_help = "Stop executing targets as soon as one of them fails. \
+Running processes will be killed and no more targets will be executed. \
+Overrides the `failFast` workspace setting.",
+        long = "fail-fast",
+        conflicts_with = "keep_going"
+    )]
+    fail_fast: bool,
+
+    #[arg(
+        help = "Keep executing targets that do not depend on failed targets.",
+        long_help = "Keep executing targets that do not depend on failed targets. \
+This is the default behavior, unless the `failFast` workspace setting is enabled.",
+        long = "keep-going"
+    )]
+    keep_going: bool,
+
     #[arg(
         help = "Skip execution of all targets.",
         long_help = "Skip execution of all targets. This can be used for debugging purpose, for example if you simply want to checkout the execution graph.",
@@ -61,6 +82,36 @@ Selected projects that don't have any target matching this value will be ignored
     )]
     dry_run: bool,
 
+    #[arg(
+        help = "Fail if the executors lockfile is not up to date.",
+        long_help = "Fail if the executors lockfile is not up to date. \
+Executors are resolved at the versions recorded in the `blaze.lock` file, and the run fails instead of adding missing executors to it. \
+This is typically used in CI, in order to make sure that the lockfile was committed along with configuration changes.",
+        long = "locked"
+    )]
+    locked: bool,
+
+    #[arg(
+        help = "Reuse executor processes across target executions.",
+        long_help = "Reuse executor processes across target executions. \
+Node and Rust custom executors run in a bridge process, which is normally started for each target execution. \
+With this option, one bridge process per executor is kept alive for the whole run and executes several targets, possibly concurrently. \
+Overrides the `persistentWorkers` workspace setting.",
+        long = "persistent-workers"
+    )]
+    persistent_workers: bool,
+
+    #[arg(
+        help = "Report files accessed outside of declared cache inputs and outputs.",
+        long_help = "Report files accessed outside of declared cache inputs and outputs. \
+The project tree is compared before and after each `std:exec` and `std:commands` target execution, and any file written outside of declared outputs is reported. \
+On Linux, opened files are also traced, and any file read without being covered by declared inputs is reported. \
+Only executed targets which have a cache configuration are checked. \
+Since file accesses cannot be attributed to a single target when several targets run at the same time, targets are executed one at a time.",
+        long = "hermetic-check"
+    )]
+    hermetic_check: bool,
+
     #[arg(
         help = "Disables the display of a tree-style execution graph after all targets have been executed.",
         long = "no-graph"
@@ -87,6 +138,55 @@ When providing zero, then no dependencies will be resolved for each target.",
         long = "depth"
     )]
     dependencies_depth: Option<usize>,
+
+    #[arg(
+        help = "Run targets again each time their input files change.",
+        long_help = "Run targets again each time their input files change. \
+Input files are the ones matched by the `inputChanges` cache invalidation option of each target. \
+When files change, only the corresponding targets and the targets depending on them are executed again. \
+If files change while targets are running, the current run is canceled and a new one is started.",
+        long = "watch",
+        conflicts_with = "dry_run"
+    )]
+    watch: bool,
+
+    #[arg(
+        help = "Write a JSON report of the run to a file.",
+        long_help = "Write a JSON report of the run to a file. \
+The report contains the status of each target (executed, cached, noop, failed or ignored), its dependencies, its execution time and its error messages.",
+        long = "report",
+        value_name = "FILE"
+    )]
+    report: Option<PathBuf>,
+
+    #[arg(
+        help = "Output format for the run results (Tree or Json).",
+        long_help = "Output format for the run results. \
+Tree displays the execution graph (unless --no-graph is used), Json prints the same report as the --report option on the standard output. \
+With Json, logs and output of executed targets are displayed on the standard error instead, so that the standard output only contains the report.",
+        long = "output-format"
+    )]
+    output_format: Option<RunOutputFormat>,
+
+    #[arg(
+        help = "Write a JUnit XML report of the run to a file.",
+        long_help = "Write a JUnit XML report of the run to a file. \
+Each project is reported as a test suite, and each target as a test case. \
+Output of processes spawned while executing targets is captured and included in the report.",
+        long = "junit",
+        value_name = "FILE"
+    )]
+    junit: Option<PathBuf>,
+
+    #[arg(
+        help = "Execute targets with the daemon of the workspace.",
+        long_help = "Execute targets with the daemon of the workspace, which must have been started with the daemon command. \
+The daemon keeps the workspace loaded between runs, but targets are executed with its own environment variables, \
+and interrupting this command does not cancel the run. Global options cannot be used along with this option.",
+        long = "daemon",
+        conflicts_with = "watch"
+    )]
+    daemon: bool,
 }
 
 impl BlazeSubCommandExecution for RunCommand {
@@ -112,10 +212,30 @@ impl BlazeSubCommandExecution for RunCommand {
             options = options.with_parallelism(*parallelism);
         }
 
+        if self.fail_fast {
+            options = options.failing_fast();
+        }
+
+        if self.keep_going {
+            options = options.keeping_going();
+        }
+
         if self.dry_run {
             options = options.as_dry_run();
         }
 
+        if self.locked {
+            options = options.as_locked();
+        }
+
+        if self.persistent_workers {
+            options = options.with_persistent_workers(true);
+        }
+
+        if self.hermetic_check {
+            options = options.with_hermetic_check();
+        }
+
         if !self.no_graph {
             options = options.displaying_graph();
         }
@@ -124,20 +244,63 @@ impl BlazeSubCommandExecution for RunCommand {
             options = options.with_dependencies_depth(max_depth);
         }
 
+        if let Some(format) = self.output_format {
+            options = options.with_output_format(format);
+        }
+
+        if let Some(report) = &self.report {
+            options = options.with_report_file(report);
+        }
+
+        if let Some(junit) = &self.junit {
+            options = options.with_junit_report_file(junit);
+        }
+
+        if self.watch {
+            return watch(root, options, globals, |run_result| {
+                if let Err(err) = run_result.and_then(|run_result| check_root_failures(&run_result))
+                {
+                    eprintln!("\n❌ {err:?}\n");
+                }
+                true
+            });
+        }
+
+        if self.daemon {
+            let Some(daemon_result) = run_with_daemon(root, &options, &globals)? else {
+                bail!("could not run targets with the daemon (no daemon is running for the workspace, or global options were provided).")
+            };
+            if !self.dry_run {
+                fail_on_root_failures(daemon_result.root_failures().to_vec())?;
+            }
+            return Ok(());
+        }
+
         let run_result = run(root, options, globals)?;
 
-        let root_failures = run_result
+        if !self.dry_run {
+            check_root_failures(&run_result)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_root_failures(run_result: &ExecutedGraph<ExecutionDetails>) -> Result<()> {
+    fail_on_root_failures(
+        run_result
             .root_executions()
             .values()
             .filter_map(|execution_result| possibly!(&execution_result.result, Some(Err(_))|None => execution_result.execution.get_double()))
-            .collect::<Vec<_>>();
+            .collect(),
+    )
+}
 
-        if !self.dry_run && !root_failures.is_empty() {
-            bail!(
-                "run failed for target(s): \n\n{}",
-                root_failures.into_iter().collect::<Vec<_>>().join("\n")
-            )
-        }
-        Ok(())
+fn fail_on_root_failures(root_failures: Vec<String>) -> Result<()> {
+    if !root_failures.is_empty() {
+        bail!(
+            "run failed for target(s): \n\n{}",
+            root_failures.into_iter().collect::<Vec<_>>().join("\n")
+        )
     }
+    Ok(())
 }
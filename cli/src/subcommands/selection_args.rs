@@ -5,10 +5,12 @@ use clap::Parser;
 use crate::subcommands::help::{exclude_projects_input_hint, include_projects_input_hint};
 
 pub fn project_selection_opts_without<const N: usize>(names: [&str; N]) -> Vec<&'static str> {
-    vec!["projects", "all", "selector", "include", "exclude"]
-        .into_iter()
-        .filter(|n| !names.contains(n))
-        .collect()
+    vec![
+        "projects", "all", "selector", "include", "exclude", "affected",
+    ]
+    .into_iter()
+    .filter(|n| !names.contains(n))
+    .collect()
 }
 
 const INCLUDE_HELP: &str = "A pattern to use for including projects.";
@@ -30,6 +32,12 @@ fn tags_long_help() -> String {
     format!("{} All projects that have at least one tag corresponding to one of the provided tags will be selected. You can pass multiple tags delimited by commas (for example, tag1,tag2,tag3).", TAGS_HELP)
 }
 
+const AFFECTED_HELP: &str = "Select projects affected by changes since a git revision.";
+
+fn affected_long_help() -> String {
+    format!("{} Projects containing files that changed since the merge base of the provided revision and the head revision (or the working directory when --head is not used) will be selected, as well as all the projects depending on them.", AFFECTED_HELP)
+}
+
 #[derive(Parser, Debug)]
 pub struct SelectionArgs {
     #[arg(
@@ -84,6 +92,22 @@ Will be parsed as a comma-separated list of project names (for e.g project1,proj
         conflicts_with_all(project_selection_opts_without(["tags"]))
     )]
     pub tags: Option<Vec<String>>,
+
+    #[arg(
+        help = AFFECTED_HELP,
+        long_help = affected_long_help(),
+        long = "affected",
+        value_name = "BASE",
+        conflicts_with_all(project_selection_opts_without(["affected"]))
+    )]
+    pub affected: Option<String>,
+
+    #[arg(
+        help = "The head git revision to use with --affected.",
+        long = "head",
+        requires = "affected"
+    )]
+    pub head: Option<String>,
 }
 
 impl SelectionArgs {
@@ -93,6 +117,11 @@ impl SelectionArgs {
             .map(ProjectSelector::array)
             .or_else(|| self.all.then_some(ProjectSelector::all()))
             .or_else(|| self.tags.as_ref().map(ProjectSelector::tagged))
+            .or_else(|| {
+                self.affected
+                    .as_ref()
+                    .map(|base| ProjectSelector::affected(base, self.head.as_deref()))
+            })
             .or_else(|| {
                 self.include.as_ref().map(|patterns| {
                     ProjectSelector::include_exclude(
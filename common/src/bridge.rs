@@ -0,0 +1,226 @@
+//! Protocol used between Blaze and executor bridge processes.
+//!
+//! Messages follow the JSON-RPC 2.0 conventions and are exchanged as JSON lines over a local socket.
+//!
+//! - The bridge process connects to the socket and sends an `initialize` request with its protocol version.
+//! - Blaze then sends one or more `execute` requests, which are answered with the executor output or a structured error.
+//!   Executions can run concurrently, and are identified by the ID of their `execute` request.
+//! - While an executor is running, the bridge process can send `log` and `progress` notifications, or queries such as `workspace/resolvePath`.
+//! - Blaze can send a `cancel` notification for an execution at any time.
+//! - Blaze sends a `shutdown` notification when no more executions will be requested. The bridge process exits once all executions are done.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{logger::LogLevel, value::Value};
+
+/// Version of the bridge protocol, which must be the same in Blaze and in bridge processes.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+const JSONRPC_VERSION: &str = "2.0";
+
+/// Request sent by the bridge process once connected ([`InitializeParams`]).
+pub const INITIALIZE_METHOD: &str = "initialize";
+/// Request sent by Blaze in order to run the executor ([`ExecuteResult`] is expected).
+pub const EXECUTE_METHOD: &str = "execute";
+/// Notification sent by Blaze when a target execution is canceled ([`CancelParams`]).
+pub const CANCEL_METHOD: &str = "cancel";
+/// Notification sent by Blaze when the bridge process should exit after running executions are done.
+pub const SHUTDOWN_METHOD: &str = "shutdown";
+/// Notification sent by the bridge process for each log entry ([`LogParams`]).
+pub const LOG_METHOD: &str = "log";
+/// Notification sent by the bridge process in order to report progress ([`ProgressParams`]).
+pub const PROGRESS_METHOD: &str = "progress";
+/// Request sent by the bridge process in order to resolve a path in the workspace ([`ResolvePathParams`], [`ResolvePathResult`]).
+pub const RESOLVE_PATH_METHOD: &str = "workspace/resolvePath";
+
+pub const METHOD_NOT_FOUND_ERROR: i64 = -32601;
+pub const INVALID_PARAMS_ERROR: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// The executor returned an error or panicked.
+pub const EXECUTOR_ERROR: i64 = -32000;
+/// The bridge process protocol version is not supported.
+pub const UNSUPPORTED_VERSION_ERROR: i64 = -32001;
+
+/// A request, a notification or a response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcMessage {
+    jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+/// Content of an [`RpcMessage`], according to its kind.
+pub enum RpcMessageKind {
+    Request {
+        id: u64,
+        method: String,
+        params: Value,
+    },
+    Notification {
+        method: String,
+        params: Value,
+    },
+    Response {
+        id: u64,
+        result: std::result::Result<Value, RpcError>,
+    },
+    Invalid,
+}
+
+impl RpcMessage {
+    fn new() -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_owned(),
+            id: None,
+            method: None,
+            params: None,
+            result: None,
+            error: None,
+        }
+    }
+
+    pub fn request(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            id: Some(id),
+            method: Some(method.to_owned()),
+            params: Some(params),
+            ..Self::new()
+        }
+    }
+
+    pub fn notification(method: &str, params: Value) -> Self {
+        Self {
+            method: Some(method.to_owned()),
+            params: Some(params),
+            ..Self::new()
+        }
+    }
+
+    pub fn response(id: u64, result: std::result::Result<Value, RpcError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                id: Some(id),
+                result: Some(result),
+                ..Self::new()
+            },
+            Err(error) => Self {
+                id: Some(id),
+                error: Some(error),
+                ..Self::new()
+            },
+        }
+    }
+
+    pub fn into_kind(self) -> RpcMessageKind {
+        match (self.id, self.method) {
+            (Some(id), Some(method)) => RpcMessageKind::Request {
+                id,
+                method,
+                params: self.params.unwrap_or(Value::Null),
+            },
+            (None, Some(method)) => RpcMessageKind::Notification {
+                method,
+                params: self.params.unwrap_or(Value::Null),
+            },
+            (Some(id), None) => RpcMessageKind::Response {
+                id,
+                result: match self.error {
+                    Some(error) => Err(error),
+                    None => Ok(self.result.unwrap_or(Value::Null)),
+                },
+            },
+            (None, None) => RpcMessageKind::Invalid,
+        }
+    }
+}
+
+/// Error returned in a response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new<M: Into<String>>(code: i64, message: M) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+/// Additional data of an [`EXECUTOR_ERROR`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExecutorErrorData {
+    /// Causes of the error, from the outermost to the innermost.
+    #[serde(default)]
+    pub causes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeParams {
+    pub protocol_version: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExecuteResult {
+    pub output: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelParams {
+    /// ID of the `execute` request.
+    pub execution: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogParams {
+    pub message: String,
+    pub level: LogLevel,
+    /// ID of the `execute` request the log entry belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProgressParams {
+    /// ID of the `execute` request being reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResolvePathParams {
+    /// Path to resolve, relative paths are resolved from the project root.
+    pub path: PathBuf,
+    /// ID of the `execute` request the path is resolved for, which determines the project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResolvePathResult {
+    /// Absolute path, which is guaranteed to be inside the workspace.
+    pub path: PathBuf,
+}
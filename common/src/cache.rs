@@ -4,6 +4,7 @@ use std::{
     num::NonZeroUsize,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
@@ -20,12 +21,18 @@ use crate::{
 pub struct TargetCache {
     #[serde(default)]
     invalidate_when: InvalidationStrategy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outputs: Option<BTreeSet<FileChangesMatcher>>,
 }
 
 impl TargetCache {
     pub fn invalidate_when(&self) -> &InvalidationStrategy {
         &self.invalidate_when
     }
+
+    pub fn outputs(&self) -> Option<&BTreeSet<FileChangesMatcher>> {
+        self.outputs.as_ref()
+    }
 }
 
 #[derive(Debug, Default, Clone, Hash, Serialize, Deserialize)]
@@ -267,6 +274,20 @@ pub enum TimeUnit {
 unit_enum_from_str!(TimeUnit);
 unit_enum_deserialize!(TimeUnit);
 
+impl TimeUnit {
+    /// Get the duration for an amount of this unit.
+    /// Amounts which are too large saturate to the largest representable number of seconds.
+    pub fn duration(self, amount: u64) -> Duration {
+        match self {
+            TimeUnit::Milliseconds => Duration::from_millis(amount),
+            TimeUnit::Seconds => Duration::from_secs(amount),
+            TimeUnit::Minutes => Duration::from_secs(amount.saturating_mul(60)),
+            TimeUnit::Hours => Duration::from_secs(amount.saturating_mul(60 * 60)),
+            TimeUnit::Days => Duration::from_secs(amount.saturating_mul(60 * 60 * 24)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct TtlOptions {
     unit: TimeUnit,
@@ -18,6 +18,8 @@ use crate::{cache::FileChangesMatcher, error::Error, unit_enum_deserialize, unit
 pub enum ExecutorKind {
     Rust,
     Node,
+    Python,
+    Wasm,
 }
 
 unit_enum_from_str!(ExecutorKind);
@@ -237,9 +239,28 @@ impl Serialize for Location {
                 }
                 value.serialize(serializer)
             }
+            Self::TarballOverHttp {
+                transport,
+                tarball_options,
+                authentication,
+            } => {
+                let mut value = to_value(transport).map_err(S::Error::custom)?;
+                value.overwrite(to_value(tarball_options).map_err(S::Error::custom)?);
+                value.overwrite(Value::object([(
+                    FORMAT_KEY,
+                    Value::string(HttpFormatIdentifier::Tarball.to_string()),
+                )]));
+                if let Some(authentication) = authentication {
+                    value.overwrite(Value::object([(
+                        AUTHENTICATION_KEY,
+                        to_value(authentication).map_err(S::Error::custom)?,
+                    )]));
+                }
+                value.serialize(serializer)
+            }
+            Self::Git { options } => options.serialize(serializer),
             Self::Npm { options } => options.serialize(serializer),
             Self::Cargo { options } => options.serialize(serializer),
-            _ => todo!(),
         }
     }
 }
@@ -301,6 +322,7 @@ const HTTP_AUTH_MODE_KEY: &str = "mode";
 #[derive(Serialize, EnumIter, Display, Hash, PartialEq, Eq, Clone)]
 pub enum HttpAuthenticationMode {
     Basic,
+    Digest,
     Bearer,
 }
 
@@ -353,6 +375,7 @@ impl HttpBearerAuthentication {
 #[derive(Hash, Debug, PartialEq, Eq, Clone)]
 pub enum HttpAuthentication {
     Basic(HttpBasicAuthentication),
+    Digest(HttpDigestAuthentication),
     Bearer(HttpBearerAuthentication),
 }
 
@@ -371,6 +394,9 @@ impl<'de> Deserialize<'de> for HttpAuthentication {
             HttpAuthenticationMode::Basic => HttpAuthentication::Basic(
                 HttpBasicAuthentication::deserialize(root).map_err(D::Error::custom)?,
             ),
+            HttpAuthenticationMode::Digest => HttpAuthentication::Digest(
+                HttpDigestAuthentication::deserialize(root).map_err(D::Error::custom)?,
+            ),
             HttpAuthenticationMode::Bearer => HttpAuthentication::Bearer(
                 HttpBearerAuthentication::deserialize(root).map_err(D::Error::custom)?,
             ),
@@ -387,6 +413,7 @@ impl Serialize for HttpAuthentication {
             HTTP_AUTH_MODE_KEY,
             to_value(match self {
                 HttpAuthentication::Basic(_) => HttpAuthenticationMode::Basic,
+                HttpAuthentication::Digest(_) => HttpAuthenticationMode::Digest,
                 HttpAuthentication::Bearer(_) => HttpAuthenticationMode::Bearer,
             })
             .map_err(S::Error::custom)?,
@@ -395,6 +422,7 @@ impl Serialize for HttpAuthentication {
         value.overwrite(
             match self {
                 HttpAuthentication::Basic(basic) => to_value(basic),
+                HttpAuthentication::Digest(digest) => to_value(digest),
                 HttpAuthentication::Bearer(bearer) => to_value(bearer),
             }
             .map_err(S::Error::custom)?,
@@ -495,6 +523,8 @@ pub enum Compression {
     Deflate,
     Zlib,
     Gzip,
+    Xz,
+    Bzip2,
 }
 
 unit_enum_from_str!(Compression);
@@ -506,6 +536,8 @@ pub struct TarballOptions {
     kind: Option<ExecutorKind>,
     #[serde(skip_serializing_if = "Option::is_none")]
     compression: Option<Compression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
 }
 
 impl TarballOptions {
@@ -516,6 +548,10 @@ impl TarballOptions {
     pub fn compression(&self) -> Option<Compression> {
         self.compression
     }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
 }
 
 #[derive(Serialize, EnumIter, Display, Hash, Debug, PartialEq, Eq, Copy, Clone)]
@@ -660,10 +696,13 @@ pub enum SshAuthentication {
 }
 
 #[derive(Deserialize, Serialize, Hash, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CargoOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<String>,
     #[serde(default)]
+    plain_http: bool,
+    #[serde(default)]
     insecure: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     token: Option<String>,
@@ -674,6 +713,10 @@ impl CargoOptions {
         self.version.as_deref()
     }
 
+    pub fn plain_http(&self) -> bool {
+        self.plain_http
+    }
+
     pub fn insecure(&self) -> bool {
         self.insecure
     }
@@ -684,10 +727,13 @@ impl CargoOptions {
 }
 
 #[derive(Deserialize, Serialize, Hash, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct NpmOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<String>,
     #[serde(default)]
+    plain_http: bool,
+    #[serde(default)]
     insecure: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     token: Option<String>,
@@ -698,6 +744,10 @@ impl NpmOptions {
         self.version.as_deref()
     }
 
+    pub fn plain_http(&self) -> bool {
+        self.plain_http
+    }
+
     pub fn insecure(&self) -> bool {
         self.insecure
     }
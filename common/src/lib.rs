@@ -1,3 +1,4 @@
+pub mod bridge;
 pub mod cache;
 pub mod configuration_file;
 pub mod dependency;
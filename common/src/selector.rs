@@ -15,6 +15,13 @@ pub enum ProjectSelector {
     },
     #[serde(untagged)]
     Tagged(BTreeSet<String>),
+    /// Projects with files changed since a git revision, along with their dependents.
+    #[serde(untagged)]
+    Affected {
+        base: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        head: Option<String>,
+    },
 }
 
 impl Display for ProjectSelector {
@@ -26,6 +33,11 @@ impl Display for ProjectSelector {
                 format!("projects matching expressions {include:?}, excluding {exclude:?}").into()
             }
             Self::Tagged(tags) => format!("projects tagged with: {tags:?}").into(),
+            Self::Affected { base, head } => format!(
+                "projects affected by changes between {base} and {}",
+                head.as_deref().unwrap_or("the working directory")
+            )
+            .into(),
         })
     }
 }
@@ -58,4 +70,11 @@ impl ProjectSelector {
     pub fn tagged<S: AsRef<str>, I: IntoIterator<Item = S>>(tags: I) -> Self {
         Self::Tagged(tags.into_iter().map(|s| s.as_ref().to_owned()).collect())
     }
+
+    pub fn affected(base: &str, head: Option<&str>) -> Self {
+        Self::Affected {
+            base: base.to_owned(),
+            head: head.map(str::to_owned),
+        }
+    }
 }
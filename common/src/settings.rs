@@ -1,7 +1,13 @@
 use std::collections::BTreeMap;
 
-use crate::{logger::LogLevel, parallelism::Parallelism, selector::ProjectSelector};
+use crate::{
+    executor::{HttpAuthentication, HttpTransport},
+    logger::LogLevel,
+    parallelism::Parallelism,
+    selector::ProjectSelector,
+};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 /// Global settings for the workspace.
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -17,6 +23,16 @@ pub struct GlobalSettings {
     log_level: Option<LogLevel>,
     #[serde(skip_serializing_if = "Option::is_none")]
     resolution_parallelism: Option<Parallelism>,
+    #[serde(default)]
+    cache_backend: CacheBackend,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fail_fast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistent_workers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_process_rust_executors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_unenforced_capabilities: Option<bool>,
 }
 
 impl GlobalSettings {
@@ -39,4 +55,58 @@ impl GlobalSettings {
     pub fn resolution_parallelism(&self) -> Option<Parallelism> {
         self.resolution_parallelism
     }
+
+    pub fn cache_backend(&self) -> &CacheBackend {
+        &self.cache_backend
+    }
+
+    pub fn fail_fast(&self) -> Option<bool> {
+        self.fail_fast
+    }
+
+    pub fn persistent_workers(&self) -> Option<bool> {
+        self.persistent_workers
+    }
+
+    pub fn in_process_rust_executors(&self) -> Option<bool> {
+        self.in_process_rust_executors
+    }
+
+    pub fn allow_unenforced_capabilities(&self) -> Option<bool> {
+        self.allow_unenforced_capabilities
+    }
+}
+
+/// Where cache entries are stored.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CacheBackend {
+    /// Cache entries are stored in the workspace `.blaze/cache` directory.
+    #[default]
+    Local,
+    /// Cache entries are stored on a remote HTTP server.
+    Http(HttpCacheBackendOptions),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpCacheBackendOptions {
+    url: Url,
+    #[serde(flatten)]
+    transport: HttpTransport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    authentication: Option<HttpAuthentication>,
+}
+
+impl HttpCacheBackendOptions {
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn transport(&self) -> &HttpTransport {
+        &self.transport
+    }
+
+    pub fn authentication(&self) -> Option<&HttpAuthentication> {
+        self.authentication.as_ref()
+    }
 }
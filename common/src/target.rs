@@ -1,10 +1,22 @@
-use std::hash::Hash;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::Hash,
+    num::NonZeroUsize,
+    path::PathBuf,
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
 
 use hash_value::Value;
 
-use crate::{cache::TargetCache, dependency::Dependency, executor::ExecutorReference};
+use crate::{
+    cache::{TargetCache, TimeUnit},
+    dependency::Dependency,
+    enums::{unit_enum_deserialize, unit_enum_from_str},
+    executor::ExecutorReference,
+};
 
 /// A single target description
 #[derive(Debug, Serialize, Deserialize, Hash)]
@@ -21,6 +33,137 @@ pub struct Target {
     cache: Option<TargetCache>,
     #[serde(default)]
     stateless: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<TimeSpan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<TargetRetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<TargetEnvironment>,
+}
+
+/// A strictly positive duration, expressed as an amount of time units.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct TimeSpan {
+    unit: TimeUnit,
+    amount: NonZeroUsize,
+}
+
+impl TimeSpan {
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    pub fn amount(&self) -> usize {
+        self.amount.get()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.unit.duration(self.amount.get() as u64)
+    }
+}
+
+impl std::fmt::Display for TimeSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = self.unit.to_string().to_lowercase();
+        match self.amount.get() {
+            1 => write!(f, "1 {}", unit.trim_end_matches('s')),
+            amount => write!(f, "{amount} {unit}"),
+        }
+    }
+}
+
+/// How the delay between two attempts evolves.
+#[derive(Debug, Default, Clone, Copy, Hash, EnumIter, Display, Serialize, PartialEq, Eq)]
+pub enum RetryBackoff {
+    /// Always wait for the same delay.
+    #[default]
+    Constant,
+    /// Wait for the delay multiplied by the number of failed attempts.
+    Linear,
+    /// Double the delay after each failed attempt.
+    Exponential,
+}
+
+unit_enum_from_str!(RetryBackoff);
+unit_enum_deserialize!(RetryBackoff);
+
+/// Policy for executing a target again when it fails.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetRetry {
+    max_attempts: NonZeroUsize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay: Option<TimeSpan>,
+    #[serde(default)]
+    backoff: RetryBackoff,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_codes: Option<BTreeSet<i32>>,
+}
+
+impl TargetRetry {
+    /// Maximum number of attempts, including the first one.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts.get()
+    }
+
+    pub fn delay(&self) -> Option<&TimeSpan> {
+        self.delay.as_ref()
+    }
+
+    pub fn backoff(&self) -> RetryBackoff {
+        self.backoff
+    }
+
+    /// Exit codes that trigger a new attempt. Any failure triggers a new attempt if not set.
+    pub fn exit_codes(&self) -> Option<&BTreeSet<i32>> {
+        self.exit_codes.as_ref()
+    }
+
+    /// Delay to wait for after a given number of failed attempts.
+    pub fn delay_after(&self, failed_attempts: usize) -> Duration {
+        let delay = match &self.delay {
+            Some(delay) => delay.duration(),
+            None => return Duration::ZERO,
+        };
+        let failed_attempts = u32::try_from(failed_attempts.max(1)).unwrap_or(u32::MAX);
+        match self.backoff {
+            RetryBackoff::Constant => delay,
+            RetryBackoff::Linear => delay.saturating_mul(failed_attempts),
+            RetryBackoff::Exponential => {
+                delay.saturating_mul(2_u32.saturating_pow(failed_attempts - 1))
+            }
+        }
+    }
+}
+
+/// Environment variables of the processes started when executing a target.
+#[derive(Debug, Default, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TargetEnvironment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pass_through: Option<BTreeSet<String>>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+    #[serde(default)]
+    files: Vec<PathBuf>,
+}
+
+impl TargetEnvironment {
+    /// Variables of the Blaze process which are passed to executors.
+    /// When set, no other variable is inherited from the Blaze process.
+    pub fn pass_through(&self) -> Option<&BTreeSet<String>> {
+        self.pass_through.as_ref()
+    }
+
+    /// Static variables, which take precedence over variables loaded from files.
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// `.env` files to load variables from, relative to the project root.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
 }
 
 impl Target {
@@ -47,4 +190,16 @@ impl Target {
     pub fn stateless(&self) -> bool {
         self.stateless
     }
+
+    pub fn timeout(&self) -> Option<&TimeSpan> {
+        self.timeout.as_ref()
+    }
+
+    pub fn retry(&self) -> Option<&TargetRetry> {
+        self.retry.as_ref()
+    }
+
+    pub fn environment(&self) -> Option<&TargetEnvironment> {
+        self.environment.as_ref()
+    }
 }
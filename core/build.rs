@@ -94,6 +94,75 @@ fn main() {
         Err(_) => panic!("could not read blaze node bundle path"),
     };
 
+    // Python bridge
+    match std::env::var("BLAZE_PYTHON_BRIDGE_PATH").map(PathBuf::from) {
+        Ok(path) => {
+            let final_path = convert_env_path(&path);
+            if !final_path.is_file() {
+                panic!("{} is not a file", final_path.display());
+            }
+            println!(
+                "cargo::rustc-env=BLAZE_PYTHON_BRIDGE_PATH={}",
+                final_path.display()
+            );
+        }
+        Err(std::env::VarError::NotPresent) => {
+            let url = format!(
+                "https://files.pythonhosted.org/packages/source/b/blaze-python-bridge/blaze_python_bridge-{version}.tar.gz",
+            );
+            let script_path = PathBuf::from(format!(
+                "blaze_python_bridge-{version}/src/blaze_python_bridge/main.py"
+            ));
+
+            println!("fetching {url}");
+
+            let response =
+                reqwest::blocking::get(&url).expect("could not send python bridge package request");
+            let status = response.status();
+
+            if !status.is_success() {
+                panic!("error response from {url} (status={status})");
+            }
+
+            let mut archive = tar::Archive::new(GzDecoder::new(response));
+            let mut entries = archive
+                .entries()
+                .expect("could not get python bridge package entries");
+
+            let python_bridge_location = out_dir.join("python-bridge/main.py");
+            loop {
+                let mut entry = entries
+                    .next()
+                    .expect("could not find python bridge script in package")
+                    .expect("failed to read python bridge package entry");
+                let path = entry
+                    .path()
+                    .expect("could not get python bridge package entry path");
+                if *path == script_path {
+                    println!("found python bridge in package at {}", path.display());
+                    std::fs::create_dir_all(python_bridge_location.parent().unwrap())
+                        .expect("could not create python bridge output directory");
+                    std::io::copy(
+                        &mut entry,
+                        &mut std::fs::OpenOptions::new()
+                            .write(true)
+                            .create(true)
+                            .truncate(true)
+                            .open(&python_bridge_location)
+                            .expect("failed to open python bridge extracted file"),
+                    )
+                    .expect("failed to extract python bridge from package");
+                    break;
+                }
+            }
+            println!(
+                "cargo::rustc-env=BLAZE_PYTHON_BRIDGE_PATH={}",
+                python_bridge_location.display()
+            );
+        }
+        Err(_) => panic!("could not read blaze python bridge path"),
+    };
+
     // Rust bridge
     let rust_bridge_location = match std::env::var(
         "CARGO_BIN_FILE_BLAZE_RUST_BRIDGE_blaze-rust-bridge",
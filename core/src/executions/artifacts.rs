@@ -0,0 +1,154 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use blaze_common::{
+    cache::FileChangesMatcher,
+    error::Result,
+    logger::Logger,
+    value::{to_value, Value},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    system::glob::glob,
+    workspace::cache_store::{get_file_mode, CacheStore},
+};
+
+use super::{
+    check::{CacheInvalidationCheck, ExecutionCacheState},
+    execution::TargetExecution,
+};
+
+const ARTIFACTS_STATE_KEY: &str = "artifacts";
+
+/// Output files of an execution, associated with their checksum in the artifacts store.
+/// Paths are relative to the project root whenever possible.
+#[derive(Serialize, Deserialize)]
+struct ArtifactsManifest {
+    files: BTreeMap<PathBuf, ArtifactFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArtifactFile {
+    checksum: u64,
+    /// Unix permission bits of the file (its executable bit for example).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mode: Option<u32>,
+}
+
+/// Archives target outputs after a successful execution, and restores them when the cache is reused.
+pub struct ArtifactsCheck<'a> {
+    logger: &'a Logger,
+    cache: &'a CacheStore,
+    matchers: &'a BTreeSet<FileChangesMatcher>,
+}
+
+impl<'a> ArtifactsCheck<'a> {
+    pub fn new(
+        matchers: &'a BTreeSet<FileChangesMatcher>,
+        cache: &'a CacheStore,
+        logger: &'a Logger,
+    ) -> Self {
+        Self {
+            matchers,
+            cache,
+            logger,
+        }
+    }
+}
+
+impl CacheInvalidationCheck for ArtifactsCheck<'_> {
+    fn state(&self, execution: &TargetExecution) -> Result<Option<Value>> {
+        let project = execution.get_project();
+        let project_root = project.root();
+        let mut files = BTreeMap::new();
+
+        for matcher in self.matchers {
+            let paths = glob(
+                matcher.root().unwrap_or(project_root),
+                matcher.pattern(),
+                matcher.exclude().iter().map(|p| p.as_str()),
+            )?;
+
+            for path in paths {
+                let file = ArtifactFile {
+                    checksum: self.cache.store_artifact(&path)?,
+                    mode: get_file_mode(&path)?,
+                };
+                let key = path
+                    .strip_prefix(project_root)
+                    .map(|relative| relative.to_owned())
+                    .unwrap_or(path);
+                files.insert(key, file);
+            }
+        }
+
+        self.logger.debug(format!(
+            "{} output file(s) were stored for {execution}",
+            files.len()
+        ));
+
+        Ok(Some(Value::object([(
+            ARTIFACTS_STATE_KEY,
+            to_value(ArtifactsManifest { files })?,
+        )])))
+    }
+
+    fn validate(
+        &mut self,
+        execution: &TargetExecution,
+        cached: &ExecutionCacheState,
+    ) -> Result<bool> {
+        // manifests written by previous versions cannot be deserialized, and are considered invalid.
+        let maybe_manifest = cached
+            .metadata
+            .at(ARTIFACTS_STATE_KEY)
+            .and_then(|manifest| ArtifactsManifest::deserialize(manifest).ok());
+
+        let manifest = match maybe_manifest {
+            Some(manifest) => manifest,
+            None => return Ok(false),
+        };
+
+        let project = execution.get_project();
+
+        for (path, file) in manifest.files {
+            if !self.cache.contains_artifact(file.checksum)? {
+                self.logger.debug(format!(
+                    "artifact for {} is missing from the store",
+                    project.root().join(&path).display()
+                ));
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn restore(&self, execution: &TargetExecution, cached: &ExecutionCacheState) -> Result<bool> {
+        let manifest = match cached.metadata.at(ARTIFACTS_STATE_KEY) {
+            Some(manifest) => ArtifactsManifest::deserialize(manifest)?,
+            None => return Ok(false),
+        };
+
+        let project = execution.get_project();
+
+        for (path, file) in manifest.files {
+            let full_path = project.root().join(&path);
+            if !self
+                .cache
+                .restore_artifact(file.checksum, &full_path, file.mode)?
+            {
+                self.logger.debug(format!(
+                    "artifact for {} is missing from the store",
+                    full_path.display()
+                ));
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -13,6 +13,9 @@ pub struct ExecutionCacheState {
     #[serde(with = "system_time_as_timestamps")]
     pub time: SystemTime,
     pub metadata: Value,
+    /// Output of the cached execution.
+    #[serde(default)]
+    pub output: Value,
 }
 
 pub trait CacheInvalidationCheck {
@@ -32,4 +35,12 @@ pub trait CacheInvalidationCheck {
         execution: &TargetExecution,
         current_state: &ExecutionCacheState,
     ) -> Result<bool>;
+
+    /// Apply the cache state, once every check has validated it.
+    /// This method is only called when the whole cache is known to be valid, so that side effects never happen for an invalidated cache.
+    /// Returns false if the state could not be applied, in which case the target will be executed again.
+    /// By default, nothing is done.
+    fn restore(&self, _: &TargetExecution, _: &ExecutionCacheState) -> Result<bool> {
+        Ok(true)
+    }
 }
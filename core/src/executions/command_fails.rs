@@ -69,6 +69,7 @@ impl<'a> CommandFailsCheck<'a> {
                         .unwrap_or(execution.get_project().root())
                         .to_owned(),
                 ),
+                ..Default::default()
             },
         )?;
 
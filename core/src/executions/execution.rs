@@ -1,6 +1,8 @@
-use anyhow::bail;
+use anyhow::{bail, Context};
 use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::VecDeque,
     convert::identity,
     fmt::Display,
     hash::{Hash, Hasher},
@@ -14,11 +16,12 @@ use blaze_common::{
 
 use crate::{
     executions::{
+        artifacts::ArtifactsCheck,
         check::{CacheInvalidationCheck, ExecutionCacheState},
         command_fails::CommandFailsCheck,
-        file_changes::InputFileChangesCheck,
+        file_changes::OutputFileChangesCheck,
         files_missing::FilesMissingCheck,
-        propagating_children::PropagatingChildrenCheck,
+        fingerprint::{execution_fingerprint, FingerprintSources, InputFileHashes},
         ttl::TtlCheck,
     },
     executors::ExecutorCacheState,
@@ -26,14 +29,20 @@ use crate::{
     workspace::cache_store::CacheStore,
 };
 
-use super::{
-    env_changes::EnvChangesCheck, executor_update::ExecutorUpdateCheck,
-    file_changes::OutputFileChangesCheck,
-};
-
 const EXECUTIONS_STATE_KEY_PREFIX: &str = "executions";
 
-#[derive(Debug)]
+/// Maximum number of cached states kept for a single execution.
+const MAX_CACHED_STATES: usize = 10;
+
+/// References the fingerprints of all cached states for an execution, from the oldest to the most recent.
+#[derive(Default, Serialize, Deserialize)]
+struct ExecutionCacheIndex {
+    fingerprints: VecDeque<u64>,
+    #[serde(default)]
+    input_hashes: InputFileHashes,
+}
+
+#[derive(Debug, Clone)]
 pub struct TargetExecution {
     project: Arc<Project>,
     target_name: String,
@@ -52,11 +61,26 @@ impl PartialEq for TargetExecution {
 }
 
 pub enum CachedExecutionState<T> {
-    Cached(u64),
+    /// The execution was restored from cache, with its cached output.
+    Cached(u64, Value),
     New(u64, T),
     NoCache(T),
 }
 
+/// Result of an execution, which can provide an output to be stored along with the execution cache state.
+pub trait ExecutionOutput {
+    fn output(&self) -> Option<&Value>;
+}
+
+impl<T: ExecutionOutput> ExecutionOutput for CachedExecutionState<T> {
+    fn output(&self) -> Option<&Value> {
+        match self {
+            Self::Cached(_, output) => Some(output),
+            Self::New(_, result) | Self::NoCache(result) => result.output(),
+        }
+    }
+}
+
 pub struct CachedDependencyExecution<'a, T> {
     pub double: String,
     pub state: Option<&'a Result<CachedExecutionState<T>>>,
@@ -81,11 +105,6 @@ impl TargetExecution {
         })
     }
 
-    pub fn get_cache_key(&self) -> String {
-        let double = self.get_double();
-        format!("{EXECUTIONS_STATE_KEY_PREFIX}/{double}")
-    }
-
     /// Run the function *f* if this execution is not cached.
     /// The function must return a result so that this wrapper can update the target execution cache state according to success or failure.
     pub fn cached<T, F>(
@@ -96,6 +115,7 @@ impl TargetExecution {
         f: F,
     ) -> Result<CachedExecutionState<T>>
     where
+        T: ExecutionOutput,
         F: FnOnce() -> Result<T>,
     {
         let target = self.get_target();
@@ -105,74 +125,68 @@ impl TargetExecution {
             None => return Ok(CachedExecutionState::NoCache(f()?)),
         };
 
-        let cache_state_key = self.get_cache_key();
-
         let invalidation_strategy = target_cache.invalidate_when();
-        let mut checks: Vec<(&str, Box<dyn CacheInvalidationCheck>)> = vec![(
-            "child cache propagation",
-            Box::new(PropagatingChildrenCheck::new(child_executions)),
-        )];
-        checks.extend(
-            vec![
-                executor_cache.map(|(state, nonce)| {
-                    (
-                        "executor was updated",
-                        Box::new(ExecutorUpdateCheck::new(state, nonce, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.expired().map(|options| {
-                    (
-                        "ttl expired",
-                        Box::new(TtlCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.files_missing().map(|options| {
-                    (
-                        "files were missing",
-                        Box::new(FilesMissingCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.input_changes().map(|options| {
-                    (
-                        "input file(s) changed",
-                        Box::new(InputFileChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.output_changes().map(|options| {
-                    (
-                        "output file(s) changed",
-                        Box::new(OutputFileChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.command_fails().map(|options| {
-                    (
-                        "cache invalidation command failed",
-                        Box::new(CommandFailsCheck::new(options))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.env_changes().map(|options| {
-                    (
-                        "environment variables changed",
-                        Box::new(EnvChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-            ]
-            .into_iter()
-            .flatten(),
-        );
 
-        let mut hasher = hasher();
-        self.project.root().hash(&mut hasher);
-        self.get_target().hash(&mut hasher);
+        let mut index = self.restore_index(context.cache)?.unwrap_or_default();
+
+        let fingerprint = execution_fingerprint(
+            self,
+            context.workspace,
+            FingerprintSources {
+                children: child_executions,
+                executor_nonce: executor_cache.map(|(_, nonce)| nonce),
+                input_changes: invalidation_strategy.input_changes(),
+                env_changes: invalidation_strategy.env_changes(),
+                input_hashes: &mut index.input_hashes,
+            },
+        )
+        .with_context(|| format!("could not compute fingerprint for {self}"))?;
+
+        if index.input_hashes.is_updated() {
+            context.cache.cache(&self.get_cache_key(), &index)?;
+        }
 
-        let mut hasher_before_nonce = hasher.clone();
+        let cache_state_key = self.get_state_cache_key(fingerprint);
+
+        let mut checks: Vec<(&str, Box<dyn CacheInvalidationCheck>)> = vec![
+            invalidation_strategy.expired().map(|options| {
+                (
+                    "ttl expired",
+                    Box::new(TtlCheck::new(options, context.logger))
+                        as Box<dyn CacheInvalidationCheck>,
+                )
+            }),
+            target_cache.outputs().map(|matchers| {
+                (
+                    "output artifacts could not be restored",
+                    Box::new(ArtifactsCheck::new(matchers, context.cache, context.logger))
+                        as Box<dyn CacheInvalidationCheck>,
+                )
+            }),
+            invalidation_strategy.files_missing().map(|options| {
+                (
+                    "files were missing",
+                    Box::new(FilesMissingCheck::new(options, context.logger))
+                        as Box<dyn CacheInvalidationCheck>,
+                )
+            }),
+            invalidation_strategy.output_changes().map(|options| {
+                (
+                    "output file(s) changed",
+                    Box::new(OutputFileChangesCheck::new(options, context.logger))
+                        as Box<dyn CacheInvalidationCheck>,
+                )
+            }),
+            invalidation_strategy.command_fails().map(|options| {
+                (
+                    "cache invalidation command failed",
+                    Box::new(CommandFailsCheck::new(options)) as Box<dyn CacheInvalidationCheck>,
+                )
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
 
         let execute_and_cache =
             |checks: Vec<Box<dyn CacheInvalidationCheck>>| -> Result<CachedExecutionState<T>> {
@@ -192,8 +206,10 @@ impl TargetExecution {
                 }
 
                 let nonce = thread_rng().next_u64();
-                nonce.hash(&mut hasher_before_nonce);
-                let new_hash = hasher_before_nonce.finish();
+                let mut hasher = hasher();
+                fingerprint.hash(&mut hasher);
+                nonce.hash(&mut hasher);
+                let new_hash = hasher.finish();
                 context.cache.cache(
                     &cache_state_key,
                     &ExecutionCacheState {
@@ -201,22 +217,34 @@ impl TargetExecution {
                         hash: new_hash,
                         metadata,
                         time: now(),
+                        output: execution_result.output().cloned().unwrap_or(Value::Null),
                     },
                 )?;
+                self.index_fingerprint(index, fingerprint, context)?;
 
                 Ok(CachedExecutionState::New(new_hash, execution_result))
             };
 
+        if let Some((ExecutorCacheState::New | ExecutorCacheState::Updated, _)) = executor_cache {
+            context.logger.debug(format!(
+                "executor was updated, {self} cache will be invalidated"
+            ));
+            return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
+        }
+
         let maybe_last_execution = context
             .cache
             .restore::<ExecutionCacheState>(&cache_state_key)?;
 
-        if maybe_last_execution.is_none() {
-            context.logger.debug(format!("{self} was not cached."));
-            return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
-        }
-
-        let last_execution_state = maybe_last_execution.unwrap();
+        let last_execution_state = match maybe_last_execution {
+            Some(state) => state,
+            None => {
+                context.logger.debug(format!(
+                    "{self} was not cached with fingerprint {fingerprint:0>16x}."
+                ));
+                return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
+            }
+        };
 
         let is_cache_valid = checks
             .iter_mut()
@@ -239,17 +267,97 @@ impl TargetExecution {
             return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
         }
 
-        last_execution_state.nonce.hash(&mut hasher);
-        let current_execution_hash = hasher.finish();
+        let mut is_cache_restored = true;
+        for (reason, check) in &checks {
+            if !check.restore(self, &last_execution_state)? {
+                context
+                    .logger
+                    .debug(format!("{self} cache will be invalidated ({reason})"));
+                is_cache_restored = false;
+                break;
+            }
+        }
 
-        if last_execution_state.hash != current_execution_hash {
-            context.logger.debug(format!(
-                "{self} configuration changed, cache will be invalidated"
-            ));
+        if !is_cache_restored {
             return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
         }
 
-        Ok(CachedExecutionState::Cached(current_execution_hash))
+        Ok(CachedExecutionState::Cached(
+            last_execution_state.hash,
+            last_execution_state.output,
+        ))
+    }
+
+    /// Remove every cached state for this execution.
+    /// Returns false if nothing was cached.
+    pub fn invalidate_cache(&self, cache: &CacheStore) -> Result<bool> {
+        let index_key = self.get_cache_key();
+        let index = match self.restore_index(cache)? {
+            Some(index) => index,
+            None => return Ok(false),
+        };
+
+        for fingerprint in index.fingerprints {
+            cache.invalidate(&self.get_state_cache_key(fingerprint))?;
+        }
+        cache.invalidate(&index_key)?;
+
+        Ok(true)
+    }
+
+    /// Key of the index which references all cached states for this execution.
+    pub fn get_cache_key(&self) -> String {
+        let double = self.get_double();
+        format!("{EXECUTIONS_STATE_KEY_PREFIX}/{double}")
+    }
+
+    fn get_state_cache_key(&self, fingerprint: u64) -> String {
+        format!("{}/{fingerprint:0>16x}", self.get_cache_key())
+    }
+
+    fn restore_index(&self, cache: &CacheStore) -> Result<Option<ExecutionCacheIndex>> {
+        let key = self.get_cache_key();
+        match cache.restore::<ExecutionCacheIndex>(&key) {
+            Ok(index) => Ok(index),
+            // entries written by older versions used the same key for a single execution state.
+            Err(err)
+                if err
+                    .downcast_ref::<serde_cbor::Error>()
+                    .is_some_and(|err| !err.is_io()) =>
+            {
+                cache.invalidate(&key).with_context(|| {
+                    format!("could not remove unreadable cache index for {self}")
+                })?;
+                Ok(None)
+            }
+            Err(err) => {
+                Err(err).with_context(|| format!("could not restore cache index for {self}"))
+            }
+        }
+    }
+
+    /// Reference a new cached state in the execution index, and drop the oldest state if there are too many.
+    fn index_fingerprint(
+        &self,
+        mut index: ExecutionCacheIndex,
+        fingerprint: u64,
+        context: CachedExecutionContext<'_>,
+    ) -> Result<()> {
+        index.fingerprints.retain(|f| *f != fingerprint);
+        index.fingerprints.push_back(fingerprint);
+
+        while index.fingerprints.len() > MAX_CACHED_STATES {
+            if let Some(oldest) = index.fingerprints.pop_front() {
+                context
+                    .logger
+                    .debug(format!("dropping cached state {oldest:0>16x} for {self}"));
+                context
+                    .cache
+                    .invalidate(&self.get_state_cache_key(oldest))?;
+            }
+        }
+
+        context.cache.cache(&self.get_cache_key(), &index)
     }
 
     /// Synchronized pointer to the project data.
@@ -0,0 +1,218 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::Context;
+use blaze_common::{
+    cache::{EnvChangesOptions, FileChangesMatcher, MatchingBehavior},
+    dependency::CachePropagation,
+    error::Result,
+    workspace::Workspace,
+};
+use possibly::possibly;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    executors::env::get_target_env,
+    system::{
+        env::Env,
+        glob::glob,
+        hash::{hash_file, hasher},
+    },
+};
+
+use super::execution::{CachedDependencyExecution, CachedExecutionState, TargetExecution};
+
+/// Checksums of input files computed for previous fingerprints.
+/// Files matched with the `Mixed` behavior are only hashed again when their modification time or size changed.
+#[derive(Default, Serialize, Deserialize)]
+pub struct InputFileHashes {
+    files: BTreeMap<PathBuf, InputFileHash>,
+    #[serde(skip)]
+    updated: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InputFileHash {
+    mtime: u128,
+    len: u64,
+    hash: u64,
+}
+
+impl InputFileHashes {
+    fn hash_file(&mut self, path: &Path) -> Result<u64> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("could not get metadata for {}", path.display()))?;
+        let mtime = metadata
+            .modified()
+            .with_context(|| format!("could not get mtime for {}", path.display()))?
+            .duration_since(UNIX_EPOCH)?
+            .as_millis();
+        let len = metadata.len();
+
+        if let Some(known) = self
+            .files
+            .get(path)
+            .filter(|known| known.mtime == mtime && known.len == len)
+        {
+            return Ok(known.hash);
+        }
+
+        let hash = hash_file(path).with_context(|| format!("could not hash {}", path.display()))?;
+        self.files
+            .insert(path.to_owned(), InputFileHash { mtime, len, hash });
+        self.updated = true;
+        Ok(hash)
+    }
+
+    /// Drop checksums of files that are not matched anymore.
+    fn retain(&mut self, paths: &BTreeSet<PathBuf>) {
+        let count = self.files.len();
+        self.files.retain(|path, _| paths.contains(path));
+        self.updated |= count != self.files.len();
+    }
+
+    /// Whether checksums were added or removed since they were restored.
+    pub fn is_updated(&self) -> bool {
+        self.updated
+    }
+}
+
+/// Everything that an execution depends on, apart from the target configuration itself.
+pub struct FingerprintSources<'a, T> {
+    pub children: &'a [CachedDependencyExecution<'a, T>],
+    pub executor_nonce: Option<u64>,
+    pub input_changes: Option<&'a BTreeSet<FileChangesMatcher>>,
+    pub env_changes: Option<&'a EnvChangesOptions>,
+    pub input_hashes: &'a mut InputFileHashes,
+}
+
+/// Compute a fingerprint for an execution, which identifies the state of its inputs.
+/// Two executions with the same fingerprint are expected to produce the same results.
+pub fn execution_fingerprint<T>(
+    execution: &TargetExecution,
+    workspace: &Workspace,
+    sources: FingerprintSources<'_, T>,
+) -> Result<u64> {
+    let project = execution.get_project();
+    let mut hasher = hasher();
+
+    project
+        .root()
+        .strip_prefix(workspace.root())
+        .unwrap_or(project.root())
+        .hash(&mut hasher);
+    execution.get_target().hash(&mut hasher);
+
+    sources.executor_nonce.hash(&mut hasher);
+
+    let children = sources
+        .children
+        .iter()
+        .filter(|child| child.source.cache_propagation() != CachePropagation::Never)
+        .filter_map(|child| {
+            possibly!(
+                child.state,
+                Some(Ok(CachedExecutionState::Cached(hash, _)|CachedExecutionState::New(hash, _))) => (child.double.as_str(), *hash)
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    children.hash(&mut hasher);
+
+    if let Some(matchers) = sources.input_changes {
+        let mut matched = BTreeSet::new();
+        for matcher in matchers {
+            hash_input_files(
+                project.root(),
+                matcher,
+                sources.input_hashes,
+                &mut matched,
+                &mut hasher,
+            )
+            .with_context(|| {
+                format!(
+                    "error while computing fingerprint for input files matching {}",
+                    matcher.pattern()
+                )
+            })?;
+        }
+        sources.input_hashes.retain(&matched);
+    }
+
+    if let Some(options) = sources.env_changes {
+        for variable in options.variables() {
+            variable.hash(&mut hasher);
+            Env::get_as_str(variable)?.hash(&mut hasher);
+        }
+    }
+
+    // variables passed through or loaded from files are not part of the target configuration.
+    if let Some(environment) = execution.get_target().environment() {
+        get_target_env(environment, project.root())
+            .context("could not get target environment")?
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .hash(&mut hasher);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Compute a hash of all files matched by input matchers, according to their matching behavior.
+pub fn input_files_hash(
+    default_root: &Path,
+    matchers: &BTreeSet<FileChangesMatcher>,
+) -> Result<u64> {
+    let mut hasher = hasher();
+    let mut input_hashes = InputFileHashes::default();
+    let mut matched = BTreeSet::new();
+    for matcher in matchers {
+        hash_input_files(
+            default_root,
+            matcher,
+            &mut input_hashes,
+            &mut matched,
+            &mut hasher,
+        )?;
+    }
+    Ok(hasher.finish())
+}
+
+fn hash_input_files<H: Hasher>(
+    default_root: &Path,
+    matcher: &FileChangesMatcher,
+    input_hashes: &mut InputFileHashes,
+    matched: &mut BTreeSet<PathBuf>,
+    hasher: &mut H,
+) -> Result<()> {
+    let root = matcher.root().unwrap_or(default_root);
+    let paths = glob(
+        root,
+        matcher.pattern(),
+        matcher.exclude().iter().map(|p| p.as_str()),
+    )?
+    .into_iter()
+    .collect::<BTreeSet<_>>();
+
+    for path in paths {
+        path.strip_prefix(root).unwrap_or(&path).hash(hasher);
+        match matcher.behavior() {
+            MatchingBehavior::Timestamps => std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .with_context(|| format!("could not get mtime for {}", path.display()))?
+                .hash(hasher),
+            MatchingBehavior::Hash => hash_file(&path)
+                .with_context(|| format!("could not hash {}", path.display()))?
+                .hash(hasher),
+            MatchingBehavior::Mixed => {
+                input_hashes.hash_file(&path)?.hash(hasher);
+                matched.insert(path);
+            }
+        }
+    }
+
+    Ok(())
+}
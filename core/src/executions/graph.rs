@@ -19,7 +19,7 @@ use crate::{
     system::parallel_executor::ParallelRunner,
     workspace::{
         configurations::DeserializationContext,
-        project_handle::{ProjectHandle, ProjectOptions},
+        project_handle::{LoadedProjects, ProjectHandle, ProjectOptions},
         selection::{Selection, SelectionContext, SelectorSource},
     },
 };
@@ -36,10 +36,33 @@ pub struct ExecutionGraph {
     dependency_graph: DependencyGraph,
 }
 
+impl Clone for ExecutionGraph {
+    /// Target executions are not shared with the original graph, so that both graphs can be executed.
+    fn clone(&self) -> Self {
+        Self {
+            dependency_graph: self
+                .dependency_graph
+                .iter()
+                .map(|(double, node)| {
+                    (
+                        double.clone(),
+                        DependencyGraphNode {
+                            target_execution: Arc::new(node.target_execution.as_ref().clone()),
+                            ..node.clone()
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Data needed when instanciating an [`ExecutionGraph`].
 pub struct ExecutionGraphOptions<'a> {
     pub workspace: &'a Workspace,
     pub deserialization_context: DeserializationContext<'a>,
+    /// Projects are only loaded if they are not found here.
+    pub loaded_projects: &'a LoadedProjects,
     pub max_depth: Option<usize>,
 }
 
@@ -185,30 +208,27 @@ impl ExecutionGraph {
                     let refs = selection
                         .select(SelectionContext {
                             workspace: options.workspace,
+                            deserialization_context: options.deserialization_context,
+                            loaded_projects: options.loaded_projects,
                         })
                         .context("could not select projects")?;
 
                     for (name, project_ref) in &refs {
                         if !projects.contains_key(*name) {
-                            projects.insert(
-                                name.to_string(),
-                                Arc::new(
-                                    ProjectHandle::from_root(
-                                        options.workspace.root().join(project_ref.path()),
-                                        ProjectOptions {
-                                            name,
-                                            deserialization_context: options
-                                                .deserialization_context,
-                                        },
-                                    )
-                                    .with_context(|| {
-                                        format!(
-                                            "error while reading \"{name}\" project configuration"
-                                        )
-                                    })?
-                                    .unwrap_inner(),
-                                ),
-                            );
+                            let project = options.loaded_projects.get_or_try_load(name, || {
+                                Ok(ProjectHandle::from_root(
+                                    options.workspace.root().join(project_ref.path()),
+                                    ProjectOptions {
+                                        name,
+                                        deserialization_context: options.deserialization_context,
+                                    },
+                                )
+                                .with_context(|| {
+                                    format!("error while reading \"{name}\" project configuration")
+                                })?
+                                .unwrap_inner())
+                            })?;
+                            projects.insert(name.to_string(), project);
                         }
                     }
                     refs.keys().map(|name| name.as_str()).collect::<Vec<_>>()
@@ -307,13 +327,23 @@ impl ExecutionGraph {
         self.dependency_graph.keys().map(String::as_str).collect()
     }
 
+    /// Get all target executions in this graph.
+    pub fn executions(&self) -> impl Iterator<Item = &TargetExecution> {
+        self.dependency_graph
+            .values()
+            .map(|node| node.target_execution.as_ref())
+    }
+
     /// Execute all targets using this graph with the specified parallelism level and execution routine.
+    /// When *fail_fast* is true, no more targets are scheduled as soon as one of them fails.
+    /// Otherwise, only targets depending on the failed target are canceled.
     pub fn execute<
         T: Send + Sync + UnwindSafe + RefUnwindSafe,
         F: Fn(&TargetExecution, &[ChildExecutionResult<T>]) -> Result<T> + Clone + UnwindSafe + Send,
     >(
         self,
         parallelism: Parallelism,
+        fail_fast: bool,
         execution_routine: F,
     ) -> Result<ExecutedGraph<T>> {
         scope(|scope| {
@@ -409,6 +439,11 @@ impl ExecutionGraph {
                         continue;
                     }
 
+                    if fail_fast {
+                        canceled.extend(pending.drain());
+                        continue;
+                    }
+
                     let mut to_cancel = HashSet::<&String>::with_capacity(pending.len());
 
                     let mut next_children = HashSet::<&String>::from_iter([&done_double]);
@@ -485,6 +520,29 @@ impl ExecutionGraph {
         })
     }
 
+    /// Get the provided doubles along with all the doubles depending on them, directly or not.
+    pub fn with_dependents<'s, I: IntoIterator<Item = &'s str>>(
+        &self,
+        doubles: I,
+    ) -> HashSet<String> {
+        let inverted_dependencies = self.create_inverted_dependency_graph();
+
+        let mut selected = HashSet::<String>::new();
+        let mut pending = doubles
+            .into_iter()
+            .filter_map(|double| self.dependency_graph.get_key_value(double))
+            .map(|(double, _)| double)
+            .collect::<VecDeque<_>>();
+
+        while let Some(double) = pending.pop_front() {
+            if selected.insert(double.to_owned()) {
+                pending.extend(inverted_dependencies[double].keys().copied());
+            }
+        }
+
+        selected
+    }
+
     /// Create an inverted dependencies graph (from dependency to parents).
     /// Keys are dependencies doubles and values are maps where keys are parent target names and values are dependency configuration accessors.
     fn create_inverted_dependency_graph(
@@ -0,0 +1,246 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use blaze_common::{cache::FileChangesMatcher, error::Result};
+use serde::Serialize;
+
+use crate::system::glob::glob;
+
+use super::execution::TargetExecution;
+
+/// Blaze files of the workspace, which are never reported.
+const BLAZE_FOLDER: &str = ".blaze";
+
+/// Files accessed by a target execution which are not covered by its cache configuration.
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HermeticViolations {
+    /// Files created, modified or removed outside of declared outputs.
+    pub undeclared_writes: BTreeSet<PathBuf>,
+    /// Files read without being covered by declared inputs or outputs (only detected on Linux).
+    pub undeclared_reads: BTreeSet<PathBuf>,
+}
+
+impl HermeticViolations {
+    pub fn is_empty(&self) -> bool {
+        self.undeclared_writes.is_empty() && self.undeclared_reads.is_empty()
+    }
+}
+
+/// Size and modification time of each file in a directory tree.
+type Snapshot = HashMap<PathBuf, (u64, SystemTime)>;
+
+/// Verification of the declared inputs and outputs of a target, while it is being executed.
+/// The project tree is compared before and after execution in order to detect writes, and file accesses are traced in order to detect reads.
+pub struct HermeticCheck {
+    project_root: PathBuf,
+    excluded: PathBuf,
+    input_matchers: BTreeSet<FileChangesMatcher>,
+    output_matchers: BTreeSet<FileChangesMatcher>,
+    inputs: HashSet<PathBuf>,
+    outputs: HashSet<PathBuf>,
+    snapshot: Snapshot,
+    tracker: AccessTracker,
+}
+
+impl HermeticCheck {
+    /// Start checking a target execution. [`None`] is returned if the target has no cache configuration, since there is nothing to verify.
+    pub fn start(execution: &TargetExecution, workspace_root: &Path) -> Result<Option<Self>> {
+        let cache = match execution.get_target().cache() {
+            Some(cache) => cache,
+            None => return Ok(None),
+        };
+
+        let project = execution.get_project();
+        let project_root = project.root();
+
+        let input_matchers = cache
+            .invalidate_when()
+            .input_changes()
+            .cloned()
+            .unwrap_or_default();
+
+        let output_matchers = cache
+            .invalidate_when()
+            .output_changes()
+            .into_iter()
+            .chain(cache.outputs())
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let excluded = workspace_root.join(BLAZE_FOLDER);
+        let inputs = matched_files(project_root, &input_matchers)?;
+        let outputs = matched_files(project_root, &output_matchers)?;
+        let snapshot = snapshot(project_root, &excluded)?;
+        let tracker = AccessTracker::start(project_root)?;
+
+        Ok(Some(Self {
+            project_root: project_root.to_owned(),
+            excluded,
+            input_matchers,
+            output_matchers,
+            inputs,
+            outputs,
+            snapshot,
+            tracker,
+        }))
+    }
+
+    /// Stop checking the target execution and collect the violations.
+    pub fn finish(mut self) -> Result<HermeticViolations> {
+        let opened = self.tracker.stop();
+
+        let after = snapshot(&self.project_root, &self.excluded)?;
+        self.inputs
+            .extend(matched_files(&self.project_root, &self.input_matchers)?);
+        self.outputs
+            .extend(matched_files(&self.project_root, &self.output_matchers)?);
+
+        let written = self
+            .snapshot
+            .iter()
+            .filter(|(path, state)| after.get(*path) != Some(state))
+            .map(|(path, _)| path)
+            .chain(
+                after
+                    .keys()
+                    .filter(|path| !self.snapshot.contains_key(*path)),
+            )
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let undeclared_reads = opened
+            .into_iter()
+            .filter(|path| {
+                (self.snapshot.contains_key(path) || after.contains_key(path))
+                    && !written.contains(path)
+                    && !self.inputs.contains(path)
+                    && !self.outputs.contains(path)
+            })
+            .collect();
+
+        let undeclared_writes = written
+            .into_iter()
+            .filter(|path| !self.outputs.contains(path))
+            .collect();
+
+        Ok(HermeticViolations {
+            undeclared_writes,
+            undeclared_reads,
+        })
+    }
+}
+
+fn matched_files(
+    default_root: &Path,
+    matchers: &BTreeSet<FileChangesMatcher>,
+) -> Result<HashSet<PathBuf>> {
+    let mut files = HashSet::new();
+    for matcher in matchers {
+        files.extend(glob(
+            matcher.root().unwrap_or(default_root),
+            matcher.pattern(),
+            matcher.exclude().iter().map(String::as_str),
+        )?);
+    }
+    Ok(files)
+}
+
+fn snapshot(root: &Path, excluded: &Path) -> Result<Snapshot> {
+    let mut snapshot = Snapshot::new();
+    let mut directories = vec![root.to_owned()];
+
+    while let Some(directory) = directories.pop() {
+        let entries = std::fs::read_dir(&directory)
+            .with_context(|| format!("could not read directory {}", directory.display()))?;
+
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if path.starts_with(excluded) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                directories.push(path);
+            } else if metadata.is_file() {
+                snapshot.insert(path, (metadata.len(), metadata.modified()?));
+            }
+        }
+    }
+
+    Ok(snapshot)
+}
+
+#[cfg(target_os = "linux")]
+use access_tracker::AccessTracker;
+
+#[cfg(target_os = "linux")]
+mod access_tracker {
+    use std::{
+        collections::HashSet,
+        path::{Path, PathBuf},
+        sync::mpsc::{channel, Receiver},
+        time::Duration,
+    };
+
+    use anyhow::Context;
+    use blaze_common::error::Result;
+    use notify::{
+        event::{AccessKind, EventKind},
+        Event, RecommendedWatcher, RecursiveMode, Watcher,
+    };
+
+    /// How long to wait for pending file system events once the execution is done.
+    const PENDING_EVENTS_DELAY: Duration = Duration::from_millis(100);
+
+    /// Traces files opened in a directory tree, using inotify.
+    pub struct AccessTracker {
+        watcher: RecommendedWatcher,
+        events: Receiver<notify::Result<Event>>,
+    }
+
+    impl AccessTracker {
+        pub fn start(root: &Path) -> Result<Self> {
+            let (sender, events) = channel();
+            let mut watcher = RecommendedWatcher::new(sender, notify::Config::default())
+                .context("could not create file system watcher")?;
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .with_context(|| format!("could not watch {}", root.display()))?;
+            Ok(Self { watcher, events })
+        }
+
+        /// Stop tracing and return the opened files.
+        pub fn stop(self) -> HashSet<PathBuf> {
+            std::thread::sleep(PENDING_EVENTS_DELAY);
+            drop(self.watcher);
+            self.events
+                .try_iter()
+                .flatten()
+                .filter(|event| matches!(event.kind, EventKind::Access(AccessKind::Open(_))))
+                .flat_map(|event| event.paths)
+                .collect()
+        }
+    }
+}
+
+/// File accesses cannot be traced on this platform, so reads are never reported.
+#[cfg(not(target_os = "linux"))]
+struct AccessTracker;
+
+#[cfg(not(target_os = "linux"))]
+impl AccessTracker {
+    fn start(_: &Path) -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn stop(self) -> HashSet<PathBuf> {
+        HashSet::new()
+    }
+}
@@ -1,10 +1,10 @@
+pub mod artifacts;
 pub mod check;
 pub mod command_fails;
-pub mod env_changes;
 pub mod execution;
-pub mod executor_update;
 pub mod file_changes;
 pub mod files_missing;
+pub mod fingerprint;
 pub mod graph;
-pub mod propagating_children;
+pub mod hermetic;
 pub mod ttl;
@@ -1,4 +1,4 @@
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
 use anyhow::bail;
 use blaze_common::{
@@ -67,15 +67,13 @@ impl CacheInvalidationCheck for TtlCheck<'_> {
             bail!("ttl cannot be zero")
         }
 
-        let duration = match self.options.unit() {
-            TimeUnit::Milliseconds => Duration::from_millis(amount_u64),
-            TimeUnit::Seconds => Duration::from_secs(amount_u64),
-            TimeUnit::Minutes => Duration::from_secs(amount_u64 * 60),
-            TimeUnit::Hours => Duration::from_secs(amount_u64 * 60 * 60),
-            TimeUnit::Days => Duration::from_secs(amount_u64 * 60 * 60 * 24),
-        };
+        let duration = self.options.unit().duration(amount_u64);
 
-        let is_expired = last_state.at + duration <= now;
+        // a TTL which cannot be represented as a point in time never expires.
+        let is_expired = last_state
+            .at
+            .checked_add(duration)
+            .is_some_and(|expiration| expiration <= now);
 
         if is_expired {
             self.logger.debug(format!(
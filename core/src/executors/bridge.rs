@@ -1,31 +1,52 @@
 use std::{
-    io::{BufRead, BufReader},
-    path::PathBuf,
-    thread::scope,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    io::{BufRead, BufReader, Write},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::Ordering,
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{scope, JoinHandle},
+    time::{Duration, Instant},
 };
 
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use blaze_common::{
+    bridge::{
+        CancelParams, ExecuteResult, ExecutorErrorData, InitializeParams, LogParams,
+        ProgressParams, ResolvePathParams, ResolvePathResult, RpcError, RpcMessage, RpcMessageKind,
+        CANCEL_METHOD, EXECUTE_METHOD, INITIALIZE_METHOD, INTERNAL_ERROR, INVALID_PARAMS_ERROR,
+        LOG_METHOD, METHOD_NOT_FOUND_ERROR, PROGRESS_METHOD, PROTOCOL_VERSION, RESOLVE_PATH_METHOD,
+        SHUTDOWN_METHOD, UNSUPPORTED_VERSION_ERROR,
+    },
     error::{Error, Result},
-    logger::{LogLevel, Logger},
-    value::Value,
+    logger::Logger,
+    value::{to_value, Value},
 };
-
-use serde::{Deserialize, Serialize};
+use interprocess::local_socket::SendHalf;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::system::{
-    ipc_server::IpcServer,
-    process::{Process, ProcessOptions, ProcessStatus},
+    hash::hasher,
+    ipc_server::{IpcClientConnection, IpcServer},
+    process::{Process, ProcessFailure, ProcessOptions, ProcessStatus},
+    sandbox::Sandbox,
+    thread::{join, thread},
 };
 
-use super::{env::get_executor_env, ExecutorContext};
+use super::{
+    capabilities::{default_sandbox, ExecutorCapabilities},
+    env::{get_executor_env, isolates_environment},
+    ExecutorContext,
+};
 
-#[derive(Serialize)]
-pub struct BridgedExecutorContext<'a> {
-    #[serde(flatten)]
-    context: ExecutorContext<'a>,
-    logger: PathBuf,
-}
+/// How often cancellation is checked while an executor is running.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a bridge process can keep running a canceled execution before it is killed.
+const CANCELLATION_GRACE_PERIOD: Duration = Duration::from_secs(2);
 
 pub type ExecutorParams<'a> = (ExecutorContext<'a>, &'a Value);
 
@@ -33,108 +54,633 @@ pub struct BridgeProcessParams<'p> {
     pub program: &'p str,
     pub arguments: &'p [String],
     pub input: Option<&'p [u8]>,
+    /// When set, the bridge process is restricted to these capabilities.
+    pub capabilities: Option<&'p ExecutorCapabilities>,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BridgeInputMessage<'a, T: Serialize> {
-    executor_params: (BridgedExecutorContext<'a>, &'a Value),
-    metadata: T,
+struct ExecuteParams<'a, T: Serialize> {
+    executor_params: (&'a ExecutorContext<'a>, &'a Value),
+    metadata: &'a T,
 }
 
+pub(super) type RpcResult = std::result::Result<Value, RpcError>;
+
+/// Run an executor in a bridge process.
+/// If a worker pool is available in the context, the execution is dispatched to a persistent bridge process.
+/// Otherwise, a bridge process is started for this execution only.
+/// Sandboxed executors (with declared capabilities, or resolved from a remote location) always get their own bridge process, since their restrictions depend on the project.
+/// This is also the case for targets which declare an environment, since it cannot be changed once the process is started.
 pub fn bridge_executor<T: Serialize>(
     (context, options): ExecutorParams<'_>,
     process_params: BridgeProcessParams<'_>,
     metadata: T,
-) -> Result<()> {
-    scope(|scope| {
-        let logger_0 = context.logger.clone();
-        let logger_1 = context.logger.clone();
-
-        let logs = IpcServer::create(
-            scope,
-            move |connection| {
-                process_logs(BufReader::new(connection), &logger_0)
-                    .context("failure while parsing executor bridge process logs")
-            },
-            move |err: Error| {
-                logger_1.error(format!("executor bridge ipc error: {err:?}"));
-            },
-        )
-        .context("error while creating executor logs pipe")?;
-
-        let project_root_directory = context.project.root().to_path_buf();
-
-        let process_env = get_executor_env(&context)?;
-
-        let process_input = BridgeInputMessage {
-            executor_params: (
-                BridgedExecutorContext {
-                    context,
-                    logger: logs.get_path().to_path_buf(),
-                },
-                options,
-            ),
-            metadata,
-        };
+) -> Result<Value> {
+    let params = to_value(ExecuteParams {
+        executor_params: (&context, options),
+        metadata: &metadata,
+    })
+    .context("could not serialize bridge parameters")?;
+
+    let sandbox = match process_params.capabilities {
+        Some(capabilities) => Some(capabilities.sandbox(&context)?),
+        None if context.remote => Some(default_sandbox(&context)?),
+        None => None,
+    };
+    let sandboxed = sandbox.is_some();
+    let has_environment = context
+        .project
+        .targets()
+        .get(context.target)
+        .is_some_and(|target| target.environment().is_some());
+
+    if let Some(workers) = context.workers.filter(|_| !sandboxed && !has_environment) {
+        return workers.execute(
+            &context,
+            &process_params,
+            get_worker_key(&process_params, &metadata)?,
+            params,
+        );
+    }
+
+    let worker = BridgeWorker::spawn(
+        &process_params,
+        BridgeWorkerOptions {
+            cwd: context.project.root(),
+            environment: get_executor_env(&context)?,
+            clear_environment: isolates_environment(&context),
+            logger: context.logger,
+            workspace_root: context.workspace.root(),
+            sandbox,
+        },
+    )?;
 
-        let serialized_input_message = serde_json::to_string(&process_input)
-            .context("could not serialize bridge parameters")?;
+    let result = worker.execute(&context, params);
 
+    worker.shutdown()?;
+    let status = worker.join()?;
+
+    match (status, result?) {
+        (ProcessStatus { success: true, .. }, Some(Ok(result))) => {
+            Ok(parse_content::<ExecuteResult>(result)?.output)
+        }
+        (_, Some(Err(error))) => Err(executor_error(error)),
+        (ProcessStatus { success: true, .. }, None) => {
+            bail!("bridge process terminated without sending the executor result.")
+        }
+        (ProcessStatus { code, .. }, _) => {
+            Err(Error::new(ProcessFailure { code }).context(match code {
+                Some(code) => format!("bridge process failed with status code {code}."),
+                None => "bridge process was terminated.".into(),
+            }))
+        }
+    }
+}
+
+/// Get the executor output from the result of an execution in a persistent bridge process.
+pub(super) fn get_worker_output(result: Option<RpcResult>) -> Result<Value> {
+    match result {
+        Some(Ok(result)) => Ok(parse_content::<ExecuteResult>(result)?.output),
+        Some(Err(error)) => Err(executor_error(error)),
+        None => bail!("bridge worker process terminated before sending the executor result."),
+    }
+}
+
+/// Identifies the bridge process an execution can be dispatched to.
+fn get_worker_key<T: Serialize>(
+    process_params: &BridgeProcessParams<'_>,
+    metadata: &T,
+) -> Result<u64> {
+    let mut hasher = hasher();
+    process_params.program.hash(&mut hasher);
+    process_params.arguments.hash(&mut hasher);
+    serde_json::to_string(metadata)
+        .context("could not serialize bridge metadata")?
+        .hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+pub(super) struct BridgeWorkerOptions<'a> {
+    pub cwd: &'a Path,
+    pub environment: HashMap<String, String>,
+    /// Whether the environment of the Blaze process is not inherited.
+    pub clear_environment: bool,
+    /// Used for messages which are not related to a specific execution.
+    pub logger: &'a Logger,
+    pub workspace_root: &'a Path,
+    /// Restrictions applied to the bridge process.
+    pub sandbox: Option<Sandbox>,
+}
+
+/// A bridge process, which can run several executions until it is shut down.
+pub(super) struct BridgeWorker {
+    session: Arc<BridgeSession>,
+    process: Arc<Process>,
+    handle: Mutex<Option<JoinHandle<Result<ProcessStatus>>>>,
+}
+
+impl BridgeWorker {
+    /// Start a bridge process. It is managed in a separate thread until it terminates.
+    pub fn spawn(
+        process_params: &BridgeProcessParams<'_>,
+        options: BridgeWorkerOptions<'_>,
+    ) -> Result<Self> {
+        let session = Arc::new(BridgeSession {
+            logger: options.logger.clone(),
+            workspace_root: options.workspace_root.to_owned(),
+            sender: Mutex::default(),
+            state: Mutex::default(),
+        });
+
+        let program = process_params.program.to_owned();
         let mut arguments = process_params.arguments.to_vec();
-        arguments.push(serialized_input_message);
-
-        let mut process = Process::run_with_options(
-            process_params.program,
-            arguments,
-            ProcessOptions {
-                cwd: Some(project_root_directory),
-                display_output: true,
-                environment: process_env,
+        let input = process_params.input.map(<[u8]>::to_vec);
+        let cwd = options.cwd.to_owned();
+        let environment = options.environment;
+        let clear_environment = options.clear_environment;
+        let sandbox = options.sandbox;
+
+        let (started_sender, started_receiver) = channel::<Arc<Process>>();
+        let thread_session = session.clone();
+
+        let handle = thread!(move || -> Result<ProcessStatus> {
+            scope(|scope| {
+                let server_session = thread_session.clone();
+                let logger = thread_session.logger.clone();
+
+                let server = IpcServer::create(
+                    scope,
+                    move |connection| {
+                        server_session
+                            .serve(connection)
+                            .context("failure while communicating with executor bridge process")
+                    },
+                    move |err: Error| {
+                        logger.error(format!("executor bridge ipc error: {err:?}"));
+                    },
+                )
+                .context("error while creating executor bridge socket")?;
+
+                let run = || -> Result<ProcessStatus> {
+                    arguments.push(
+                        server
+                            .get_path()
+                            .to_str()
+                            .ok_or_else(|| anyhow!("invalid socket path {server}"))?
+                            .to_owned(),
+                    );
+
+                    let mut process = Process::run_with_command_hook(
+                        program,
+                        arguments,
+                        ProcessOptions {
+                            cwd: Some(cwd),
+                            display_output: true,
+                            environment,
+                            clear_environment,
+                        },
+                        |command| {
+                            if let Some(sandbox) = &sandbox {
+                                sandbox.apply(command);
+                            }
+                        },
+                    )
+                    .context("error while creating executor process")?;
+
+                    if let Some(input) = input {
+                        process
+                            .stdin_write(&input)
+                            .context("error while writing to process stdin.")?;
+                    }
+
+                    let process = Arc::new(process);
+                    let _ = started_sender.send(process.clone());
+
+                    process
+                        .wait()
+                        .context("error while waiting for bridge process termination.")
+                };
+
+                let status = run();
+
+                thread_session.terminate();
+
+                server
+                    .close()
+                    .context("could not close executor bridge socket.")?;
+
+                status
+            })
+        });
+
+        let process = match started_receiver.recv() {
+            Ok(process) => process,
+            Err(_) => {
+                return Err(match join!(handle) {
+                    Err(err) => err,
+                    Ok(_) => anyhow!("bridge process could not be started."),
+                })
+            }
+        };
+
+        Ok(Self {
+            session,
+            process,
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+
+    /// Run an execution and wait for its result.
+    /// [`None`] is returned if the bridge process terminated before sending the result.
+    pub fn execute(
+        &self,
+        context: &ExecutorContext<'_>,
+        params: Value,
+    ) -> Result<Option<RpcResult>> {
+        let (id, receiver) = self.session.submit(
+            context.logger.clone(),
+            context.project.root().to_owned(),
+            params,
+        )?;
+
+        let mut cancellation_sent_at = None::<Instant>;
+        let mut killed = false;
+
+        loop {
+            match receiver.recv_timeout(CANCELLATION_POLL_INTERVAL) {
+                Ok(result) => return Ok(Some(result)),
+                Err(RecvTimeoutError::Disconnected) => return Ok(None),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+
+            if let Some(sent_at) = cancellation_sent_at {
+                // executors which ignore cancellation must not block the run, even in a persistent worker.
+                if !killed && sent_at.elapsed() > CANCELLATION_GRACE_PERIOD {
+                    context.logger.warn(
+                        "executor did not stop after cancellation, its bridge process will be killed",
+                    );
+                    self.process.kill()?;
+                    killed = true;
+                }
+                continue;
+            }
+
+            if context
+                .canceled
+                .is_some_and(|canceled| canceled.load(Ordering::SeqCst))
+            {
+                cancellation_sent_at = Some(Instant::now());
+                if let Err(err) = self.session.send(RpcMessage::notification(
+                    CANCEL_METHOD,
+                    to_value(CancelParams { execution: id })?,
+                )) {
+                    context.logger.warn(format!(
+                        "could not send cancellation to bridge process ({err})"
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Whether the bridge process has terminated (or closed its connection).
+    pub fn is_terminated(&self) -> bool {
+        self.session
+            .state()
+            .map(|state| state.terminated)
+            .unwrap_or(true)
+    }
+
+    /// Ask the bridge process to exit once all executions are done.
+    pub fn shutdown(&self) -> Result<()> {
+        self.session.shutdown()
+    }
+
+    /// Wait for the bridge process termination.
+    pub fn join(&self) -> Result<ProcessStatus> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on bridge worker thread)."))?
+            .take()
+            .ok_or_else(|| anyhow!("bridge worker was already joined."))?;
+        join!(handle)
+    }
+}
+
+/// State of the communication with a bridge process.
+struct BridgeSession {
+    logger: Logger,
+    workspace_root: PathBuf,
+    sender: Mutex<Option<SendHalf>>,
+    state: Mutex<SessionState>,
+}
+
+#[derive(Default)]
+struct SessionState {
+    initialized: bool,
+    shutdown: bool,
+    terminated: bool,
+    next_id: u64,
+    /// Requests waiting for the bridge process to be initialized.
+    queued: Vec<RpcMessage>,
+    /// Running executions, indexed by their `execute` request ID.
+    executions: HashMap<u64, BridgeExecution>,
+}
+
+struct BridgeExecution {
+    logger: Logger,
+    project_root: PathBuf,
+    result: Sender<RpcResult>,
+}
+
+impl BridgeSession {
+    fn serve(&self, connection: IpcClientConnection) -> Result<()> {
+        let (receiver, sender) = connection.split();
+
+        let _ = self
+            .sender
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on bridge sender)."))?
+            .insert(sender);
+
+        for line in BufReader::new(receiver).lines() {
+            let line = line.context("error occured while reading bridge message")?;
+            let message = serde_json::from_str::<RpcMessage>(&line)
+                .with_context(|| format!("error occurred while parsing bridge message: {line}"))?;
+
+            match message.into_kind() {
+                RpcMessageKind::Request { id, method, params } => {
+                    let result = self.handle_request(&method, params);
+                    let accepted = method == INITIALIZE_METHOD && result.is_ok();
+                    self.send(RpcMessage::response(id, result))?;
+                    if accepted {
+                        self.initialized()?;
+                    }
+                }
+                RpcMessageKind::Notification { method, params } => {
+                    self.handle_notification(&method, params)?
+                }
+                RpcMessageKind::Response { id, result } => {
+                    match self.state()?.executions.remove(&id) {
+                        Some(execution) => {
+                            let _ = execution.result.send(result);
+                        }
+                        None => self
+                            .logger
+                            .debug(format!("unexpected bridge response (id={id})")),
+                    }
+                }
+                RpcMessageKind::Invalid => {
+                    self.logger.debug(format!("invalid bridge message: {line}"));
+                }
+            }
+        }
+
+        self.terminate();
+
+        Ok(())
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, SessionState>> {
+        self.state
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on bridge session state)."))
+    }
+
+    fn send(&self, message: RpcMessage) -> Result<()> {
+        let mut line =
+            serde_json::to_string(&message).context("could not serialize bridge message")?;
+        line.push('\n');
+
+        let mut sender = self
+            .sender
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on bridge sender)."))?;
+
+        sender
+            .as_mut()
+            .ok_or_else(|| anyhow!("bridge process is not connected"))?
+            .write_all(line.as_bytes())
+            .context("could not send bridge message")
+    }
+
+    /// Request a new execution. The request is sent as soon as the bridge process is initialized.
+    fn submit(
+        &self,
+        logger: Logger,
+        project_root: PathBuf,
+        params: Value,
+    ) -> Result<(u64, Receiver<RpcResult>)> {
+        let mut state = self.state()?;
+
+        if state.terminated {
+            bail!("bridge process has terminated")
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let (result_sender, result_receiver) = channel();
+        state.executions.insert(
+            id,
+            BridgeExecution {
+                logger,
+                project_root,
+                result: result_sender,
             },
-        )
-        .context("error while creating executor process")?;
+        );
 
-        if let Some(input) = process_params.input {
-            process
-                .stdin_write(input)
-                .context("error while writing to process stdin.")?;
+        let request = RpcMessage::request(id, EXECUTE_METHOD, params);
+
+        if !state.initialized {
+            state.queued.push(request);
+        } else if let Err(err) = self.send(request) {
+            state.executions.remove(&id);
+            return Err(err);
         }
 
-        let result = match process
-            .wait()
-            .context("error while waiting for bridge process output.")?
-        {
-            ProcessStatus { success: true, .. } => Ok(()),
-            ProcessStatus {
-                success: false,
-                code: Some(code),
-            } => Err(anyhow!("bridge process failed with status code {code}.")),
-            ProcessStatus {
-                success: false,
-                code: None,
-            } => Err(anyhow!("bridge process was terminated.")),
-        };
+        Ok((id, result_receiver))
+    }
 
-        logs.close()
-            .context("could not close logging IPC server.")?;
+    fn initialized(&self) -> Result<()> {
+        let mut state = self.state()?;
+        state.initialized = true;
 
-        result
-    })
+        for request in std::mem::take(&mut state.queued) {
+            self.send(request)?;
+        }
+
+        if state.shutdown {
+            self.send(RpcMessage::notification(SHUTDOWN_METHOD, Value::Null))?;
+        }
+
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        let mut state = self.state()?;
+        state.shutdown = true;
+
+        if state.initialized && !state.terminated {
+            self.send(RpcMessage::notification(SHUTDOWN_METHOD, Value::Null))?;
+        }
+
+        Ok(())
+    }
+
+    /// Stop communicating with the bridge process. Running executions will not receive any result.
+    fn terminate(&self) {
+        if let Ok(mut state) = self.state() {
+            state.terminated = true;
+            state.queued.clear();
+            state.executions.clear();
+        }
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+    }
+
+    fn handle_request(&self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            INITIALIZE_METHOD => {
+                let params = parse_params::<InitializeParams>(params)?;
+                if params.protocol_version != PROTOCOL_VERSION {
+                    return Err(RpcError::new(
+                        UNSUPPORTED_VERSION_ERROR,
+                        format!(
+                            "bridge protocol version {} is not supported (expected {PROTOCOL_VERSION})",
+                            params.protocol_version
+                        ),
+                    ));
+                }
+                Ok(Value::Null)
+            }
+            RESOLVE_PATH_METHOD => {
+                let params = parse_params::<ResolvePathParams>(params)?;
+                let project_root = self
+                    .get_project_root(params.execution)
+                    .map_err(|err| RpcError::new(INTERNAL_ERROR, err.to_string()))?;
+                let path =
+                    resolve_workspace_path(&self.workspace_root, &project_root, &params.path)
+                        .map_err(|err| RpcError::new(INVALID_PARAMS_ERROR, format!("{err:#}")))?;
+                to_value(ResolvePathResult { path })
+                    .map_err(|err| RpcError::new(INTERNAL_ERROR, err.to_string()))
+            }
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND_ERROR,
+                format!("unknown method {method}"),
+            )),
+        }
+    }
+
+    fn handle_notification(&self, method: &str, params: Value) -> Result<()> {
+        match method {
+            LOG_METHOD => {
+                let LogParams {
+                    message,
+                    level,
+                    execution,
+                } = parse_content(params)?;
+                self.with_logger(execution, |logger| logger.log(message, level))?;
+            }
+            PROGRESS_METHOD => {
+                let progress = parse_content::<ProgressParams>(params)?;
+                self.with_logger(progress.execution, |logger| {
+                    logger.info(format_progress(&progress))
+                })?;
+            }
+            method => self
+                .logger
+                .debug(format!("unknown bridge notification {method}")),
+        }
+        Ok(())
+    }
+
+    /// Get the project root of an execution, or the workspace root if there is none.
+    fn get_project_root(&self, execution: Option<u64>) -> Result<PathBuf> {
+        let state = self.state()?;
+        Ok(execution
+            .and_then(|id| state.executions.get(&id))
+            .map(|execution| execution.project_root.clone())
+            .unwrap_or_else(|| self.workspace_root.clone()))
+    }
+
+    /// Use the logger of an execution, or the session logger if there is none.
+    fn with_logger<F: FnOnce(&Logger)>(&self, execution: Option<u64>, f: F) -> Result<()> {
+        let state = self.state()?;
+        f(execution
+            .and_then(|id| state.executions.get(&id))
+            .map(|execution| &execution.logger)
+            .unwrap_or(&self.logger));
+        Ok(())
+    }
 }
 
-#[derive(Deserialize)]
-struct LogEntry {
-    message: String,
-    level: LogLevel,
+fn parse_content<T: DeserializeOwned>(content: Value) -> Result<T> {
+    T::deserialize(content).context("invalid bridge message content")
 }
 
-pub fn process_logs(stream: impl BufRead, logger: &Logger) -> Result<()> {
-    for line in stream.lines() {
-        let log = line.context("error occured while processing log line")?;
-        let entry = serde_json::from_str::<LogEntry>(&log)
-            .with_context(|| format!("error occurred while parsing log line: {log}"))?;
-        logger.log(&entry.message, entry.level)
+fn parse_params<T: DeserializeOwned>(params: Value) -> std::result::Result<T, RpcError> {
+    T::deserialize(params).map_err(|err| RpcError::new(INVALID_PARAMS_ERROR, err.to_string()))
+}
+
+/// Format a progress report as a log message.
+pub(super) fn format_progress(progress: &ProgressParams) -> String {
+    let counter = match (progress.completed, progress.total) {
+        (Some(completed), Some(total)) => Some(format!("[{completed}/{total}]")),
+        (Some(completed), None) => Some(format!("[{completed}]")),
+        _ => None,
+    };
+    counter
+        .into_iter()
+        .chain(progress.message.clone())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Resolve a path from the project root, making sure that it stays inside the workspace.
+pub(super) fn resolve_workspace_path(
+    workspace_root: &Path,
+    project_root: &Path,
+    path: &Path,
+) -> Result<PathBuf> {
+    let mut resolved = PathBuf::new();
+
+    for component in project_root.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            component => resolved.push(component),
+        }
+    }
+
+    if !resolved.starts_with(workspace_root) {
+        bail!("{} is outside of the workspace", path.display())
+    }
+
+    Ok(resolved)
+}
+
+/// Convert an error returned by the bridge process.
+fn executor_error(error: RpcError) -> Error {
+    let data = error
+        .data
+        .and_then(|data| parse_content::<ExecutorErrorData>(data).ok())
+        .unwrap_or_default();
+
+    error_from_causes(error.message, data.causes)
+}
+
+/// Rebuild an executor error from its causes, from the outermost to the innermost.
+pub(super) fn error_from_causes(message: String, causes: Vec<String>) -> Error {
+    let mut causes = causes.into_iter().rev();
+
+    match causes.next() {
+        Some(root_cause) => causes.fold(anyhow!(root_cause), |error, cause| error.context(cause)),
+        None => anyhow!(message),
     }
-    Ok(())
+    .context("executor returned an error")
 }
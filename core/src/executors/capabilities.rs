@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeSet,
+    path::{Component as PathComponent, Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, value::Value};
+use serde::{Deserialize, Serialize};
+
+use crate::system::sandbox::{Sandbox, SandboxRules};
+
+use super::{bridge::resolve_workspace_path, ExecutorContext};
+
+/// Operations which are allowed for a custom executor, as declared in its package metadata.
+/// When capabilities are declared, the executor bridge process is restricted to them.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExecutorCapabilities {
+    /// Paths where the executor is allowed to write, relative to the project root.
+    #[serde(default)]
+    pub writable_paths: BTreeSet<PathBuf>,
+    /// Whether the executor is allowed to open network sockets.
+    #[serde(default)]
+    pub network: bool,
+}
+
+impl ExecutorCapabilities {
+    /// Read capabilities from a package metadata value, located at *key*.
+    pub fn from_metadata(metadata: &Value, key: &str) -> Result<Option<Self>> {
+        metadata
+            .at(key)
+            .map(|value| {
+                Self::deserialize(value).map_err(|err| {
+                    anyhow!("invalid value in `{key}`. must be an object with `writablePaths` and `network` keys ({err}).")
+                })
+            })
+            .transpose()
+    }
+
+    /// Build the sandbox enforcing these capabilities for a target execution.
+    /// Writable paths are created if they do not exist yet, and must be located in the workspace.
+    pub(super) fn sandbox(&self, context: &ExecutorContext<'_>) -> Result<Sandbox> {
+        let mut writable = Vec::with_capacity(self.writable_paths.len());
+
+        for path in &self.writable_paths {
+            let resolved =
+                resolve_workspace_path(context.workspace.root(), context.project.root(), path)
+                    .with_context(|| format!("invalid writable path {}", path.display()))?;
+            if !resolved.exists() {
+                std::fs::create_dir_all(&resolved).with_context(|| {
+                    format!("could not create writable path {}", resolved.display())
+                })?;
+            }
+            writable.push(resolved);
+        }
+
+        build_sandbox(
+            context,
+            &SandboxRules {
+                writable,
+                network: self.network,
+            },
+        )
+    }
+}
+
+/// Build the sandbox applied to executors resolved from a remote location (Git, HTTP, npm or Cargo)
+/// which do not declare capabilities. They can only write to the target outputs, and cannot use the network.
+pub(super) fn default_sandbox(context: &ExecutorContext<'_>) -> Result<Sandbox> {
+    let mut writable = BTreeSet::new();
+    add_output_directories(context, &mut writable)?;
+    build_sandbox(
+        context,
+        &SandboxRules {
+            writable: writable.into_iter().collect(),
+            network: false,
+        },
+    )
+}
+
+/// Restrictions which cannot be enforced are an error, unless the workspace explicitly allows them.
+fn build_sandbox(context: &ExecutorContext<'_>, rules: &SandboxRules) -> Result<Sandbox> {
+    let sandbox = Sandbox::new(rules).context("could not prepare executor sandbox")?;
+
+    let mut unenforced = vec![];
+    if !sandbox.restricts_file_system() {
+        unenforced.push("file system restrictions (Landlock is not available)");
+    }
+    if !rules.network && !sandbox.restricts_network() {
+        unenforced.push("network restrictions (seccomp is not available)");
+    }
+
+    if unenforced.is_empty() {
+        return Ok(sandbox);
+    }
+
+    let unenforced = unenforced.join(", ");
+
+    if !context
+        .workspace
+        .settings()
+        .allow_unenforced_capabilities()
+        .unwrap_or_default()
+    {
+        bail!("executor {unenforced} cannot be enforced on this system. set `settings.allowUnenforcedCapabilities` to `true` in the workspace configuration in order to run the executor anyway.")
+    }
+
+    context.logger.warn(format!(
+        "executor {unenforced} cannot be enforced on this system"
+    ));
+
+    Ok(sandbox)
+}
+
+/// Add the directories containing the target declared outputs to *directories*.
+/// Directories which are already covered by another one are skipped, and output directories are created if they do not exist yet.
+pub(super) fn add_output_directories(
+    context: &ExecutorContext<'_>,
+    directories: &mut BTreeSet<PathBuf>,
+) -> Result<()> {
+    let workspace_root = context.workspace.root();
+    let project_root = context.project.root();
+
+    let Some(cache) = context
+        .project
+        .targets()
+        .get(context.target)
+        .and_then(|target| target.cache())
+    else {
+        return Ok(());
+    };
+
+    let matchers = cache.outputs().into_iter().flatten().chain(
+        cache
+            .invalidate_when()
+            .output_changes()
+            .into_iter()
+            .flatten(),
+    );
+
+    for matcher in matchers {
+        let relative = matcher
+            .root()
+            .unwrap_or(Path::new(""))
+            .join(get_static_prefix(matcher.pattern()));
+
+        let directory = resolve_workspace_path(workspace_root, project_root, &relative)
+            .with_context(|| {
+                format!("invalid output directory for pattern {}", matcher.pattern())
+            })?;
+
+        if directories
+            .iter()
+            .any(|existing| directory.starts_with(existing))
+        {
+            continue;
+        }
+
+        std::fs::create_dir_all(&directory).with_context(|| {
+            format!("could not create output directory {}", directory.display())
+        })?;
+
+        directories.retain(|existing| !existing.starts_with(&directory));
+        directories.insert(directory);
+    }
+
+    Ok(())
+}
+
+/// Get the leading components of a glob pattern which do not contain any special character.
+/// For plain file paths, the parent directory is returned.
+fn get_static_prefix(pattern: &str) -> PathBuf {
+    let components = Path::new(pattern).components().collect::<Vec<_>>();
+
+    let is_glob = |component: &PathComponent| {
+        component
+            .as_os_str()
+            .to_string_lossy()
+            .contains(['*', '?', '[', '{'])
+    };
+
+    let end = components
+        .iter()
+        .position(is_glob)
+        .unwrap_or(components.len().saturating_sub(1));
+
+    components[..end].iter().collect()
+}
@@ -1,6 +1,10 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
-use blaze_common::{error::Result, util::path_to_string};
+use blaze_common::{
+    error::Result, target::TargetEnvironment, util::path_to_string, workspace::Workspace,
+};
+
+use crate::system::env::Env;
 
 use super::ExecutorContext;
 
@@ -13,20 +17,68 @@ const PROJECT_ROOT: &str = "BLAZE_PROJECT_ROOT";
 const TARGET: &str = "BLAZE_TARGET";
 
 pub fn get_executor_env(ctx: &ExecutorContext) -> Result<HashMap<String, String>> {
+    let mut env = match get_target_environment(ctx) {
+        Some(environment) => get_target_env(environment, ctx.project.root())?,
+        None => HashMap::new(),
+    };
+    env.extend(get_workspace_env(ctx.workspace)?);
+    env.extend([
+        (PROJECT_NAME.into(), ctx.project.name().to_owned()),
+        (PROJECT_ROOT.into(), path_to_string(ctx.project.root())?),
+        (TARGET.into(), ctx.target.to_owned()),
+    ]);
+    Ok(env)
+}
+
+/// Whether processes started for the target must not inherit the environment of the Blaze process.
+/// This is the case when the target declares the variables to pass through.
+pub fn isolates_environment(ctx: &ExecutorContext) -> bool {
+    get_target_environment(ctx).is_some_and(|environment| environment.pass_through().is_some())
+}
+
+fn get_target_environment<'a>(ctx: &ExecutorContext<'a>) -> Option<&'a TargetEnvironment> {
+    ctx.project
+        .targets()
+        .get(ctx.target)
+        .and_then(|target| target.environment())
+}
+
+/// Variables declared by the environment of a target.
+/// Variables passed through from the Blaze process are overridden by variables loaded from files (in declaration order), which are overridden by static variables.
+pub fn get_target_env(
+    environment: &TargetEnvironment,
+    project_root: &Path,
+) -> Result<HashMap<String, String>> {
+    let mut env = HashMap::new();
+
+    for name in environment.pass_through().into_iter().flatten() {
+        if let Some(value) = Env::get_as_str(name)? {
+            env.insert(name.to_owned(), value);
+        }
+    }
+
+    for file in environment.files() {
+        env.extend(Env::read_dotenv_file(&project_root.join(file))?);
+    }
+
+    env.extend(environment.variables().clone());
+
+    Ok(env)
+}
+
+/// Environment variables which do not depend on the target being executed.
+pub fn get_workspace_env(workspace: &Workspace) -> Result<HashMap<String, String>> {
     Ok([
-        (WORKSPACE_NAME.into(), ctx.workspace.name().to_owned()),
-        (WORKSPACE_ROOT.into(), path_to_string(ctx.workspace.root())?),
+        (WORKSPACE_NAME.into(), workspace.name().to_owned()),
+        (WORKSPACE_ROOT.into(), path_to_string(workspace.root())?),
         (
             WORKSPACE_CONFIGURATION_FILE_PATH.into(),
-            path_to_string(ctx.workspace.configuration_file_path())?,
+            path_to_string(workspace.configuration_file_path())?,
         ),
         (
             WORKSPACE_CONFIGURATION_FILE_FORMAT.into(),
-            ctx.workspace.configuration_file_format().to_string(),
+            workspace.configuration_file_format().to_string(),
         ),
-        (PROJECT_NAME.into(), ctx.project.name().to_owned()),
-        (PROJECT_ROOT.into(), path_to_string(ctx.project.root())?),
-        (TARGET.into(), ctx.target.to_owned()),
     ]
     .into())
 }
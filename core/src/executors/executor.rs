@@ -1,10 +1,15 @@
-use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::{
+    panic::{RefUnwindSafe, UnwindSafe},
+    sync::atomic::AtomicBool,
+};
 
 use blaze_common::{
     error::Result, logger::Logger, project::Project, value::Value, workspace::Workspace,
 };
 use serde::Serialize;
 
+use super::WorkerPool;
+
 #[derive(Serialize)]
 pub struct ExecutorContext<'a> {
     pub workspace: &'a Workspace,
@@ -12,10 +17,22 @@ pub struct ExecutorContext<'a> {
     pub target: &'a str,
     #[serde(skip)]
     pub logger: &'a Logger,
+    /// When set, the target execution should be stopped as soon as possible.
+    #[serde(skip)]
+    pub canceled: Option<&'a AtomicBool>,
+    /// When set, executors running in bridge processes reuse persistent processes from the pool.
+    #[serde(skip)]
+    pub workers: Option<&'a WorkerPool>,
+    /// Whether the executor was resolved from a remote location (Git, HTTP, npm or Cargo).
+    /// Such executors are sandboxed even if they do not declare capabilities.
+    #[serde(skip)]
+    pub remote: bool,
 }
 
 pub type DynExecutor = Box<dyn Executor + Send + Sync + UnwindSafe + RefUnwindSafe>;
 
 pub trait Executor {
-    fn execute(&self, context: ExecutorContext, options: Value) -> Result<()>;
+    /// Execute a target with the provided options.
+    /// The returned value is the target output, which can be used by dependent targets.
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value>;
 }
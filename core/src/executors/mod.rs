@@ -1,12 +1,17 @@
-mod env;
+pub(crate) mod env;
 mod std;
 
 mod executor;
 mod resolve;
+mod workers;
 
 mod bridge;
+mod capabilities;
 mod node;
+mod python;
 mod rust;
+mod wasm;
 
 pub use executor::*;
 pub use resolve::*;
+pub use workers::WorkerPool;
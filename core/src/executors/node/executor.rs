@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     executors::{
         bridge::{bridge_executor, BridgeProcessParams},
+        capabilities::ExecutorCapabilities,
         Executor, ExecutorContext,
     },
     system::env::Env,
@@ -26,11 +27,12 @@ impl NodeExecutor {
 }
 
 impl Executor for NodeExecutor {
-    fn execute(&self, context: ExecutorContext, options: Value) -> Result<()> {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
         execute_node_bridge(NodeBridgeParameters {
             module: &self.package.root.join(self.package.path.as_path()),
             context,
             options: &options,
+            capabilities: self.package.capabilities.as_ref(),
         })
     }
 }
@@ -47,9 +49,10 @@ pub struct NodeBridgeParameters<'a> {
     pub module: &'a Path,
     pub context: ExecutorContext<'a>,
     pub options: &'a Value,
+    pub capabilities: Option<&'a ExecutorCapabilities>,
 }
 
-pub fn execute_node_bridge(parameters: NodeBridgeParameters) -> Result<()> {
+pub fn execute_node_bridge(parameters: NodeBridgeParameters) -> Result<Value> {
     bridge_executor(
         (parameters.context, parameters.options),
         BridgeProcessParams {
@@ -67,6 +70,7 @@ pub fn execute_node_bridge(parameters: NodeBridgeParameters) -> Result<()> {
             .collect::<Vec<_>>()
             .as_slice(),
             input: Some(include_bytes!(env!("BLAZE_NODE_BRIDGE_BUNDLE_PATH"))),
+            capabilities: parameters.capabilities,
         },
         NodeBridgeMetadata {
             module: parameters.module,
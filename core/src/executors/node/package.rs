@@ -6,9 +6,12 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::system::{
-    env::Env,
-    process::{Process, ProcessOptions},
+use crate::{
+    executors::capabilities::ExecutorCapabilities,
+    system::{
+        env::Env,
+        process::{Process, ProcessOptions},
+    },
 };
 
 const NPM_LOCATION: &str = "npm";
@@ -72,6 +75,7 @@ const PACKAGE_METADATA_TYPE: &str = "executor";
 const PACKAGE_METADATA_PATH_KEY: &str = "blaze.path";
 const PACKAGE_METADATA_INSTALL_KEY: &str = "blaze.install";
 const PACKAGE_METADATA_BUILD_KEY: &str = "blaze.build";
+const PACKAGE_METADATA_CAPABILITIES_KEY: &str = "blaze.capabilities";
 
 const DEFAULT_BUILD_SCRIPT: &str = "build";
 const PACKAGE_SCRIPTS_KEY: &str = "scripts";
@@ -83,6 +87,8 @@ pub struct NodeExecutorPackage {
     pub path: PathBuf,
     pub version: String,
     pub root: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ExecutorCapabilities>,
 }
 
 impl NodeExecutorPackage {
@@ -163,6 +169,10 @@ impl NodeExecutorPackage {
             path: Path::new(path).to_owned(),
             version: version.to_owned(),
             root: executor_root.to_owned(),
+            capabilities: ExecutorCapabilities::from_metadata(
+                &value,
+                PACKAGE_METADATA_CAPABILITIES_KEY,
+            )?,
         })
     }
 
@@ -0,0 +1,50 @@
+use blaze_common::{error::Result, util::path_to_string, value::Value};
+use serde::{Deserialize, Serialize};
+
+use crate::executors::{
+    bridge::{bridge_executor, BridgeProcessParams},
+    Executor, ExecutorContext,
+};
+
+use super::package::PythonExecutorPackage;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PythonExecutor {
+    #[serde(flatten)]
+    package: PythonExecutorPackage,
+}
+
+impl PythonExecutor {
+    pub fn new(package: PythonExecutorPackage) -> Self {
+        Self { package }
+    }
+}
+
+#[derive(Serialize)]
+struct PythonBridgeMetadata<'a> {
+    module: &'a str,
+    function: &'a str,
+}
+
+impl Executor for PythonExecutor {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
+        bridge_executor(
+            (context, &options),
+            BridgeProcessParams {
+                program: path_to_string(self.package.interpreter())?.as_str(),
+                // the bridge script is read from stdin, isolated mode keeps the working directory out of the import path.
+                arguments: ["-I", "-u", "-", "--"]
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+                    .as_slice(),
+                input: Some(include_bytes!(env!("BLAZE_PYTHON_BRIDGE_PATH"))),
+                capabilities: self.package.capabilities.as_ref(),
+            },
+            PythonBridgeMetadata {
+                module: &self.package.module,
+                function: &self.package.function,
+            },
+        )
+    }
+}
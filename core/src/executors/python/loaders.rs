@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use blaze_common::{
+    error::Result,
+    value::{to_value, Value},
+};
+use serde::Deserialize;
+
+use crate::executors::{
+    loader::{ExecutorLoader, ExecutorWithMetadata},
+    DynExecutor,
+};
+
+use super::{executor::PythonExecutor, package::PythonExecutorPackage};
+
+/// This loader will install the executor in its own virtual environment before loading.
+pub struct LocalPythonExecutorLoader {
+    workspace_root: PathBuf,
+}
+
+impl LocalPythonExecutorLoader {
+    pub fn new(workspace_root: &Path) -> Self {
+        Self {
+            workspace_root: workspace_root.to_owned(),
+        }
+    }
+}
+
+impl ExecutorLoader for LocalPythonExecutorLoader {
+    fn load_from_src(&self, root: &Path) -> Result<ExecutorWithMetadata> {
+        let package =
+            PythonExecutorPackage::from_root(&self.workspace_root, root).with_context(|| {
+                format!(
+                    "error while reading python executor metadata at {}",
+                    root.display()
+                )
+            })?;
+
+        package
+            .build()
+            .with_context(|| format!("failed to build python executor at {}", root.display()))?;
+
+        let executor = Box::new(PythonExecutor::new(package));
+
+        Ok(ExecutorWithMetadata {
+            metadata: to_value(&executor)?,
+            executor,
+        })
+    }
+
+    fn load_from_metadata(&self, metadata: &Value) -> Result<DynExecutor> {
+        Ok(Box::new(PythonExecutor::deserialize(metadata)?))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod executor;
+pub mod loaders;
+mod package;
+pub use package::is_python_executor;
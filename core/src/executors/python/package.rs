@@ -0,0 +1,240 @@
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, util::path_to_string, value::Value};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    executors::capabilities::ExecutorCapabilities,
+    system::{
+        env::Env,
+        hash::hasher,
+        process::{Process, ProcessOptions},
+    },
+};
+
+const PYTHON_LOCATION_ENV: &str = "BLAZE_PYTHON_LOCATION";
+
+#[cfg(not(windows))]
+const DEFAULT_PYTHON_LOCATION: &str = "python3";
+
+#[cfg(windows)]
+const DEFAULT_PYTHON_LOCATION: &str = "python";
+
+const ENVIRONMENTS_LOCATION: &str = ".blaze/python";
+
+const PYPROJECT_TOML: &str = "pyproject.toml";
+
+/// Files describing the executor requirements. Its dependencies are installed again only when one of them changes.
+const REQUIREMENTS_FILES: [&str; 4] = [PYPROJECT_TOML, "setup.py", "setup.cfg", "requirements.txt"];
+
+/// File of the virtual environment holding the hash of the requirements it was installed with.
+const REQUIREMENTS_HASH_FILE: &str = ".blaze-requirements";
+
+const VERSION: &str = "tool.blaze.version";
+const TYPE: &str = "tool.blaze.type";
+const MODULE: &str = "tool.blaze.module";
+const FUNCTION: &str = "tool.blaze.function";
+const INSTALL: &str = "tool.blaze.install";
+const CAPABILITIES: &str = "tool.blaze.capabilities";
+
+const DEFAULT_FUNCTION: &str = "executor";
+
+pub fn is_python_executor(root: &Path) -> Result<bool> {
+    Ok(match std::fs::metadata(root.join(PYPROJECT_TOML)) {
+        Ok(metadata) => metadata.is_file(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+        Err(err) => return Err(err.into()),
+    })
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonExecutorPackage {
+    pub module: String,
+    pub function: String,
+    pub install: bool,
+    pub root: PathBuf,
+    /// Location of the virtual environment the executor is installed in.
+    pub environment: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ExecutorCapabilities>,
+}
+
+impl PythonExecutorPackage {
+    pub fn from_root(workspace_root: &Path, executor_root: &Path) -> Result<Self> {
+        let pyproject_path = executor_root.join(PYPROJECT_TOML);
+
+        let content = std::fs::read_to_string(&pyproject_path)
+            .with_context(|| format!("could not read {}.", pyproject_path.display()))?;
+        let manifest = toml::from_str::<Value>(&content).with_context(|| {
+            format!(
+                "could not parse executor manifest located at {}",
+                pyproject_path.display()
+            )
+        })?;
+
+        if !matches!(manifest.at(VERSION).and_then(Value::as_str), Some("1")) {
+            bail!(
+                "[{VERSION}] must have value \"1\" (in {}).",
+                pyproject_path.display()
+            )
+        }
+
+        if !matches!(manifest.at(TYPE).and_then(Value::as_str), Some("executor")) {
+            bail!(
+                "[{TYPE}] must be \"executor\" (in {}).",
+                pyproject_path.display()
+            )
+        }
+
+        let module = manifest.at(MODULE).and_then(Value::as_str).ok_or_else(|| {
+            anyhow!(
+                "[{MODULE}] must be the name of the Python module containing your executor function (in {}).",
+                pyproject_path.display()
+            )
+        })?;
+
+        let function = manifest
+            .at(FUNCTION)
+            .map(|value| {
+                value.as_str().ok_or_else(|| {
+                    anyhow!("invalid value in [{FUNCTION}]. must be a function name.")
+                })
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_FUNCTION);
+
+        let install = manifest
+            .at(INSTALL)
+            .map(|value| {
+                value
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("invalid value in [{INSTALL}]. must be a boolean."))
+            })
+            .transpose()?
+            .unwrap_or(true);
+
+        Ok(Self {
+            module: module.to_owned(),
+            function: function.to_owned(),
+            install,
+            root: executor_root.to_owned(),
+            environment: get_environment_location(workspace_root, executor_root),
+            capabilities: ExecutorCapabilities::from_metadata(&manifest, CAPABILITIES)?,
+        })
+    }
+
+    /// Create the executor virtual environment if needed, and install the executor package into it.
+    pub fn build(&self) -> Result<()> {
+        if !self.interpreter().is_file() {
+            let venv_status = Process::run_with_options(
+                Env::get_as_str(PYTHON_LOCATION_ENV)?
+                    .unwrap_or_else(|| DEFAULT_PYTHON_LOCATION.to_owned()),
+                [
+                    "-m".into(),
+                    "venv".into(),
+                    path_to_string(&self.environment)?,
+                ],
+                ProcessOptions {
+                    cwd: Some(self.root.to_path_buf()),
+                    display_output: true,
+                    ..Default::default()
+                },
+            )
+            .context("could not start python virtual environment creation process")?
+            .wait()?;
+
+            if !venv_status.success {
+                bail!(
+                    "python virtual environment creation failed (path={}, exitcode={:?})",
+                    self.environment.display(),
+                    venv_status.code
+                );
+            }
+        }
+
+        if self.install {
+            let requirements_hash = self.requirements_hash()?;
+            let requirements_hash_path = self.environment.join(REQUIREMENTS_HASH_FILE);
+
+            match std::fs::read_to_string(&requirements_hash_path) {
+                Ok(installed_hash) if installed_hash == requirements_hash => return Ok(()),
+                Ok(_) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            // the project is installed in editable mode, so that source changes do not require a new installation.
+            let install_status = Process::run_with_options(
+                self.interpreter(),
+                [
+                    "-m".into(),
+                    "pip".into(),
+                    "install".into(),
+                    "--editable".into(),
+                    path_to_string(&self.root)?,
+                ],
+                ProcessOptions {
+                    cwd: Some(self.root.to_path_buf()),
+                    display_output: true,
+                    ..Default::default()
+                },
+            )
+            .context("could not start python executor install process")?
+            .wait()?;
+
+            if !install_status.success {
+                bail!(
+                    "python executor installation failed (path={}, exitcode={:?})",
+                    self.root.display(),
+                    install_status.code
+                );
+            }
+
+            std::fs::write(&requirements_hash_path, requirements_hash)
+                .with_context(|| format!("could not write {}", requirements_hash_path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Hash the content of the files describing the executor requirements.
+    fn requirements_hash(&self) -> Result<String> {
+        let mut hasher = hasher();
+        for file in REQUIREMENTS_FILES {
+            let path = self.root.join(file);
+            match std::fs::read(&path) {
+                Ok(content) => {
+                    file.hash(&mut hasher);
+                    content.hash(&mut hasher);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(anyhow!(err).context(format!("could not read {}", path.display())))
+                }
+            }
+        }
+        Ok(format!("{:016x}", hasher.finish()))
+    }
+
+    /// Python interpreter of the executor virtual environment.
+    pub fn interpreter(&self) -> PathBuf {
+        #[cfg(not(windows))]
+        return self.environment.join("bin/python");
+        #[cfg(windows)]
+        self.environment.join("Scripts\\python.exe")
+    }
+}
+
+/// Each executor gets its own virtual environment, which is reused when the executor is rebuilt.
+fn get_environment_location(workspace_root: &Path, executor_root: &Path) -> PathBuf {
+    let mut hasher = hasher();
+    executor_root.hash(&mut hasher);
+    workspace_root
+        .join(ENVIRONMENTS_LOCATION)
+        .join(format!("{:016x}", hasher.finish()))
+}
@@ -0,0 +1,424 @@
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::CargoOptions,
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
+use flate2::read::GzDecoder;
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    header::AUTHORIZATION,
+    StatusCode,
+};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::Url;
+
+use crate::{
+    executors::DynExecutor,
+    system::{archive::unpack_tarball, http::download_to_file, random::random_string},
+};
+
+use super::{
+    loader::{ExecutorLoadStrategy, ExecutorLoader, ExecutorWithMetadata, LoaderContext},
+    resolver::{ExecutorResolution, ExecutorResolver, ExecutorUpdate},
+};
+
+const CRATES_LOCATION: &str = ".blaze/cargo";
+const CRATES_IO_INDEX: &str = "https://index.crates.io/";
+const INDEX_CONFIG: &str = "config.json";
+const DOWNLOAD_URL_MARKERS: [&str; 5] = [
+    "{crate}",
+    "{version}",
+    "{prefix}",
+    "{lowerprefix}",
+    "{sha256-checksum}",
+];
+
+/// Registry configuration, located at the root of the index.
+#[derive(Deserialize)]
+struct IndexConfig {
+    dl: String,
+}
+
+/// A single version of a crate, as described by a line of its index file.
+#[derive(Deserialize)]
+struct IndexEntry {
+    vers: String,
+    cksum: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    version: String,
+    crate_root: PathBuf,
+    metadata: Value,
+}
+
+/// Resolves a Rust executor published to a crates registry, using the sparse index protocol.
+pub struct CargoResolver<'a> {
+    options: CargoOptions,
+    context: CargoResolverContext<'a>,
+    crates_root: PathBuf,
+}
+
+#[derive(Clone, Copy)]
+pub struct CargoResolverContext<'a> {
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
+}
+
+impl<'a> CargoResolver<'a> {
+    pub fn new(options: CargoOptions, context: CargoResolverContext<'a>) -> Self {
+        Self {
+            options,
+            context,
+            crates_root: context.workspace.root().join(CRATES_LOCATION),
+        }
+    }
+
+    /// Get the sparse index URL and the crate name from a cargo URL (`cargo://[<host>[:<port>]][/<index path>]/<crate>`).
+    /// The crates.io index is used if the URL does not have any host.
+    fn parse_url(&self, url: &Url) -> Result<(Url, String)> {
+        let mut segments = url
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        let name = segments
+            .pop()
+            .ok_or_else(|| anyhow!("cargo executor URL {url} does not have any crate name"))?
+            .to_owned();
+
+        let index = match url.host_str().filter(|host| !host.is_empty()) {
+            Some(host) => Url::parse(&format!(
+                "{}://{host}{}/{}",
+                if self.options.plain_http() {
+                    "http"
+                } else {
+                    "https"
+                },
+                url.port()
+                    .map(|port| format!(":{port}"))
+                    .unwrap_or_default(),
+                segments
+                    .iter()
+                    .map(|segment| format!("{segment}/"))
+                    .collect::<String>()
+            ))?,
+            None if segments.is_empty() => Url::parse(CRATES_IO_INDEX)?,
+            None => bail!("cargo executor URL {url} has an index path but no registry host"),
+        };
+
+        Ok((index, name))
+    }
+
+    fn client(&self) -> Result<Client> {
+        Client::builder()
+            .danger_accept_invalid_certs(self.options.insecure())
+            .build()
+            .context("could not create HTTP client")
+    }
+
+    /// Build a request, with the registry token if the URL belongs to the registry.
+    fn request(&self, client: &Client, index: &Url, url: Url) -> RequestBuilder {
+        let same_origin = url.origin() == index.origin();
+        let request = client.get(url);
+        match self.options.token() {
+            Some(token) if same_origin => request.header(AUTHORIZATION, token),
+            _ => request,
+        }
+    }
+
+    /// Send a request to the registry, `None` is returned if the resource does not exist.
+    fn send(&self, client: &Client, index: &Url, url: Url) -> Result<Option<Response>> {
+        let response = self
+            .request(client, index, url.clone())
+            .send()
+            .with_context(|| format!("could not reach crates registry at {url}"))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(None),
+            status if status.is_success() => Ok(Some(response)),
+            status => bail!("crates registry returned status {status} for {url}"),
+        }
+    }
+
+    fn fetch(&self, client: &Client, index: &Url, url: Url) -> Result<Option<Vec<u8>>> {
+        self.send(client, index, url.clone())?
+            .map(|response| {
+                Ok(response
+                    .bytes()
+                    .with_context(|| format!("could not read response from {url}"))?
+                    .to_vec())
+            })
+            .transpose()
+    }
+
+    fn fetch_entries(&self, client: &Client, index: &Url, name: &str) -> Result<Vec<IndexEntry>> {
+        let lowercase_name = name.to_lowercase();
+        let url = index.join(&format!(
+            "{}/{lowercase_name}",
+            index_prefix(&lowercase_name)
+        ))?;
+
+        let content = self
+            .fetch(client, index, url)?
+            .ok_or_else(|| anyhow!("crate {name} does not exist in registry {index}"))?;
+
+        String::from_utf8_lossy(&content)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str::<IndexEntry>(line)
+                    .with_context(|| format!("invalid index entry for crate {name}"))
+            })
+            .collect()
+    }
+
+    /// Select the greatest version that matches the version requirement, ignoring yanked versions.
+    fn select_version<'e>(
+        &self,
+        name: &str,
+        entries: &'e [IndexEntry],
+    ) -> Result<(Version, &'e IndexEntry)> {
+        let requirement = match self.options.version() {
+            Some(version) => VersionReq::parse(version)
+                .with_context(|| format!("invalid version requirement \"{version}\""))?,
+            None => VersionReq::STAR,
+        };
+
+        entries
+            .iter()
+            .filter(|entry| !entry.yanked)
+            .filter_map(|entry| Some((Version::parse(&entry.vers).ok()?, entry)))
+            .filter(|(version, _)| requirement.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .ok_or_else(|| anyhow!("no version of crate {name} matches \"{requirement}\""))
+    }
+
+    fn install(
+        &self,
+        client: &Client,
+        index: &Url,
+        name: &str,
+        entry: &IndexEntry,
+    ) -> Result<(DynExecutor, Value)> {
+        let config = self
+            .fetch(client, index, index.join(INDEX_CONFIG)?)?
+            .ok_or_else(|| anyhow!("registry {index} does not have any {INDEX_CONFIG} file"))?;
+        let config = serde_json::from_slice::<IndexConfig>(&config)
+            .with_context(|| format!("invalid registry configuration for {index}"))?;
+
+        let version = &entry.vers;
+        let download_url = download_url(&config.dl, name, version, &entry.cksum)?;
+
+        let response = self
+            .send(client, index, download_url)?
+            .ok_or_else(|| anyhow!("crate {name}@{version} could not be found for download"))?;
+
+        let crate_root = self.crates_root.join(name).join(version);
+
+        std::fs::create_dir_all(&self.crates_root)?;
+        let download_path = self
+            .crates_root
+            .join(format!("{}.download", random_string(12)));
+
+        let unpacked = download_to_file::<Sha256>(response, &download_path)
+            .with_context(|| format!("could not download crate {name}@{version}"))
+            .and_then(|checksum| {
+                let checksum = format!("{checksum:x}");
+                if !checksum.eq_ignore_ascii_case(&entry.cksum) {
+                    bail!(
+                        "checksum mismatch for crate {name}@{version} (expected {}, got {checksum})",
+                        entry.cksum
+                    )
+                }
+
+                if crate_root.try_exists()? {
+                    std::fs::remove_dir_all(&crate_root)?;
+                }
+                std::fs::create_dir_all(&crate_root)?;
+
+                // crate files are all in a top-level "<name>-<version>" directory.
+                unpack_tarball(
+                    GzDecoder::new(BufReader::new(File::open(&download_path)?)),
+                    &crate_root,
+                    1,
+                )
+                .with_context(|| format!("could not unpack crate {name}@{version}"))
+            });
+        let _ = std::fs::remove_file(&download_path);
+        unpacked?;
+
+        self.context.logger.debug(format!(
+            "{name}@{version} was unpacked to {}",
+            crate_root.display()
+        ));
+
+        let ExecutorWithMetadata { executor, metadata } =
+            self.get_loader().load_from_src(&crate_root)?;
+
+        Ok((
+            executor,
+            to_value(State {
+                version: version.to_owned(),
+                crate_root,
+                metadata,
+            })?,
+        ))
+    }
+
+    fn get_loader(&self) -> Box<dyn ExecutorLoader> {
+        ExecutorLoadStrategy::RustCrate.get_loader(LoaderContext {
+            workspace: self.context.workspace,
+        })
+    }
+}
+
+impl ExecutorResolver for CargoResolver<'_> {
+    fn resolve(&self, url: &Url) -> Result<ExecutorResolution> {
+        let (index, name) = self.parse_url(url)?;
+        let client = self.client()?;
+        let entries = self.fetch_entries(&client, &index, &name)?;
+        let (_, entry) = self.select_version(&name, &entries)?;
+
+        let (executor, state) = self.install(&client, &index, &name, entry)?;
+
+        Ok(ExecutorResolution { executor, state })
+    }
+
+    fn update(&self, url: &Url, state: &Value) -> Result<ExecutorUpdate> {
+        let (index, name) = self.parse_url(url)?;
+        let state = State::deserialize(state)?;
+        let client = self.client()?;
+
+        let up_to_date = || -> Result<ExecutorUpdate> {
+            Ok(ExecutorUpdate {
+                executor: self.get_loader().load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            })
+        };
+
+        let entries = match self.fetch_entries(&client, &index, &name) {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.context.logger.warn(format!(
+                    "could not check for updates of {name}, using version {} ({err:#})",
+                    state.version
+                ));
+                return up_to_date();
+            }
+        };
+
+        let (version, entry) = self.select_version(&name, &entries)?;
+
+        if version.to_string() == state.version {
+            self.context
+                .logger
+                .debug(format!("{name}@{version} is the latest matching version"));
+            return up_to_date();
+        }
+
+        self.context.logger.debug(format!(
+            "{name} will be updated from {} to {version}",
+            state.version
+        ));
+
+        let (executor, new_state) = self.install(&client, &index, &name, entry)?;
+
+        if state.crate_root.try_exists()? {
+            std::fs::remove_dir_all(&state.crate_root)?;
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+
+    fn pin(&self, state: &Value) -> Result<Option<String>> {
+        Ok(Some(State::deserialize(state)?.version))
+    }
+
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        let (index, name) = self.parse_url(url)?;
+        let state = state.map(State::deserialize).transpose()?;
+
+        if let Some(state) = state.as_ref().filter(|state| state.version == pin) {
+            return Ok(ExecutorUpdate {
+                executor: self.get_loader().load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            });
+        }
+
+        let client = self.client()?;
+        let entries = self.fetch_entries(&client, &index, &name)?;
+        let entry = entries
+            .iter()
+            .find(|entry| entry.vers == pin)
+            .ok_or_else(|| anyhow!("version {pin} of crate {name} does not exist"))?;
+
+        let (executor, new_state) = self.install(&client, &index, &name, entry)?;
+
+        if let Some(state) = state {
+            if state.crate_root.try_exists()? {
+                std::fs::remove_dir_all(&state.crate_root)?;
+            }
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+}
+
+/// Directory of a crate index file, according to the length of its name.
+fn index_prefix(name: &str) -> String {
+    match name.len() {
+        1 => "1".into(),
+        2 => "2".into(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Build the crate download URL from the `dl` template of the registry configuration.
+fn download_url(template: &str, name: &str, version: &str, checksum: &str) -> Result<Url> {
+    let url = if DOWNLOAD_URL_MARKERS
+        .iter()
+        .any(|marker| template.contains(marker))
+    {
+        template
+            .replace("{crate}", name)
+            .replace("{version}", version)
+            .replace("{prefix}", &index_prefix(name))
+            .replace("{lowerprefix}", &index_prefix(&name.to_lowercase()))
+            .replace("{sha256-checksum}", checksum)
+    } else {
+        format!(
+            "{}/{name}/{version}/download",
+            template.trim_end_matches('/')
+        )
+    };
+
+    Url::parse(&url).with_context(|| format!("invalid download URL for crate {name}@{version}"))
+}
@@ -94,6 +94,8 @@ impl<'a> FileSystemResolver<'a> {
         let strategy = match kind {
             ExecutorKind::Node => ExecutorLoadStrategy::NodeLocal,
             ExecutorKind::Rust => ExecutorLoadStrategy::RustLocal,
+            ExecutorKind::Python => ExecutorLoadStrategy::PythonLocal,
+            ExecutorKind::Wasm => ExecutorLoadStrategy::WasmLocal,
         };
 
         strategy.get_loader(LoaderContext {
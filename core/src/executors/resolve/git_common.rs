@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use blaze_common::{
     error::Result,
     executor::{ExecutorKind, GitCheckout, GitOptions},
@@ -8,7 +8,7 @@ use blaze_common::{
     value::{to_value, Value},
     workspace::Workspace,
 };
-use git2::{build::CheckoutBuilder, FetchOptions, RemoteCallbacks};
+use git2::{build::CheckoutBuilder, FetchOptions, Oid, RemoteCallbacks, Repository};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -43,7 +43,7 @@ pub struct GitHeadlessResolver<'a> {
 pub struct GitResolverContext<'a> {
     pub workspace: &'a Workspace,
     pub logger: &'a Logger,
-    pub save_in_workspace: bool
+    pub save_in_workspace: bool,
 }
 
 impl<'a> GitHeadlessResolver<'a> {
@@ -78,10 +78,42 @@ impl<'a> GitHeadlessResolver<'a> {
         fetch_options
     }
 
+    fn customized_fetch_options(&self) -> FetchOptions<'_> {
+        let mut remote_callbacks = self.default_remote_callbacks();
+        (self.remote_callbacks_customizer)(&mut remote_callbacks);
+
+        let mut fetch_options = self.default_fetch_options(remote_callbacks);
+        (self.fetch_options_customizer)(&mut fetch_options);
+        fetch_options
+    }
+
+    fn clone_repository(&self, url: &Url) -> Result<(Repository, PathBuf)> {
+        let repository_path = self.repositories_root.join(random_string(12));
+
+        if repository_path.try_exists()? {
+            std::fs::remove_dir_all(&repository_path)?;
+        }
+
+        std::fs::create_dir_all(&repository_path)?;
+
+        let mut repo_builder = git2::build::RepoBuilder::new();
+        repo_builder.fetch_options(self.customized_fetch_options());
+
+        let repository = repo_builder.clone(url.as_ref(), &repository_path)?;
+
+        self.context
+            .logger
+            .debug(format!("cloned {} to {}", url, repository_path.display()));
+
+        Ok((repository, repository_path))
+    }
+
     fn get_loader(&self, kind: ExecutorKind) -> Box<dyn ExecutorLoader> {
         let strategy = match kind {
             ExecutorKind::Node => ExecutorLoadStrategy::NodeLocal,
             ExecutorKind::Rust => ExecutorLoadStrategy::RustLocal,
+            ExecutorKind::Python => ExecutorLoadStrategy::PythonLocal,
+            ExecutorKind::Wasm => ExecutorLoadStrategy::WasmLocal,
         };
 
         strategy.get_loader(LoaderContext {
@@ -112,28 +144,7 @@ impl<'a> GitHeadlessResolver<'a> {
 
 impl ExecutorResolver for GitHeadlessResolver<'_> {
     fn resolve(&self, url: &Url) -> Result<ExecutorResolution> {
-        let repository_path = self.repositories_root.join(random_string(12));
-
-        if repository_path.try_exists()? {
-            std::fs::remove_dir_all(&repository_path)?;
-        }
-
-        std::fs::create_dir_all(&repository_path)?;
-
-        let mut repo_builder = git2::build::RepoBuilder::new();
-        let mut remote_callbacks = self.default_remote_callbacks();
-        (self.remote_callbacks_customizer)(&mut remote_callbacks);
-
-        let mut fetch_options = self.default_fetch_options(remote_callbacks);
-        (self.fetch_options_customizer)(&mut fetch_options);
-
-        repo_builder.fetch_options(fetch_options);
-
-        let repository = repo_builder.clone(url.as_ref(), &repository_path)?;
-
-        self.context
-            .logger
-            .debug(format!("cloned {} to {}", url, repository_path.display()));
+        let (repository, repository_path) = self.clone_repository(url)?;
 
         if let Some(checkout) = &self.git_options.checkout() {
             match checkout {
@@ -267,4 +278,75 @@ impl ExecutorResolver for GitHeadlessResolver<'_> {
             updated: true,
         })
     }
+
+    fn pin(&self, state: &Value) -> Result<Option<String>> {
+        let state = State::deserialize(state)?;
+        let repository = git2::Repository::open(&state.repository_path)?;
+        Ok(Some(head_commit(&repository)?.to_string()))
+    }
+
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        let commit = Oid::from_str(pin).with_context(|| format!("invalid commit id {pin}"))?;
+
+        let (repository, repository_path) = match state {
+            Some(state) => {
+                let state = State::deserialize(state)?;
+                let repository = git2::Repository::open(&state.repository_path)?;
+
+                if head_commit(&repository)? == commit {
+                    let loader = self.get_loader(state.kind);
+                    return Ok(ExecutorUpdate {
+                        executor: loader.load_from_metadata(&state.metadata)?,
+                        new_state: None,
+                        updated: false,
+                    });
+                }
+
+                (repository, state.repository_path)
+            }
+            None => self.clone_repository(url)?,
+        };
+
+        if repository.find_commit(commit).is_err() {
+            let mut fetch_options = self.customized_fetch_options();
+            repository
+                .find_remote("origin")?
+                .fetch(&[pin], Some(&mut fetch_options), None)?;
+        }
+
+        repository.set_head_detached(commit)?;
+        repository.checkout_head(Some(CheckoutBuilder::default().force()))?;
+
+        self.context.logger.debug(format!(
+            "repository files for {url} are now at locked commit {pin}"
+        ));
+
+        let src_path = self.get_src_path(&repository_path);
+
+        let (ExecutorWithMetadata { executor, metadata }, kind) = self.build_and_load(&src_path)?;
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(to_value(State {
+                kind,
+                metadata,
+                repository_path,
+                src_path,
+            })?),
+            updated: true,
+        })
+    }
+}
+
+fn head_commit(repository: &Repository) -> Result<Oid> {
+    repository
+        .head()?
+        .resolve()?
+        .target()
+        .ok_or_else(|| anyhow!("could not resolve commit id for HEAD"))
 }
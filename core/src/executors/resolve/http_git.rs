@@ -78,4 +78,17 @@ impl ExecutorResolver for GitOverHttpResolver<'_> {
     fn update(&self, url: &Url, state: &Value) -> Result<ExecutorUpdate> {
         self.delegate.update(url, state)
     }
+
+    fn pin(&self, state: &Value) -> Result<Option<String>> {
+        self.delegate.pin(state)
+    }
+
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        self.delegate.resolve_pinned(url, pin, state)
+    }
 }
@@ -0,0 +1,350 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::{Compression, ExecutorKind, HttpAuthentication, HttpTransport, TarballOptions},
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
+use bzip2::read::BzDecoder;
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use reqwest::{
+    blocking::{Client, Response},
+    header::{
+        HeaderMap, HeaderName, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    },
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::Url;
+use xz2::read::XzDecoder;
+
+use crate::{
+    executors::DynExecutor,
+    system::{
+        archive::unpack_tarball,
+        http::{download_to_file, send_authenticated},
+        random::random_string,
+    },
+};
+
+use super::{
+    kinds::infer_local_executor_type,
+    loader::{ExecutorLoadStrategy, ExecutorLoader, ExecutorWithMetadata, LoaderContext},
+    resolver::{ExecutorResolution, ExecutorResolver, ExecutorUpdate},
+};
+
+const TARBALLS_LOCATION: &str = ".blaze/tarballs";
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    archive_path: PathBuf,
+    src_path: PathBuf,
+    metadata: Value,
+    kind: ExecutorKind,
+    digest: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Resolves an executor from a tar archive downloaded over HTTP.
+pub struct TarballOverHttpResolver<'a> {
+    transport: HttpTransport,
+    tarball_options: TarballOptions,
+    authentication: Option<HttpAuthentication>,
+    context: TarballOverHttpResolverContext<'a>,
+    tarballs_root: PathBuf,
+}
+
+#[derive(Clone, Copy)]
+pub struct TarballOverHttpResolverContext<'a> {
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
+}
+
+impl<'a> TarballOverHttpResolver<'a> {
+    pub fn new(
+        transport: HttpTransport,
+        tarball_options: TarballOptions,
+        authentication: Option<HttpAuthentication>,
+        context: TarballOverHttpResolverContext<'a>,
+    ) -> Self {
+        Self {
+            transport,
+            tarball_options,
+            authentication,
+            context,
+            tarballs_root: context.workspace.root().join(TARBALLS_LOCATION),
+        }
+    }
+
+    fn client(&self) -> Result<Client> {
+        let headers = self
+            .transport
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::try_from(name.as_str())
+                        .with_context(|| format!("invalid header name {name}"))?,
+                    HeaderValue::try_from(value.as_str())
+                        .with_context(|| format!("invalid value for header {name}"))?,
+                ))
+            })
+            .collect::<Result<HeaderMap>>()?;
+
+        Client::builder()
+            .default_headers(headers)
+            .danger_accept_invalid_certs(self.transport.insecure())
+            .build()
+            .context("could not create HTTP client")
+    }
+
+    /// Download the archive. When the previous state is provided, the request is conditional and `None` is returned if the archive did not change.
+    fn download(&self, url: &Url, state: Option<&State>) -> Result<Option<Response>> {
+        let mut request = self.client()?.get(url.clone());
+
+        if let Some(state) = state {
+            if let Some(etag) = &state.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &state.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = send_authenticated(request, self.authentication.as_ref())
+            .with_context(|| format!("could not reach {url}"))?;
+
+        match response.status() {
+            StatusCode::NOT_MODIFIED if state.is_some() => Ok(None),
+            status if status.is_success() => Ok(Some(response)),
+            status => bail!("server returned status {status} when downloading {url}"),
+        }
+    }
+
+    /// Unpack and load the downloaded archive. If *expected_digest* is provided, the archive must match it.
+    fn install(
+        &self,
+        url: &Url,
+        response: Response,
+        expected_digest: Option<&str>,
+    ) -> Result<(DynExecutor, Value)> {
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+
+        std::fs::create_dir_all(&self.tarballs_root)?;
+        let download_path = self
+            .tarballs_root
+            .join(format!("{}.download", random_string(12)));
+
+        let unpacked = download_to_file::<Sha256>(response, &download_path)
+            .with_context(|| format!("could not download archive from {url}"))
+            .and_then(|digest| {
+                let digest = format!("sha256:{digest:x}");
+
+                if let Some(expected_digest) = expected_digest {
+                    if digest != expected_digest {
+                        bail!("archive downloaded from {url} does not match the locked digest (expected {expected_digest}, got {digest})")
+                    }
+                }
+
+                Ok((self.unpack(url, &download_path)?, digest))
+            });
+        let _ = std::fs::remove_file(&download_path);
+        let (archive_path, digest) = unpacked?;
+
+        self.context
+            .logger
+            .debug(format!("{url} was unpacked to {}", archive_path.display()));
+
+        let src_path = match self.tarball_options.path() {
+            Some(path) => archive_path.join(path),
+            None => archive_path.clone(),
+        };
+
+        let kind = match self.tarball_options.kind() {
+            Some(kind) => kind,
+            None => infer_local_executor_type(&src_path)?,
+        };
+
+        let ExecutorWithMetadata { executor, metadata } =
+            self.get_loader(kind).load_from_src(&src_path)?;
+
+        Ok((
+            executor,
+            to_value(State {
+                archive_path,
+                src_path,
+                metadata,
+                kind,
+                digest,
+                etag,
+                last_modified,
+            })?,
+        ))
+    }
+
+    /// Unpack a downloaded archive to a new directory.
+    fn unpack(&self, url: &Url, download_path: &Path) -> Result<PathBuf> {
+        let archive_path = self.tarballs_root.join(random_string(12));
+
+        if archive_path.try_exists()? {
+            std::fs::remove_dir_all(&archive_path)?;
+        }
+        std::fs::create_dir_all(&archive_path)?;
+
+        let compression = self
+            .tarball_options
+            .compression()
+            .or_else(|| infer_compression(url));
+
+        let content = BufReader::new(File::open(download_path)?);
+        let reader: Box<dyn Read> = match compression {
+            None => Box::new(content),
+            Some(Compression::Gzip) => Box::new(GzDecoder::new(content)),
+            Some(Compression::Deflate) => Box::new(DeflateDecoder::new(content)),
+            Some(Compression::Zlib) => Box::new(ZlibDecoder::new(content)),
+            Some(Compression::Xz) => Box::new(XzDecoder::new(content)),
+            Some(Compression::Bzip2) => Box::new(BzDecoder::new(content)),
+        };
+
+        unpack_tarball(reader, &archive_path, 0)
+            .with_context(|| format!("could not unpack archive from {url}"))?;
+
+        Ok(archive_path)
+    }
+
+    fn get_loader(&self, kind: ExecutorKind) -> Box<dyn ExecutorLoader> {
+        let strategy = match kind {
+            ExecutorKind::Node => ExecutorLoadStrategy::NodeLocal,
+            ExecutorKind::Rust => ExecutorLoadStrategy::RustLocal,
+            ExecutorKind::Python => ExecutorLoadStrategy::PythonLocal,
+            ExecutorKind::Wasm => ExecutorLoadStrategy::WasmLocal,
+        };
+
+        strategy.get_loader(LoaderContext {
+            workspace: self.context.workspace,
+        })
+    }
+}
+
+impl ExecutorResolver for TarballOverHttpResolver<'_> {
+    fn resolve(&self, url: &Url) -> Result<ExecutorResolution> {
+        let response = self
+            .download(url, None)?
+            .with_context(|| format!("archive was not sent by the server for {url}"))?;
+
+        let (executor, state) = self.install(url, response, None)?;
+
+        Ok(ExecutorResolution { executor, state })
+    }
+
+    fn update(&self, url: &Url, state: &Value) -> Result<ExecutorUpdate> {
+        let state = State::deserialize(state)?;
+
+        let up_to_date = || -> Result<ExecutorUpdate> {
+            Ok(ExecutorUpdate {
+                executor: self
+                    .get_loader(state.kind)
+                    .load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            })
+        };
+
+        let response = match self.download(url, Some(&state)) {
+            Ok(Some(response)) => response,
+            Ok(None) => {
+                self.context.logger.debug(format!("{url} was not modified"));
+                return up_to_date();
+            }
+            Err(err) => {
+                self.context.logger.warn(format!(
+                    "could not check for updates of {url}, using the downloaded archive ({err:#})"
+                ));
+                return up_to_date();
+            }
+        };
+
+        let (executor, new_state) = self.install(url, response, None)?;
+
+        if state.archive_path.try_exists()? {
+            std::fs::remove_dir_all(&state.archive_path)?;
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+
+    fn pin(&self, state: &Value) -> Result<Option<String>> {
+        Ok(Some(State::deserialize(state)?.digest))
+    }
+
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        let state = state.map(State::deserialize).transpose()?;
+
+        if let Some(state) = state.as_ref().filter(|state| state.digest == pin) {
+            return Ok(ExecutorUpdate {
+                executor: self
+                    .get_loader(state.kind)
+                    .load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            });
+        }
+
+        let response = self
+            .download(url, None)?
+            .with_context(|| format!("archive was not sent by the server for {url}"))?;
+
+        let (executor, new_state) = self.install(url, response, Some(pin))?;
+
+        if let Some(state) = state {
+            if state.archive_path.try_exists()? {
+                std::fs::remove_dir_all(&state.archive_path)?;
+            }
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+}
+
+/// Guess the archive compression from the URL file extension.
+fn infer_compression(url: &Url) -> Option<Compression> {
+    let file_name = url.path_segments()?.last()?.to_ascii_lowercase();
+    let extension = Path::new(&file_name).extension()?.to_str()?;
+
+    match extension {
+        "gz" | "tgz" => Some(Compression::Gzip),
+        "xz" | "txz" => Some(Compression::Xz),
+        "bz2" | "tbz" | "tbz2" => Some(Compression::Bzip2),
+        _ => None,
+    }
+}
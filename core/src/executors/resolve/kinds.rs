@@ -1,18 +1,24 @@
-use std::{collections::HashMap, path::Path};
+use std::path::Path;
 
 use anyhow::bail;
 use blaze_common::{error::Result, executor::ExecutorKind};
 
-use crate::executors::{node::is_node_executor, rust::is_rust_executor};
+use crate::executors::{
+    node::is_node_executor, python::is_python_executor, rust::is_rust_executor,
+    wasm::is_wasm_executor,
+};
 
 pub fn infer_local_executor_type(root: &Path) -> Result<ExecutorKind> {
-    let cases = HashMap::from([
+    // a compiled component is checked first, because it can be shipped along with its sources.
+    let cases = [
         (
-            ExecutorKind::Node,
-            is_node_executor as fn(&Path) -> Result<bool>,
+            ExecutorKind::Wasm,
+            is_wasm_executor as fn(&Path) -> Result<bool>,
         ),
+        (ExecutorKind::Node, is_node_executor),
         (ExecutorKind::Rust, is_rust_executor),
-    ]);
+        (ExecutorKind::Python, is_python_executor),
+    ];
 
     for (kind, supports) in cases {
         if supports(root)? {
@@ -3,7 +3,8 @@ use std::path::Path;
 use blaze_common::{error::Result, value::Value, workspace::Workspace};
 
 use crate::executors::{
-    node::loaders::LocalNodeExecutorLoader, rust::loaders::LocalRustExecutorLoader, DynExecutor,
+    node::loaders::LocalNodeExecutorLoader, python::loaders::LocalPythonExecutorLoader,
+    rust::loaders::LocalRustExecutorLoader, wasm::loaders::LocalWasmExecutorLoader, DynExecutor,
 };
 
 pub struct ExecutorWithMetadata {
@@ -21,20 +22,29 @@ pub struct LoaderContext<'a> {
     pub workspace: &'a Workspace,
 }
 
-#[allow(unused)]
 pub enum ExecutorLoadStrategy {
     RustLocal,
     RustCrate,
     NodeLocal,
     NodePackage,
+    PythonLocal,
+    WasmLocal,
 }
 
 impl ExecutorLoadStrategy {
     pub fn get_loader(&self, context: LoaderContext<'_>) -> Box<dyn ExecutorLoader> {
         match self {
-            Self::NodeLocal => Box::new(LocalNodeExecutorLoader),
-            Self::RustLocal => Box::new(LocalRustExecutorLoader::new(context.workspace.root())),
-            _ => todo!(),
+            Self::NodeLocal | Self::NodePackage => Box::new(LocalNodeExecutorLoader),
+            Self::RustLocal | Self::RustCrate => Box::new(LocalRustExecutorLoader::new(
+                context.workspace.root(),
+                context
+                    .workspace
+                    .settings()
+                    .in_process_rust_executors()
+                    .unwrap_or_default(),
+            )),
+            Self::PythonLocal => Box::new(LocalPythonExecutorLoader::new(context.workspace.root())),
+            Self::WasmLocal => Box::new(LocalWasmExecutorLoader::new(context.workspace.root())),
         }
     }
 }
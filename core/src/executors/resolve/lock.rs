@@ -0,0 +1,194 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, executor::Location};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+pub const LOCKFILE_NAME: &str = "blaze.lock";
+
+/// How the lockfile is used when resolving executors.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Locked versions are used, and executors that are not locked yet are added to the lockfile.
+    #[default]
+    Honor,
+    /// Locked versions are used, and resolution fails if the lockfile would have to be modified.
+    Frozen,
+    /// Locked versions are ignored, and the lockfile is rewritten with the resolved versions.
+    Update,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct LockedExecutor {
+    url: Url,
+    pin: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Lockfile {
+    executors: BTreeMap<String, LockedExecutor>,
+}
+
+/// Exact executor versions recorded in the workspace lockfile, keyed by executor location.
+pub struct ExecutorLock {
+    path: PathBuf,
+    mode: LockMode,
+    locked: BTreeMap<String, LockedExecutor>,
+    resolved: Mutex<BTreeMap<String, LockedExecutor>>,
+}
+
+impl ExecutorLock {
+    pub fn load(root: &Path, mode: LockMode) -> Result<Self> {
+        let path = root.join(LOCKFILE_NAME);
+
+        let locked = if path.try_exists()? {
+            serde_json::from_str::<Lockfile>(&std::fs::read_to_string(&path)?)
+                .with_context(|| format!("invalid lockfile {}", path.display()))?
+                .executors
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path,
+            mode,
+            locked,
+            resolved: Mutex::default(),
+        })
+    }
+
+    /// Get the locked version of an executor package, unless locked versions are ignored.
+    pub fn get(&self, url: &Url, location: &Location) -> Option<&str> {
+        if self.mode == LockMode::Update {
+            return None;
+        }
+        self.locked
+            .get(&lock_key(url, location))
+            .map(|locked| locked.pin.as_str())
+    }
+
+    /// Record the version an executor package was resolved to.
+    pub fn record(&self, url: &Url, location: &Location, pin: String) -> Result<()> {
+        let key = lock_key(url, location);
+        let resolved = LockedExecutor {
+            url: without_credentials(url),
+            pin,
+        };
+
+        if self.mode == LockMode::Frozen && self.locked.get(&key) != Some(&resolved) {
+            bail!(
+                "{LOCKFILE_NAME} is not up to date for executor {url}, run `blaze executors update` to update it"
+            )
+        }
+
+        self.resolved
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on resolved executors)."))?
+            .insert(key, resolved);
+
+        Ok(())
+    }
+
+    /// Write the lockfile if its content changed.
+    /// In update mode, only the executors that were resolved are kept.
+    pub fn save(self) -> Result<()> {
+        let resolved = self
+            .resolved
+            .into_inner()
+            .map_err(|_| anyhow!("poison error (Mutex on resolved executors)."))?;
+
+        let executors = match self.mode {
+            LockMode::Frozen => return Ok(()),
+            LockMode::Update => resolved,
+            LockMode::Honor => {
+                let mut executors = self.locked.clone();
+                executors.extend(resolved);
+                executors
+            }
+        };
+
+        let exists = self.path.try_exists()?;
+
+        if (exists && executors == self.locked) || (!exists && executors.is_empty()) {
+            return Ok(());
+        }
+
+        let mut content = serde_json::to_string_pretty(&Lockfile { executors })?;
+        content.push('\n');
+
+        std::fs::write(&self.path, content)
+            .with_context(|| format!("could not write lockfile {}", self.path.display()))
+    }
+}
+
+/// Get a readable identity for an executor location, made of its URL and of the options that select a version.
+/// Credentials (URL user info, tokens, headers and authentication options) are never part of it, so that they are not written to the lockfile.
+fn lock_key(url: &Url, location: &Location) -> String {
+    let mut fields = Vec::<(&str, String)>::new();
+
+    match location {
+        Location::Git {
+            options: git_options,
+        }
+        | Location::GitOverHttp { git_options, .. }
+        | Location::GitOverSsh { git_options, .. } => {
+            if let Some(checkout) = git_options.checkout() {
+                fields.push(("checkout", checkout.to_string()));
+            }
+            if let Some(path) = git_options.path() {
+                fields.push(("path", path.display().to_string()));
+            }
+        }
+        Location::TarballOverHttp {
+            tarball_options, ..
+        } => {
+            if let Some(kind) = tarball_options.kind() {
+                fields.push(("kind", kind.to_string()));
+            }
+            if let Some(compression) = tarball_options.compression() {
+                fields.push(("compression", compression.to_string()));
+            }
+            if let Some(path) = tarball_options.path() {
+                fields.push(("path", path.display().to_string()));
+            }
+        }
+        Location::Npm { options } => {
+            if let Some(version) = options.version() {
+                fields.push(("version", version.to_owned()));
+            }
+        }
+        Location::Cargo { options } => {
+            if let Some(version) = options.version() {
+                fields.push(("version", version.to_owned()));
+            }
+        }
+        Location::LocalFileSystem { .. } => {}
+    }
+
+    let url = without_credentials(url);
+
+    if fields.is_empty() {
+        return url.to_string();
+    }
+
+    format!(
+        "{url} ({})",
+        fields
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn without_credentials(url: &Url) -> Url {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url
+}
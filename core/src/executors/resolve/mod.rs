@@ -1,10 +1,12 @@
+pub mod cargo;
 pub mod file_system;
 pub mod git;
 pub mod git_common;
 pub mod http_git;
+pub mod http_tarball;
 pub mod kinds;
 pub mod loader;
-#[allow(dead_code)]
+pub mod lock;
 pub mod npm;
 pub mod resolver;
 pub mod ssh_git;
@@ -13,9 +15,10 @@ pub mod standard;
 use std::{
     collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
+    path::{Path, PathBuf},
 };
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use blaze_common::{
     error::Result,
     executor::{ExecutorReference, Location},
@@ -36,7 +39,10 @@ use crate::{
 
 use standard::resolve_standard_executor;
 
-use self::resolver::{resolver_for_location, ExecutorResolver};
+use self::{
+    lock::ExecutorLock,
+    resolver::{resolver_for_location, ExecutorResolver},
+};
 
 /// Extra data needed in order to resolve an executor.
 #[derive(Clone, Copy)]
@@ -44,8 +50,10 @@ pub struct CustomResolutionContext<'a> {
     pub workspace: &'a Workspace,
     pub cache: Option<&'a CacheStore>,
     pub logger: &'a Logger,
+    pub lock: &'a ExecutorLock,
 }
 
+#[derive(Default)]
 pub struct ResolvedExecutors {
     executors: HashMap<u64, ResolvedExecutor>,
 }
@@ -54,6 +62,44 @@ impl ResolvedExecutors {
     pub fn get_for_reference(&self, reference: &ExecutorReference) -> Option<&ResolvedExecutor> {
         self.executors.get(&get_executor_package_id(reference))
     }
+
+    /// Get the references which are not resolved yet.
+    pub fn unresolved<'a, I>(&self, references: I) -> HashSet<ExecutorReference>
+    where
+        I: IntoIterator<Item = &'a ExecutorReference>,
+    {
+        references
+            .into_iter()
+            .filter(|reference| self.get_for_reference(reference).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Add executors which were resolved separately.
+    pub fn extend(&mut self, other: ResolvedExecutors) {
+        self.executors.extend(other.executors);
+    }
+
+    /// Forget custom executors, so that they are resolved again. Standard executors are kept since they never change.
+    pub fn retain_standard(&mut self) {
+        self.executors
+            .retain(|_, executor| matches!(executor, ResolvedExecutor::Standard(_)));
+    }
+
+    /// Forget custom executors located on the file system whose source root contains one of the changed paths, so that they are resolved again.
+    pub fn invalidate_sources<'a, I: IntoIterator<Item = &'a Path>>(&mut self, changed_paths: I) {
+        for path in changed_paths {
+            self.executors.retain(|_, executor| {
+                !matches!(
+                    executor,
+                    ResolvedExecutor::Custom(CustomExecutorResolution {
+                        source_root: Some(root),
+                        ..
+                    }) if path.starts_with(root)
+                )
+            });
+        }
+    }
 }
 
 pub enum ResolvedExecutor {
@@ -176,6 +222,8 @@ pub struct CustomExecutorResolution {
     state: ExecutorCacheState,
     executor: DynExecutor,
     nonce: u64,
+    /// Directory containing the executor sources, for executors located on the file system.
+    source_root: Option<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -208,8 +256,37 @@ fn resolve_custom_executor(
         .as_ref()
         .map(|metadata| metadata.nonce);
 
-    let (executor, resolution_state, cache_state) = match maybe_cached_metadata {
-        Some(cached_metadata) => {
+    let locked_pin = context.lock.get(url, location);
+
+    let (executor, resolution_state, cache_state) = match (maybe_cached_metadata, locked_pin) {
+        (maybe_cached_metadata, Some(pin)) => {
+            let cached_state = maybe_cached_metadata.map(|metadata| metadata.resolution_state);
+            let update = resolver
+                .resolve_pinned(url, pin, cached_state.as_ref())
+                .with_context(|| {
+                    format!("failed to resolve executor {url} at locked version {pin}")
+                })?;
+
+            let cache_state = match (&cached_state, update.updated) {
+                (None, _) => ExecutorCacheState::New,
+                (Some(_), true) => ExecutorCacheState::Updated,
+                (Some(_), false) => ExecutorCacheState::Cached,
+            };
+
+            context
+                .logger
+                .debug(format!("{url} was resolved at locked version {pin}"));
+
+            (
+                update.executor,
+                update
+                    .new_state
+                    .or(cached_state)
+                    .ok_or_else(|| anyhow!("no resolution state for executor {url}"))?,
+                cache_state,
+            )
+        }
+        (Some(cached_metadata), None) => {
             context.logger.debug(format!("{url} exists in cache"));
             let update = resolver
                 .update(url, &cached_metadata.resolution_state)
@@ -231,7 +308,7 @@ fn resolve_custom_executor(
                 },
             )
         }
-        None => {
+        (None, None) => {
             let resolution = resolver
                 .resolve(url)
                 .with_context(|| format!("failed to resolve executor {url}"))?;
@@ -246,6 +323,10 @@ fn resolve_custom_executor(
         }
     };
 
+    if let Some(pin) = resolver.pin(&resolution_state)? {
+        context.lock.record(url, location, pin)?;
+    }
+
     let nonce = match cache_state {
         ExecutorCacheState::Cached if maybe_current_nonce.is_some() => maybe_current_nonce.unwrap(),
         _ => thread_rng().next_u64(),
@@ -266,9 +347,16 @@ fn resolve_custom_executor(
         executor,
         nonce,
         state: cache_state,
+        source_root: possibly!(location, Location::LocalFileSystem { .. } => get_source_root(url, context.workspace.root())),
     })
 }
 
+fn get_source_root(url: &Url, workspace_root: &Path) -> PathBuf {
+    // relative URL paths are relative to the workspace root.
+    let path = workspace_root.join(url.path());
+    dunce::canonicalize(&path).unwrap_or(path)
+}
+
 pub fn get_executor_package_id(reference: &ExecutorReference) -> u64 {
     let mut hasher = hasher();
     match reference {
@@ -299,10 +387,11 @@ pub fn get_executor_package_id(reference: &ExecutorReference) -> u64 {
                 }
                 Location::TarballOverHttp {
                     transport,
+                    tarball_options,
                     authentication,
-                    ..
                 } => {
                     transport.headers().hash(&mut hasher);
+                    tarball_options.hash(&mut hasher);
                     authentication.hash(&mut hasher);
                 }
                 Location::LocalFileSystem { .. } => {}
@@ -1,42 +1,482 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, fs::File, io::BufReader, path::PathBuf};
 
+use anyhow::{anyhow, bail, Context};
+use base64::{prelude::BASE64_STANDARD, Engine};
 use blaze_common::{
-    error::Result, executor::NpmOptions, logger::Logger, value::Value, workspace::Workspace,
+    error::Result,
+    executor::NpmOptions,
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
 };
+use flate2::read::GzDecoder;
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::ACCEPT,
+};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
 use url::Url;
 
-use super::resolver::{ExecutorResolution, ExecutorResolver, ExecutorUpdate};
+use crate::{
+    executors::DynExecutor,
+    system::{archive::unpack_tarball, http::download_to_file, random::random_string},
+};
+
+use super::{
+    loader::{ExecutorLoadStrategy, ExecutorLoader, ExecutorWithMetadata, LoaderContext},
+    resolver::{ExecutorResolution, ExecutorResolver, ExecutorUpdate},
+};
 
 const PACKAGE_LOCATION: &str = ".blaze/npm";
+const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org/";
+const ABBREVIATED_METADATA_MEDIA_TYPE: &str = "application/vnd.npm.install-v1+json";
 
-struct NpmResolver<'a> {
+/// Abbreviated package metadata, as returned by the registry.
+#[derive(Deserialize)]
+struct PackageMetadata {
+    versions: BTreeMap<String, PackageVersionMetadata>,
+}
+
+#[derive(Deserialize)]
+struct PackageVersionMetadata {
+    dist: PackageDistribution,
+}
+
+#[derive(Deserialize)]
+struct PackageDistribution {
+    tarball: Url,
+    integrity: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    version: String,
+    package_root: PathBuf,
+    metadata: Value,
+}
+
+/// Resolves a node executor published as a package to an npm registry.
+pub struct NpmResolver<'a> {
     options: NpmOptions,
-    logger: &'a Logger,
+    context: NpmResolverContext<'a>,
     packages_root: PathBuf,
 }
 
 #[derive(Clone, Copy)]
 pub struct NpmResolverContext<'a> {
-    workspace: &'a Workspace,
-    logger: &'a Logger,
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
 }
 
 impl<'a> NpmResolver<'a> {
     pub fn new(options: NpmOptions, context: NpmResolverContext<'a>) -> Self {
         Self {
             options,
-            logger: context.logger,
+            context,
             packages_root: context.workspace.root().join(PACKAGE_LOCATION),
         }
     }
+
+    /// Get the registry URL. The official registry is used if the executor URL does not have any host.
+    fn registry(&self, url: &Url) -> Result<Url> {
+        match url.host_str().filter(|host| !host.is_empty()) {
+            Some(host) => Ok(Url::parse(&format!(
+                "{}://{host}{}/",
+                if self.options.plain_http() {
+                    "http"
+                } else {
+                    "https"
+                },
+                url.port()
+                    .map(|port| format!(":{port}"))
+                    .unwrap_or_default()
+            ))?),
+            None => Ok(Url::parse(DEFAULT_REGISTRY)?),
+        }
+    }
+
+    fn client(&self) -> Result<Client> {
+        Client::builder()
+            .danger_accept_invalid_certs(self.options.insecure())
+            .build()
+            .context("could not create HTTP client")
+    }
+
+    /// Build a request, with the registry token if the URL belongs to the registry.
+    fn request(&self, client: &Client, registry: &Url, url: Url) -> RequestBuilder {
+        let same_origin = url.origin() == registry.origin();
+        let request = client.get(url);
+        match self.options.token() {
+            Some(token) if same_origin => request.bearer_auth(token),
+            _ => request,
+        }
+    }
+
+    fn fetch_metadata(
+        &self,
+        client: &Client,
+        registry: &Url,
+        name: &str,
+    ) -> Result<PackageMetadata> {
+        let url = registry.join(&name.replace('/', "%2f"))?;
+
+        let response = self
+            .request(client, registry, url)
+            .header(ACCEPT, ABBREVIATED_METADATA_MEDIA_TYPE)
+            .send()
+            .with_context(|| format!("could not reach npm registry for package {name}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            bail!("npm registry returned status {status} for package {name}")
+        }
+
+        serde_json::from_slice(
+            &response
+                .bytes()
+                .with_context(|| format!("could not read npm registry response for {name}"))?,
+        )
+        .with_context(|| format!("invalid npm registry metadata for package {name}"))
+    }
+
+    /// Select the greatest version that matches the version range.
+    fn select_version<'m>(
+        &self,
+        name: &str,
+        metadata: &'m PackageMetadata,
+    ) -> Result<(Version, &'m PackageVersionMetadata)> {
+        let range = self.options.version().unwrap_or("*");
+        let requirements = parse_version_range(range)
+            .with_context(|| format!("invalid version range \"{range}\""))?;
+
+        metadata
+            .versions
+            .iter()
+            .filter_map(|(version, package)| Some((Version::parse(version).ok()?, package)))
+            .filter(|(version, _)| {
+                requirements
+                    .iter()
+                    .any(|requirement| requirement.matches(version))
+            })
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .ok_or_else(|| anyhow!("no version of package {name} matches \"{range}\""))
+    }
+
+    fn install(
+        &self,
+        client: &Client,
+        registry: &Url,
+        name: &str,
+        version: &Version,
+        package: &PackageVersionMetadata,
+    ) -> Result<(DynExecutor, Value)> {
+        let package_root = self.packages_root.join(name).join(version.to_string());
+
+        let response = self
+            .request(client, registry, package.dist.tarball.clone())
+            .send()
+            .with_context(|| format!("could not download package {name}@{version}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            bail!("npm registry returned status {status} when downloading {name}@{version}")
+        }
+
+        std::fs::create_dir_all(&self.packages_root)?;
+        let download_path = self
+            .packages_root
+            .join(format!("{}.download", random_string(12)));
+
+        let unpacked = download_to_file::<Sha512>(response, &download_path)
+            .with_context(|| format!("could not download package {name}@{version}"))
+            .and_then(|digest| {
+                if let Some(integrity) = &package.dist.integrity {
+                    check_integrity(&digest, integrity)
+                        .with_context(|| format!("could not verify package {name}@{version}"))?;
+                }
+
+                if package_root.try_exists()? {
+                    std::fs::remove_dir_all(&package_root)?;
+                }
+                std::fs::create_dir_all(&package_root)?;
+
+                // npm packages files are all in a top-level "package" directory.
+                unpack_tarball(
+                    GzDecoder::new(BufReader::new(File::open(&download_path)?)),
+                    &package_root,
+                    1,
+                )
+                .with_context(|| format!("could not unpack package {name}@{version}"))
+            });
+        let _ = std::fs::remove_file(&download_path);
+        unpacked?;
+
+        self.context.logger.debug(format!(
+            "{name}@{version} was unpacked to {}",
+            package_root.display()
+        ));
+
+        let ExecutorWithMetadata { executor, metadata } =
+            self.get_loader().load_from_src(&package_root)?;
+
+        Ok((
+            executor,
+            to_value(State {
+                version: version.to_string(),
+                package_root,
+                metadata,
+            })?,
+        ))
+    }
+
+    fn get_loader(&self) -> Box<dyn ExecutorLoader> {
+        ExecutorLoadStrategy::NodePackage.get_loader(LoaderContext {
+            workspace: self.context.workspace,
+        })
+    }
 }
 
 impl ExecutorResolver for NpmResolver<'_> {
-    fn resolve(&self, _url: &Url) -> Result<ExecutorResolution> {
-        todo!()
+    fn resolve(&self, url: &Url) -> Result<ExecutorResolution> {
+        let name = package_name(url)?;
+        let registry = self.registry(url)?;
+        let client = self.client()?;
+        let metadata = self.fetch_metadata(&client, &registry, &name)?;
+        let (version, package) = self.select_version(&name, &metadata)?;
+
+        let (executor, state) = self.install(&client, &registry, &name, &version, package)?;
+
+        Ok(ExecutorResolution { executor, state })
+    }
+
+    fn update(&self, url: &Url, state: &Value) -> Result<ExecutorUpdate> {
+        let name = package_name(url)?;
+        let state = State::deserialize(state)?;
+        let registry = self.registry(url)?;
+        let client = self.client()?;
+
+        let up_to_date = || -> Result<ExecutorUpdate> {
+            Ok(ExecutorUpdate {
+                executor: self.get_loader().load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            })
+        };
+
+        let metadata = match self.fetch_metadata(&client, &registry, &name) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                self.context.logger.warn(format!(
+                    "could not check for updates of {name}, using version {} ({err:#})",
+                    state.version
+                ));
+                return up_to_date();
+            }
+        };
+
+        let (version, package) = self.select_version(&name, &metadata)?;
+
+        if version.to_string() == state.version {
+            self.context
+                .logger
+                .debug(format!("{name}@{version} is the latest matching version"));
+            return up_to_date();
+        }
+
+        self.context.logger.debug(format!(
+            "{name} will be updated from {} to {version}",
+            state.version
+        ));
+
+        let (executor, new_state) = self.install(&client, &registry, &name, &version, package)?;
+
+        if state.package_root.try_exists()? {
+            std::fs::remove_dir_all(&state.package_root)?;
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+
+    fn pin(&self, state: &Value) -> Result<Option<String>> {
+        Ok(Some(State::deserialize(state)?.version))
+    }
+
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        let name = package_name(url)?;
+        let state = state.map(State::deserialize).transpose()?;
+
+        if let Some(state) = state.as_ref().filter(|state| state.version == pin) {
+            return Ok(ExecutorUpdate {
+                executor: self.get_loader().load_from_metadata(&state.metadata)?,
+                new_state: None,
+                updated: false,
+            });
+        }
+
+        let registry = self.registry(url)?;
+        let client = self.client()?;
+        let metadata = self.fetch_metadata(&client, &registry, &name)?;
+        let version = Version::parse(pin).with_context(|| format!("invalid version {pin}"))?;
+        let package = metadata
+            .versions
+            .get(pin)
+            .ok_or_else(|| anyhow!("version {pin} of package {name} does not exist"))?;
+
+        let (executor, new_state) = self.install(&client, &registry, &name, &version, package)?;
+
+        if let Some(state) = state {
+            if state.package_root.try_exists()? {
+                std::fs::remove_dir_all(&state.package_root)?;
+            }
+        }
+
+        Ok(ExecutorUpdate {
+            executor,
+            new_state: Some(new_state),
+            updated: true,
+        })
+    }
+}
+
+/// Get the package name from an npm URL (`npm://[<host>[:<port>]]/<package>`).
+fn package_name(url: &Url) -> Result<String> {
+    match url.path().trim_matches('/') {
+        "" => bail!("npm executor URL {url} does not have any package name"),
+        name => Ok(name.to_owned()),
+    }
+}
+
+/// Parse an npm version range (see <https://github.com/npm/node-semver#ranges>).
+/// Each comparator set separated by `||` is converted to a requirement, a version matches the range if it matches any of them.
+fn parse_version_range(range: &str) -> Result<Vec<VersionReq>> {
+    let mut requirements = Vec::new();
+
+    for set in range.split("||") {
+        let comparators = match set.split_once(" - ") {
+            Some((lower, upper)) => vec![
+                convert_comparator(">=", lower.trim())?,
+                convert_comparator("<=", upper.trim())?,
+            ],
+            None => {
+                let mut comparators = Vec::new();
+                let mut tokens = set.split_whitespace();
+                while let Some(token) = tokens.next() {
+                    let (operator, version) = split_operator(token);
+                    // operators can be separated from their version by spaces (e.g. ">= 1.2.3").
+                    let version = match version {
+                        "" => tokens
+                            .next()
+                            .ok_or_else(|| anyhow!("missing version after \"{operator}\""))?,
+                        version => version,
+                    };
+                    comparators.push(convert_comparator(operator, version)?);
+                }
+                comparators
+            }
+        };
+
+        requirements.push(if comparators.is_empty() {
+            VersionReq::STAR
+        } else {
+            VersionReq::parse(&comparators.join(", "))?
+        });
     }
 
-    fn update(&self, _url: &Url, _state: &Value) -> Result<ExecutorUpdate> {
-        todo!()
+    Ok(requirements)
+}
+
+fn split_operator(comparator: &str) -> (&str, &str) {
+    for operator in ["<=", ">=", "~>", "<", ">", "=", "~", "^"] {
+        if let Some(version) = comparator.strip_prefix(operator) {
+            return (operator, version);
+        }
+    }
+    ("", comparator)
+}
+
+/// Convert an npm comparator to its Cargo equivalent.
+fn convert_comparator(operator: &str, version: &str) -> Result<String> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    // build metadata is ignored when comparing versions.
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    let (core, prerelease) = match version.split_once('-') {
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version, None),
+    };
+
+    // a partial version is cut at the first missing or wildcard (x, X or *) component.
+    let mut components = Vec::new();
+    for component in core.split('.') {
+        if ["x", "X", "*"].contains(&component) {
+            break;
+        }
+        components.push(
+            component
+                .parse::<u64>()
+                .with_context(|| format!("invalid version \"{version}\""))?,
+        );
+    }
+    if components.len() > 3 {
+        bail!("invalid version \"{version}\"")
+    }
+
+    if components.is_empty() {
+        // "<*" and ">*" cannot match any version, other wildcards match every version.
+        return Ok(match operator {
+            "<" | ">" => "<0.0.0-0",
+            _ => ">=0.0.0",
+        }
+        .to_owned());
     }
+
+    let mut version = components
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(".");
+    if let Some(prerelease) = prerelease.filter(|_| components.len() == 3) {
+        version = format!("{version}-{prerelease}");
+    }
+
+    // a bare version is an exact version, and Cargo partial comparators already behave like npm ones.
+    let operator = match operator {
+        "" => "=",
+        "~>" => "~",
+        operator => operator,
+    };
+
+    Ok(format!("{operator}{version}"))
+}
+
+/// Check a SHA-512 digest against a subresource integrity string. Other hashes are ignored.
+fn check_integrity(digest: &[u8], integrity: &str) -> Result<()> {
+    let expected = integrity
+        .split_whitespace()
+        .filter_map(|hash| hash.strip_prefix("sha512-"))
+        .collect::<Vec<_>>();
+
+    if expected.is_empty() {
+        return Ok(());
+    }
+
+    let actual = BASE64_STANDARD.encode(digest);
+
+    if !expected.contains(&actual.as_str()) {
+        bail!("integrity check failed (expected {integrity}, got sha512-{actual})")
+    }
+
+    Ok(())
 }
@@ -1,13 +1,17 @@
+use anyhow::bail;
 use blaze_common::{error::Result, executor::Location, value::Value};
 use url::Url;
 
 use crate::executors::DynExecutor;
 
 use super::{
+    cargo::{CargoResolver, CargoResolverContext},
     file_system::{FileSystemResolver, FileSystemResolverContext},
     git::GitResolver,
     git_common::GitResolverContext,
     http_git::GitOverHttpResolver,
+    http_tarball::{TarballOverHttpResolver, TarballOverHttpResolverContext},
+    npm::{NpmResolver, NpmResolverContext},
     ssh_git::GitOverSshResolver,
     CustomResolutionContext,
 };
@@ -27,6 +31,23 @@ pub trait ExecutorResolver {
     fn resolve(&self, url: &Url) -> Result<ExecutorResolution>;
 
     fn update(&self, url: &Url, state: &Value) -> Result<ExecutorUpdate>;
+
+    /// Get the exact version (commit, package version, archive digest...) that a resolution state points to.
+    /// It is recorded in the lockfile. Executors without any version are never locked.
+    fn pin(&self, _state: &Value) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Resolve the executor at a version previously returned by [`ExecutorResolver::pin`].
+    /// If the current resolution state is already at this version, the executor must be loaded without checking for updates.
+    fn resolve_pinned(
+        &self,
+        url: &Url,
+        pin: &str,
+        _state: Option<&Value>,
+    ) -> Result<ExecutorUpdate> {
+        bail!("executor {url} cannot be resolved at locked version {pin}")
+    }
 }
 
 pub fn resolver_for_location<'a>(
@@ -36,7 +57,7 @@ pub fn resolver_for_location<'a>(
     let git_context = || GitResolverContext {
         logger: context.logger,
         workspace: context.workspace,
-        save_in_workspace: context.cache.is_some()
+        save_in_workspace: context.cache.is_some(),
     };
 
     match location {
@@ -53,7 +74,7 @@ pub fn resolver_for_location<'a>(
             authentication,
         } => Box::new(GitOverHttpResolver::new(
             git_options,
-            transport,  
+            transport,
             authentication,
             git_context(),
         )),
@@ -67,6 +88,32 @@ pub fn resolver_for_location<'a>(
             authentication,
             git_context(),
         )),
-        _ => todo!(),
+        Location::Npm { options } => Box::new(NpmResolver::new(
+            options,
+            NpmResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
+        Location::Cargo { options } => Box::new(CargoResolver::new(
+            options,
+            CargoResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
+        Location::TarballOverHttp {
+            transport,
+            tarball_options,
+            authentication,
+        } => Box::new(TarballOverHttpResolver::new(
+            transport,
+            tarball_options,
+            authentication,
+            TarballOverHttpResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
     }
 }
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     executors::{
         bridge::{bridge_executor, BridgeProcessParams},
+        capabilities::ExecutorCapabilities,
         Executor, ExecutorContext,
     },
     system::locks::ProcessLock,
@@ -35,19 +36,38 @@ const BRIDGE_CHECKSUM_FILENAME: &str = "checksum.txt";
 pub struct RustExecutor {
     library_path: PathBuf,
     exported_symbol_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capabilities: Option<ExecutorCapabilities>,
 }
 
 impl RustExecutor {
-    pub fn new(library_path: &Path, export_fn: &str) -> Self {
+    pub fn new(
+        library_path: &Path,
+        export_fn: &str,
+        capabilities: Option<ExecutorCapabilities>,
+    ) -> Self {
         Self {
             library_path: library_path.to_owned(),
             exported_symbol_name: export_fn.to_owned(),
+            capabilities,
         }
     }
+
+    pub fn library_path(&self) -> &Path {
+        &self.library_path
+    }
+
+    pub fn exported_symbol_name(&self) -> &str {
+        &self.exported_symbol_name
+    }
+
+    pub fn capabilities(&self) -> Option<&ExecutorCapabilities> {
+        self.capabilities.as_ref()
+    }
 }
 
 impl Executor for RustExecutor {
-    fn execute(&self, context: ExecutorContext, options: Value) -> Result<()> {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
         let bridge = install_bridge_executable(context.workspace.root())?;
 
         bridge_executor(
@@ -56,6 +76,7 @@ impl Executor for RustExecutor {
                 program: path_to_string(bridge)?.as_str(),
                 arguments: &[],
                 input: None,
+                capabilities: self.capabilities.as_ref(),
             },
             BridgeMetadata { executor_ref: self },
         )
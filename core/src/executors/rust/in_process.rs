@@ -0,0 +1,150 @@
+use std::{
+    collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Arc, Mutex},
+};
+
+use anyhow::{anyhow, Context};
+use blaze_common::{bridge::ProgressParams, error::Result, value::Value};
+use blaze_devkit::{check_abi_version, ExecutorFn, ExecutorHost};
+use libloading::Library;
+use once_cell::sync::Lazy;
+
+use crate::executors::{
+    bridge::{format_progress, resolve_workspace_path},
+    Executor, ExecutorContext,
+};
+
+use super::executor::RustExecutor;
+
+/// Executor libraries, which are loaded once and kept until the Blaze process exits.
+static LIBRARIES: Lazy<Mutex<HashMap<PathBuf, Arc<Library>>>> = Lazy::new(Mutex::default);
+
+fn load_library(path: &Path) -> Result<Arc<Library>> {
+    let mut libraries = LIBRARIES
+        .lock()
+        .map_err(|_| anyhow!("poison error (Mutex on executor libraries)."))?;
+    if let Some(library) = libraries.get(path) {
+        return Ok(library.clone());
+    }
+    let library = unsafe {
+        Library::new(path)
+            .with_context(|| format!("could not load library at {}", path.display()))?
+    };
+    check_abi_version(&library)
+        .with_context(|| format!("cannot use executor library at {}", path.display()))?;
+    let library = Arc::new(library);
+    libraries.insert(path.to_owned(), library.clone());
+    Ok(library)
+}
+
+/// Rust executor whose library is loaded in the Blaze process, instead of being run by the bridge executable.
+pub struct InProcessRustExecutor {
+    executor: RustExecutor,
+}
+
+impl InProcessRustExecutor {
+    pub fn new(executor: RustExecutor) -> Self {
+        Self { executor }
+    }
+}
+
+impl Executor for InProcessRustExecutor {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
+        if context
+            .project
+            .targets()
+            .get(context.target)
+            .is_some_and(|target| target.timeout().is_some())
+        {
+            // an in-process executor cannot be stopped if it ignores cancellation.
+            context.logger.debug(
+                "target has a timeout, Rust executor will be executed by the bridge executable",
+            );
+            return self.executor.execute(context, options);
+        }
+
+        if self.executor.capabilities().is_some() || context.remote {
+            // the Blaze process itself cannot be sandboxed.
+            context
+                .logger
+                .debug("Rust executor is sandboxed, it will be executed by the bridge executable");
+            return self.executor.execute(context, options);
+        }
+
+        if context
+            .project
+            .targets()
+            .get(context.target)
+            .is_some_and(|target| target.environment().is_some())
+        {
+            context.logger.warn(
+                "target environment is not applied to Rust executors when they are executed in-process",
+            );
+        }
+
+        let library = load_library(self.executor.library_path())?;
+
+        let executor_fn = unsafe {
+            *library
+                .get::<ExecutorFn>(self.executor.exported_symbol_name().as_bytes())
+                .with_context(|| {
+                    format!(
+                        "could not load executor exported function \"{}\"",
+                        self.executor.exported_symbol_name()
+                    )
+                })?
+        };
+
+        let host = InProcessHost { context: &context };
+
+        let devkit_context = blaze_devkit::ExecutorContext {
+            workspace: context.workspace,
+            project: context.project,
+            target: context.target,
+            logger: context.logger,
+            host: &host,
+        };
+
+        catch_unwind(AssertUnwindSafe(|| executor_fn(devkit_context, options)))
+            .map_err(|panic_error| {
+                if let Some(message) = panic_error.downcast_ref::<String>() {
+                    anyhow!("executor panicked: {message}")
+                } else if let Some(message) = panic_error.downcast_ref::<&str>() {
+                    anyhow!("executor panicked: {message}")
+                } else {
+                    anyhow!("executor panicked (unknown value received {panic_error:?})")
+                }
+            })?
+            .map_err(|executor_error| anyhow!(executor_error).context("executor returned an error"))
+    }
+}
+
+struct InProcessHost<'a> {
+    context: &'a ExecutorContext<'a>,
+}
+
+impl ExecutorHost for InProcessHost<'_> {
+    fn is_canceled(&self) -> bool {
+        self.context
+            .canceled
+            .is_some_and(|canceled| canceled.load(Ordering::SeqCst))
+    }
+
+    fn progress(&self, progress: ProgressParams) {
+        self.context.logger.info(format_progress(&progress));
+    }
+
+    fn resolve_path(
+        &self,
+        path: &Path,
+    ) -> std::result::Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        resolve_workspace_path(
+            self.context.workspace.root(),
+            self.context.project.root(),
+            path,
+        )
+        .map_err(Into::into)
+    }
+}
@@ -11,7 +11,7 @@ use serde::Deserialize;
 use crate::{
     executors::{
         loader::{ExecutorLoader, ExecutorWithMetadata},
-        rust::executor::RustExecutor,
+        rust::{executor::RustExecutor, in_process::InProcessRustExecutor},
         DynExecutor,
     },
     system::{
@@ -30,19 +30,29 @@ const EXECUTORS_LOCATION: &str = ".blaze/rust/lib";
 
 pub struct LocalRustExecutorLoader {
     workspace_root: PathBuf,
+    in_process: bool,
 }
 
 impl LocalRustExecutorLoader {
-    pub fn new(workspace_root: &Path) -> Self {
+    pub fn new(workspace_root: &Path, in_process: bool) -> Self {
         Self {
             workspace_root: workspace_root.to_owned(),
+            in_process,
+        }
+    }
+
+    fn into_dyn_executor(&self, executor: RustExecutor) -> DynExecutor {
+        if self.in_process {
+            Box::new(InProcessRustExecutor::new(executor))
+        } else {
+            Box::new(executor)
         }
     }
 }
 
 impl ExecutorLoader for LocalRustExecutorLoader {
     fn load_from_metadata(&self, metadata: &Value) -> Result<DynExecutor> {
-        Ok(Box::new(RustExecutor::deserialize(metadata)?))
+        Ok(self.into_dyn_executor(RustExecutor::deserialize(metadata)?))
     }
 
     fn load_from_src(&self, root: &Path) -> Result<ExecutorWithMetadata> {
@@ -60,6 +70,7 @@ impl ExecutorLoader for LocalRustExecutorLoader {
                     path_to_string(root.join("target"))?,
                 )]
                 .into(),
+                ..Default::default()
             },
         )?;
 
@@ -104,6 +115,7 @@ impl ExecutorLoader for LocalRustExecutorLoader {
                     path_to_string(root.join("target"))?,
                 )]
                 .into(),
+                ..Default::default()
             },
         )?
         .wait()?
@@ -115,11 +127,15 @@ impl ExecutorLoader for LocalRustExecutorLoader {
             );
         }
 
-        let executor = RustExecutor::new(&library_path, &package.exported_fn);
+        let executor = RustExecutor::new(
+            &library_path,
+            &package.exported_fn,
+            package.capabilities.clone(),
+        );
         let metadata = to_value(&executor)?;
 
         Ok(ExecutorWithMetadata {
-            executor: Box::new(executor),
+            executor: self.into_dyn_executor(executor),
             metadata,
         })
     }
@@ -1,4 +1,5 @@
 pub mod executor;
+pub mod in_process;
 pub mod loaders;
 mod package;
 pub use package::is_rust_executor;
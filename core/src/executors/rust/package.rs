@@ -3,6 +3,8 @@ use std::path::Path;
 use anyhow::{anyhow, bail, Context};
 use blaze_common::{error::Result, value::Value};
 
+use crate::executors::capabilities::ExecutorCapabilities;
+
 const CARGO_TOML: &str = "Cargo.toml";
 
 const CRATE_TYPE: &str = "lib.crate-type";
@@ -10,6 +12,7 @@ const NAME: &str = "package.name";
 const EXPORTED: &str = "package.metadata.blaze.exported";
 const TYPE: &str = "package.metadata.blaze.type";
 const VERSION: &str = "package.metadata.blaze.version";
+const CAPABILITIES: &str = "package.metadata.blaze.capabilities";
 
 pub fn is_rust_executor(root: &Path) -> Result<bool> {
     Ok(match std::fs::metadata(root.join(CARGO_TOML)) {
@@ -22,6 +25,7 @@ pub fn is_rust_executor(root: &Path) -> Result<bool> {
 pub struct RustExecutorPackage {
     pub name: String,
     pub exported_fn: String,
+    pub capabilities: Option<ExecutorCapabilities>,
 }
 
 impl RustExecutorPackage {
@@ -89,6 +93,7 @@ impl RustExecutorPackage {
         Ok(Self {
             name: name.to_owned(),
             exported_fn: exported.to_owned(),
+            capabilities: ExecutorCapabilities::from_metadata(&manifest, CAPABILITIES)?,
         })
     }
 }
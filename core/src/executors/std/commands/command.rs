@@ -47,6 +47,8 @@ pub struct Command {
     pub on_failure: OnFailure,
     pub cwd: Option<PathBuf>,
     pub environment: HashMap<String, String>,
+    /// Whether the environment of the Blaze process is not inherited.
+    pub clear_environment: bool,
     pub quiet: bool,
 }
 
@@ -59,6 +61,7 @@ impl FromStr for Command {
             cwd: None,
             detach: false,
             environment: Default::default(),
+            clear_environment: false,
             on_failure: OnFailure::default(),
             quiet: false,
         })
@@ -104,6 +107,7 @@ impl<'de> Deserialize<'de> for Command {
                     cwd: command.cwd,
                     detach: command.detach,
                     environment: command.environment,
+                    clear_environment: false,
                     on_failure: command.on_failure,
                     quiet: command.quiet,
                 },
@@ -162,6 +166,7 @@ impl Command {
                     cwd: self_clone.cwd.to_owned(),
                     display_output: !self_clone.quiet,
                     environment: self_clone.environment.to_owned(),
+                    clear_environment: self_clone.clear_environment,
                 },
             )
             .with_context(|| format!("error while creating process for command \"{self_clone}\"."))
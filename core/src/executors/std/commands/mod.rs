@@ -3,7 +3,10 @@ use blaze_common::{error::Result, value::Value};
 use serde::Deserialize;
 
 use crate::{
-    executors::{env::get_executor_env, Executor, ExecutorContext},
+    executors::{
+        env::{get_executor_env, isolates_environment},
+        Executor, ExecutorContext,
+    },
     system::process::ProcessStatus,
 };
 
@@ -28,7 +31,7 @@ struct CommandsExecutorOptions {
 pub struct CommandsExecutor;
 
 impl Executor for CommandsExecutor {
-    fn execute(&self, ctx: ExecutorContext, raw_options: Value) -> Result<()> {
+    fn execute(&self, ctx: ExecutorContext, raw_options: Value) -> Result<Value> {
         let options = CommandsExecutorOptions::deserialize(&raw_options).with_context(|| {
             format!("error while converting commands executor options from {raw_options}")
         })?;
@@ -37,6 +40,7 @@ impl Executor for CommandsExecutor {
             use_shell: options.use_shell,
             default_cwd: ctx.project.root().to_owned(),
             default_environment: get_executor_env(&ctx)?,
+            clear_environment: isolates_environment(&ctx),
         });
 
         runner.on_command_started(|command| {
@@ -68,6 +72,6 @@ impl Executor for CommandsExecutor {
             .run_all(&options.commands)
             .context("error while running commands")?;
 
-        Ok(())
+        Ok(Value::Null)
     }
 }
@@ -4,10 +4,13 @@ use std::{
     sync::mpsc::{channel, Receiver},
 };
 
-use anyhow::{bail, Context};
-use blaze_common::error::Result;
+use anyhow::Context;
+use blaze_common::error::{Error, Result};
 
-use crate::{executors::std::options::UseShell, system::process::ProcessStatus};
+use crate::{
+    executors::std::options::UseShell,
+    system::process::{ProcessFailure, ProcessStatus},
+};
 
 use super::command::{Command, OnFailure, RunningCommand};
 
@@ -20,6 +23,7 @@ pub struct CommandsRunnerOptions {
     pub use_shell: Option<UseShell>,
     pub default_cwd: PathBuf,
     pub default_environment: HashMap<String, String>,
+    pub clear_environment: bool,
 }
 
 type CommandStartedHandler<'a> = Box<dyn Fn(&Command) + 'a>;
@@ -105,9 +109,16 @@ impl<'a> CommandsRunner<'a> {
                         );
                     }
                     OnFailure::Ignore => (),
-                    OnFailure::ForceExit => return Self::kill_all(terminated_cmd, running),
+                    OnFailure::ForceExit => {
+                        return Self::kill_all(terminated_cmd, termination.status, running)
+                    }
                     OnFailure::Exit => {
-                        return Self::command_failure(terminated_cmd, running, termination_recv)
+                        return Self::command_failure(
+                            terminated_cmd,
+                            termination.status,
+                            running,
+                            termination_recv,
+                        )
                     }
                 }
             }
@@ -116,7 +127,11 @@ impl<'a> CommandsRunner<'a> {
         Ok(())
     }
 
-    fn kill_all(cause: Command, running: BTreeMap<usize, RunningCommand>) -> Result<()> {
+    fn kill_all(
+        cause: Command,
+        status: ProcessStatus,
+        running: BTreeMap<usize, RunningCommand>,
+    ) -> Result<()> {
         let kills = running
             .into_values()
             .map(RunningCommand::kill)
@@ -138,17 +153,20 @@ impl<'a> CommandsRunner<'a> {
             Some(details.join(", "))
         };
 
-        bail!(
-            "command {} failed {}",
-            cause,
-            error_details
-                .map(|details| format!("({details})"))
-                .unwrap_or(String::default())
+        Err(
+            Error::new(ProcessFailure { code: status.code }).context(format!(
+                "command {} failed {}",
+                cause,
+                error_details
+                    .map(|details| format!("({details})"))
+                    .unwrap_or(String::default())
+            )),
         )
     }
 
     fn command_failure(
         cause: Command,
+        status: ProcessStatus,
         mut running: BTreeMap<usize, RunningCommand>,
         termination_recv: Receiver<Result<Termination>>,
     ) -> Result<()> {
@@ -171,7 +189,7 @@ impl<'a> CommandsRunner<'a> {
                 "(all detached processes exited successfully)".into()
             })
         }
-        bail!(error_msg)
+        Err(Error::new(ProcessFailure { code: status.code }).context(error_msg))
     }
 
     fn configure_cmd(&self, command: &mut Command) -> Result<()> {
@@ -186,6 +204,7 @@ impl<'a> CommandsRunner<'a> {
         command
             .environment
             .extend(self.options.default_environment.to_owned());
+        command.clear_environment = self.options.clear_environment;
 
         Ok(())
     }
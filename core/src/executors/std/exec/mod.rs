@@ -1,14 +1,21 @@
 use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
-    executors::{env::get_executor_env, Executor, ExecutorContext},
+    executors::{
+        env::{get_executor_env, isolates_environment},
+        Executor, ExecutorContext,
+    },
     system::{
-        process::{Process, ProcessOptions},
+        process::{Process, ProcessFailure, ProcessOptions},
         shell::ShellFormatter,
     },
 };
-use anyhow::{bail, Context};
-use blaze_common::{error::Result, util::normalize_path, value::Value};
+use anyhow::Context;
+use blaze_common::{
+    error::{Error, Result},
+    util::normalize_path,
+    value::Value,
+};
 use serde::Deserialize;
 
 use super::options::UseShell;
@@ -30,7 +37,7 @@ struct Options {
 pub struct ExecExecutor;
 
 impl Executor for ExecExecutor {
-    fn execute(&self, context: ExecutorContext, options: Value) -> Result<()> {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
         let mut options =
             Options::deserialize(options).context("could not deserialize executor options")?;
 
@@ -61,6 +68,7 @@ impl Executor for ExecExecutor {
         }
 
         options.environment.extend(get_executor_env(&context)?);
+        let clear_environment = isolates_environment(&context);
 
         let cwd = options
             .cwd
@@ -93,6 +101,7 @@ impl Executor for ExecExecutor {
                 cwd: Some(cwd),
                 display_output: !options.quiet,
                 environment: options.environment,
+                clear_environment,
             },
         )
         .with_context(|| format!("could not create process for \"{}\"", program.display()))?
@@ -108,13 +117,13 @@ impl Executor for ExecExecutor {
                     .map(|c| format!("with status code {c}"))
                     .unwrap_or_else(|| "without any status code".into())
             );
-            bail!(error)
+            return Err(Error::new(ProcessFailure { code: result.code }).context(error));
         }
 
         context
             .logger
             .debug(format!("{} terminated successfully.", program.display()));
 
-        Ok(())
+        Ok(Value::Null)
     }
 }
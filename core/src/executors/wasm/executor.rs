@@ -0,0 +1,29 @@
+use std::path::{Path, PathBuf};
+
+use blaze_common::{error::Result, value::Value};
+use serde::{Deserialize, Serialize};
+
+use crate::executors::{Executor, ExecutorContext};
+
+use super::runtime::run_component;
+
+/// Executor compiled as a WASI component, which runs in an embedded runtime instead of a bridge process.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmExecutor {
+    component_path: PathBuf,
+}
+
+impl WasmExecutor {
+    pub fn new(component_path: &Path) -> Self {
+        Self {
+            component_path: component_path.to_owned(),
+        }
+    }
+}
+
+impl Executor for WasmExecutor {
+    fn execute(&self, context: ExecutorContext, options: Value) -> Result<Value> {
+        run_component(&self.component_path, &context, &options)
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use blaze_common::{
+    error::Result,
+    value::{to_value, Value},
+};
+use serde::Deserialize;
+
+use crate::executors::{
+    loader::{ExecutorLoader, ExecutorWithMetadata},
+    DynExecutor,
+};
+
+use super::{executor::WasmExecutor, package::install_component};
+
+/// This loader will copy the executor component in the workspace before loading.
+pub struct LocalWasmExecutorLoader {
+    workspace_root: PathBuf,
+}
+
+impl LocalWasmExecutorLoader {
+    pub fn new(workspace_root: &Path) -> Self {
+        Self {
+            workspace_root: workspace_root.to_owned(),
+        }
+    }
+}
+
+impl ExecutorLoader for LocalWasmExecutorLoader {
+    fn load_from_src(&self, root: &Path) -> Result<ExecutorWithMetadata> {
+        let component_path = install_component(&self.workspace_root, root)
+            .with_context(|| format!("failed to load wasm executor at {}", root.display()))?;
+
+        let executor = Box::new(WasmExecutor::new(&component_path));
+
+        Ok(ExecutorWithMetadata {
+            metadata: to_value(&executor)?,
+            executor,
+        })
+    }
+
+    fn load_from_metadata(&self, metadata: &Value) -> Result<DynExecutor> {
+        Ok(Box::new(WasmExecutor::deserialize(metadata)?))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod executor;
+pub mod loaders;
+mod package;
+mod runtime;
+pub use package::is_wasm_executor;
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use blaze_common::error::Result;
+
+use crate::system::random::random_string;
+
+use super::runtime::load_component;
+
+/// Compiled WASI component, at the root of the executor directory.
+pub const COMPONENT_FILENAME: &str = "executor.wasm";
+
+const COMPONENTS_LOCATION: &str = ".blaze/wasm";
+
+pub fn is_wasm_executor(root: &Path) -> Result<bool> {
+    Ok(match std::fs::metadata(root.join(COMPONENT_FILENAME)) {
+        Ok(metadata) => metadata.is_file(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+        Err(err) => return Err(err.into()),
+    })
+}
+
+/// Copy the executor component in the workspace, so that it is not affected by changes in the executor directory.
+/// The component is compiled in order to make sure that it is valid.
+pub fn install_component(workspace_root: &Path, executor_root: &Path) -> Result<PathBuf> {
+    let source_path = executor_root.join(COMPONENT_FILENAME);
+
+    if !source_path.is_file() {
+        bail!(
+            "wasm executor component was not found at {}",
+            source_path.display()
+        );
+    }
+
+    let components_location = workspace_root.join(COMPONENTS_LOCATION);
+
+    std::fs::create_dir_all(&components_location).with_context(|| {
+        format!(
+            "error while creating wasm executors directory at {}",
+            components_location.display()
+        )
+    })?;
+
+    let component_path = components_location.join(format!("{}.wasm", random_string(16)));
+
+    std::fs::copy(&source_path, &component_path).with_context(|| {
+        format!(
+            "could not copy wasm executor component from {}",
+            source_path.display()
+        )
+    })?;
+
+    load_component(&component_path)?;
+
+    Ok(component_path)
+}
@@ -0,0 +1,296 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread::scope,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    bridge::ProgressParams,
+    error::Result,
+    logger::{LogLevel, Logger},
+    util::path_to_string,
+    value::Value,
+};
+use once_cell::sync::{Lazy, OnceCell};
+use wasmtime::{
+    component::{Component, Linker, ResourceTable},
+    Config, Engine, Store, UpdateDeadline,
+};
+use wasmtime_wasi::{
+    pipe::MemoryOutputPipe, DirPerms, FilePerms, WasiCtx, WasiCtxBuilder, WasiView,
+};
+
+use crate::{
+    executors::{
+        bridge::{error_from_causes, format_progress, resolve_workspace_path},
+        capabilities::add_output_directories,
+        env::get_executor_env,
+        ExecutorContext,
+    },
+    system::{
+        process::{forward_output, OutputStream},
+        thread::{join, thread},
+    },
+};
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "wit",
+        world: "executor",
+    });
+}
+
+use bindings::{
+    blaze::executor::{host, types},
+    Executor as ExecutorComponent,
+};
+
+/// Interval between epoch increments, which is also the maximum delay before a canceled execution is interrupted.
+const EPOCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Maximum size of the output written by a component to each standard stream.
+const MAX_OUTPUT_SIZE: usize = 64 * 1024 * 1024;
+
+static ENGINE: OnceCell<Engine> = OnceCell::new();
+
+/// Compiled components, indexed by their path.
+static COMPONENTS: Lazy<Mutex<HashMap<PathBuf, Component>>> = Lazy::new(Mutex::default);
+
+fn get_engine() -> Result<&'static Engine> {
+    ENGINE.get_or_try_init(|| {
+        let mut config = Config::new();
+        config.wasm_component_model(true).epoch_interruption(true);
+        Engine::new(&config).context("could not create wasm engine")
+    })
+}
+
+/// Compile a component, or get it from the cache if it was already compiled.
+pub fn load_component(path: &Path) -> Result<Component> {
+    let engine = get_engine()?;
+    let mut components = COMPONENTS
+        .lock()
+        .map_err(|_| anyhow!("poison error (Mutex on wasm components)."))?;
+
+    if let Some(component) = components.get(path) {
+        return Ok(component.clone());
+    }
+
+    let component = Component::from_file(engine, path)
+        .with_context(|| format!("could not compile wasm component at {}", path.display()))?;
+
+    components.insert(path.to_owned(), component.clone());
+
+    Ok(component)
+}
+
+/// Output written by a component to a standard stream, which is displayed and captured like the output of executor processes.
+struct ComponentOutput {
+    pipe: MemoryOutputPipe,
+    stream: OutputStream,
+    forwarded: usize,
+}
+
+impl ComponentOutput {
+    fn new(stream: OutputStream) -> Self {
+        Self {
+            pipe: MemoryOutputPipe::new(MAX_OUTPUT_SIZE),
+            stream,
+            forwarded: 0,
+        }
+    }
+
+    /// Forward what was written since the last call.
+    fn forward(&mut self) -> Result<()> {
+        let contents = self.pipe.contents();
+        if contents.len() > self.forwarded {
+            forward_output(self.stream, &contents[self.forwarded..])?;
+            self.forwarded = contents.len();
+        }
+        Ok(())
+    }
+}
+
+/// State of a component instance.
+struct WasmState {
+    wasi: WasiCtx,
+    table: ResourceTable,
+    logger: Logger,
+    canceled: Arc<AtomicBool>,
+    workspace_root: PathBuf,
+    project_root: PathBuf,
+}
+
+impl WasiView for WasmState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl types::Host for WasmState {}
+
+impl host::Host for WasmState {
+    fn log(&mut self, level: host::LogLevel, message: String) {
+        self.logger.log(
+            message,
+            match level {
+                host::LogLevel::Debug => LogLevel::Debug,
+                host::LogLevel::Info => LogLevel::Info,
+                host::LogLevel::Warn => LogLevel::Warn,
+                host::LogLevel::Error => LogLevel::Error,
+            },
+        )
+    }
+
+    fn is_canceled(&mut self) -> bool {
+        self.canceled.load(Ordering::SeqCst)
+    }
+
+    fn progress(&mut self, progress: host::Progress) {
+        self.logger.info(format_progress(&ProgressParams {
+            execution: None,
+            message: progress.message,
+            completed: progress.completed,
+            total: progress.total,
+        }))
+    }
+
+    fn resolve_path(&mut self, path: String) -> std::result::Result<String, String> {
+        resolve_workspace_path(&self.workspace_root, &self.project_root, Path::new(&path))
+            .and_then(path_to_string)
+            .map_err(|err| format!("{err:#}"))
+    }
+}
+
+/// Run a component in a sandbox.
+/// Only the project root and the directories of the target declared outputs can be accessed, and network is disabled.
+pub fn run_component(path: &Path, context: &ExecutorContext, options: &Value) -> Result<Value> {
+    let engine = get_engine()?;
+    let component = load_component(path)?;
+
+    let mut linker = Linker::<WasmState>::new(engine);
+    wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+    ExecutorComponent::add_to_linker(&mut linker, |state: &mut WasmState| state)?;
+
+    let canceled = Arc::new(AtomicBool::new(false));
+    let mut stdout = ComponentOutput::new(OutputStream::Stdout);
+    let mut stderr = ComponentOutput::new(OutputStream::Stderr);
+
+    let mut store = Store::new(
+        engine,
+        WasmState {
+            wasi: create_wasi_context(context, &stdout, &stderr)?,
+            table: ResourceTable::new(),
+            logger: context.logger.clone(),
+            canceled: canceled.clone(),
+            workspace_root: context.workspace.root().to_owned(),
+            project_root: context.project.root().to_owned(),
+        },
+    );
+
+    // the timeout is also enforced here, so that the component is interrupted even if cancellation is not requested in time.
+    // a timeout which cannot be represented as an instant is the same as no timeout.
+    let timeout_deadline = context
+        .project
+        .targets()
+        .get(context.target)
+        .and_then(|target| target.timeout())
+        .and_then(|timeout| Instant::now().checked_add(timeout.duration()));
+
+    let deadline_canceled = canceled.clone();
+    store.set_epoch_deadline(1);
+    store.epoch_deadline_callback(move |_| {
+        if deadline_canceled.load(Ordering::SeqCst) {
+            bail!("target execution was canceled");
+        }
+        if timeout_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            bail!("target execution timed out");
+        }
+        Ok(UpdateDeadline::Continue(1))
+    });
+
+    let context_input =
+        serde_json::to_string(context).context("could not serialize executor context")?;
+    let options_input =
+        serde_json::to_string(options).context("could not serialize executor options")?;
+
+    let result = scope(|scope| {
+        let (done_sender, done_receiver) = channel::<()>();
+        let target_canceled = context.canceled;
+
+        let (stdout, stderr) = (&mut stdout, &mut stderr);
+        let watcher = thread!(scope, move || -> Result<()> {
+            while let Err(RecvTimeoutError::Timeout) = done_receiver.recv_timeout(EPOCH_INTERVAL) {
+                if target_canceled
+                    .is_some_and(|target_canceled| target_canceled.load(Ordering::SeqCst))
+                {
+                    canceled.store(true, Ordering::SeqCst);
+                }
+                engine.increment_epoch();
+                stdout.forward()?;
+                stderr.forward()?;
+            }
+            stdout.forward()?;
+            stderr.forward()
+        });
+
+        let result = ExecutorComponent::instantiate(&mut store, &component, &linker)
+            .context("could not instantiate wasm component")
+            .and_then(|executor| executor.call_execute(&mut store, &context_input, &options_input));
+
+        drop(done_sender);
+        join!(watcher).context("could not forward wasm executor output")?;
+
+        result
+    })
+    .context("wasm executor failure")?;
+
+    match result {
+        Ok(output) => serde_json::from_str::<Value>(&output)
+            .context("wasm executor output is not a valid JSON document"),
+        Err(types::ExecutorError { message, causes }) => Err(error_from_causes(message, causes)),
+    }
+}
+
+fn create_wasi_context(
+    context: &ExecutorContext,
+    stdout: &ComponentOutput,
+    stderr: &ComponentOutput,
+) -> Result<WasiCtx> {
+    let mut builder = WasiCtxBuilder::new();
+
+    builder
+        .stdout(stdout.pipe.clone())
+        .stderr(stderr.pipe.clone())
+        .allow_tcp(false)
+        .allow_udp(false)
+        .allow_ip_name_lookup(false)
+        .envs(&get_executor_env(context)?.into_iter().collect::<Vec<_>>());
+
+    for directory in get_preopened_directories(context)? {
+        let guest_path = path_to_string(&directory)?;
+        builder
+            .preopened_dir(&directory, &guest_path, DirPerms::all(), FilePerms::all())
+            .with_context(|| format!("could not open directory {guest_path} for wasm executor"))?;
+    }
+
+    Ok(builder.build())
+}
+
+/// Get the project root, and the directories containing the target declared outputs.
+/// Output directories are created if they do not exist yet.
+fn get_preopened_directories(context: &ExecutorContext) -> Result<BTreeSet<PathBuf>> {
+    let mut directories = BTreeSet::from([context.project.root().to_owned()]);
+    add_output_directories(context, &mut directories)?;
+    Ok(directories)
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use anyhow::anyhow;
+use blaze_common::{error::Result, logger::Logger, value::Value};
+
+use crate::system::process::ProcessContext;
+
+use super::{
+    bridge::{get_worker_output, BridgeProcessParams, BridgeWorker, BridgeWorkerOptions},
+    env::get_workspace_env,
+    ExecutorContext,
+};
+
+/// Bridge processes which are kept alive in order to run several target executions, grouped by executor.
+/// If a bridge process terminates unexpectedly, only its running executions fail and it is started again for the next ones.
+/// All bridge processes are stopped when the pool is dropped.
+pub struct WorkerPool {
+    logger: Logger,
+    workers: Mutex<HashMap<u64, Vec<PooledWorker>>>,
+}
+
+/// A bridge process of the pool, along with the executions it is currently running.
+struct PooledWorker {
+    worker: Arc<BridgeWorker>,
+    /// Bridges change their working directory to the project root of each execution,
+    /// so a process only runs executions of a single project at the same time.
+    project_root: PathBuf,
+    running: usize,
+}
+
+impl WorkerPool {
+    pub fn new(logger: Logger) -> Self {
+        Self {
+            logger,
+            workers: Mutex::default(),
+        }
+    }
+
+    /// Run an execution in the bridge process identified by *key*, starting it if needed.
+    pub(super) fn execute(
+        &self,
+        context: &ExecutorContext<'_>,
+        process_params: &BridgeProcessParams<'_>,
+        key: u64,
+        params: Value,
+    ) -> Result<Value> {
+        let worker = self.acquire_worker(context, process_params, key)?;
+
+        let result = worker.execute(context, params);
+
+        self.release_worker(key, &worker)?;
+
+        let result = result?;
+
+        if result.is_none() {
+            self.logger.warn(format!(
+                "executor bridge worker for target {} terminated unexpectedly, it will be started again for next executions",
+                context.target
+            ));
+        }
+
+        get_worker_output(result)
+    }
+
+    /// Get a bridge process identified by *key* which is idle or already running executions of the same project, starting one if needed.
+    fn acquire_worker(
+        &self,
+        context: &ExecutorContext<'_>,
+        process_params: &BridgeProcessParams<'_>,
+        key: u64,
+    ) -> Result<Arc<BridgeWorker>> {
+        let mut workers = self.lock()?;
+        let project_root = context.project.root();
+
+        let candidates = workers.entry(key).or_default();
+        candidates.retain(|candidate| !candidate.worker.is_terminated());
+
+        let available = candidates
+            .iter()
+            .position(|candidate| candidate.running > 0 && candidate.project_root == project_root)
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .position(|candidate| candidate.running == 0)
+            });
+
+        if let Some(index) = available {
+            let candidate = &mut candidates[index];
+            candidate.project_root = project_root.to_owned();
+            candidate.running += 1;
+            return Ok(candidate.worker.clone());
+        }
+
+        self.logger.debug("starting executor bridge worker");
+
+        // worker processes must not be tracked or captured along with the target execution that started them.
+        let worker = Arc::new(ProcessContext::current().detached().enter(|| {
+            BridgeWorker::spawn(
+                process_params,
+                BridgeWorkerOptions {
+                    cwd: context.workspace.root(),
+                    environment: get_workspace_env(context.workspace)?,
+                    clear_environment: false,
+                    logger: &self.logger,
+                    workspace_root: context.workspace.root(),
+                    sandbox: None,
+                },
+            )
+        })?);
+
+        candidates.push(PooledWorker {
+            worker: worker.clone(),
+            project_root: project_root.to_owned(),
+            running: 1,
+        });
+
+        Ok(worker)
+    }
+
+    /// Mark an execution started with [`WorkerPool::acquire_worker`] as done.
+    fn release_worker(&self, key: u64, worker: &Arc<BridgeWorker>) -> Result<()> {
+        let mut workers = self.lock()?;
+
+        if let Some(pooled) = workers
+            .get_mut(&key)
+            .into_iter()
+            .flatten()
+            .find(|pooled| Arc::ptr_eq(&pooled.worker, worker))
+        {
+            pooled.running -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<u64, Vec<PooledWorker>>>> {
+        self.workers
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on executor workers)."))
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        let Ok(mut workers) = self.lock() else {
+            return;
+        };
+
+        let workers = workers
+            .drain()
+            .flat_map(|(_, workers)| workers)
+            .map(|pooled| pooled.worker)
+            .collect::<Vec<_>>();
+
+        for worker in &workers {
+            if let Err(err) = worker.shutdown() {
+                self.logger.debug(format!(
+                    "could not shut down executor bridge worker ({err})"
+                ));
+            }
+        }
+
+        for worker in workers {
+            if let Err(err) = worker.join() {
+                self.logger
+                    .warn(format!("executor bridge worker failed ({err:?})"));
+            }
+        }
+    }
+}
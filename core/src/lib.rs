@@ -7,8 +7,11 @@ mod workspace;
 
 pub use blaze_common as common;
 pub use executions::graph::ExecutedGraph;
+pub use executions::hermetic::HermeticViolations;
 pub use usecases::*;
 pub use workspace::selection::SelectorSource;
 
+#[cfg(feature = "testing")]
+pub use system::process::{OutputRedirection, OutputStream};
 #[cfg(feature = "testing")]
 pub use system::time;
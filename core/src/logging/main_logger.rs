@@ -1,8 +1,8 @@
 use super::colors::colorize;
+use crate::system::process::{display, OutputStream};
 use blaze_common::logger::{LogLevel, Logger, LoggingStrategy};
 use colored::*;
 use rand::{thread_rng, RngCore};
-use std::io::Write;
 
 pub fn get_logger(level: LogLevel) -> Logger {
     Logger::new(MainLoggingStrategy::new(level))
@@ -71,7 +71,7 @@ impl LoggingStrategy for MainLoggingStrategy {
             return;
         }
 
-        let write_to_stream = |s: &mut dyn Write| {
+        let write_to_stream = |stream: OutputStream| {
             let mut parts = vec![];
 
             if let Some(context) = &self.context {
@@ -85,12 +85,12 @@ impl LoggingStrategy for MainLoggingStrategy {
 
             parts.push(format!("[{}] {}\n", fmt_log_level(level), message));
 
-            let _ = s.write_all(parts.concat().as_bytes());
+            let _ = display(stream, parts.concat().as_bytes());
         };
 
         match level {
-            LogLevel::Info => write_to_stream(&mut std::io::stdout()),
-            _ => write_to_stream(&mut std::io::stderr()),
+            LogLevel::Info => write_to_stream(OutputStream::Stdout),
+            _ => write_to_stream(OutputStream::Stderr),
         };
     }
 }
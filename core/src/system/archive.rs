@@ -0,0 +1,72 @@
+use std::{
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use blaze_common::error::Result;
+use tar::EntryType;
+
+/// Unpack a tar archive into a directory, removing the first *strip_components* components from each entry path.
+/// Entries that would be written outside of the destination directory are rejected.
+pub fn unpack_tarball<R: Read>(
+    reader: R,
+    destination: &Path,
+    strip_components: usize,
+) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive
+        .entries()
+        .context("could not read archive entries")?
+    {
+        let mut entry = entry.context("could not read archive entry")?;
+        let entry_path = entry.path()?.into_owned();
+
+        let relative_path = entry_path
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .skip(strip_components)
+            .map(|component| match component {
+                Component::Normal(part) => Ok(part),
+                _ => bail!("archive entry {} has an invalid path", entry_path.display()),
+            })
+            .collect::<Result<PathBuf>>()?;
+
+        if relative_path.as_os_str().is_empty() {
+            continue;
+        }
+
+        if matches!(
+            entry.header().entry_type(),
+            EntryType::Symlink | EntryType::Link
+        ) {
+            let escapes = entry.link_name()?.is_some_and(|link| {
+                link.components().any(|component| {
+                    matches!(
+                        component,
+                        Component::ParentDir | Component::RootDir | Component::Prefix(_)
+                    )
+                })
+            });
+            if escapes {
+                bail!(
+                    "archive entry {} links outside of the archive",
+                    entry_path.display()
+                )
+            }
+        }
+
+        let target = destination.join(&relative_path);
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        entry
+            .unpack(&target)
+            .with_context(|| format!("could not unpack archive entry {}", entry_path.display()))?;
+    }
+
+    Ok(())
+}
@@ -31,6 +31,14 @@ impl Env {
             .transpose()
     }
 
+    /// Read the variables of a `.env` file, without setting them in the current process.
+    pub fn read_dotenv_file(path: &Path) -> Result<Vec<(String, String)>> {
+        dotenvy::from_path_iter(path)
+            .with_context(|| format!("could not get variables from {}", path.display()))?
+            .map(|item| item.with_context(|| format!("invalid variable in {}", path.display())))
+            .collect()
+    }
+
     pub fn load_dotenv_files(root: &Path) -> Result<()> {
         let mut variables = HashMap::<String, String>::new();
 
@@ -1,15 +1,77 @@
+use anyhow::anyhow;
 use blaze_common::error::Result;
-use std::{hash::Hasher, io::Read, path::Path};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    hash::Hasher,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    time::SystemTime,
+};
 use xxhash_rust::xxh3::Xxh3;
 
+/// Checksums of files, along with the size and modification time they were computed for.
+type FileHashIndex = HashMap<PathBuf, (u64, SystemTime, u64)>;
+
+/// Checksums of files which were already computed, when enabled with [`enable_file_hash_index`].
+static FILE_HASH_INDEX: Lazy<Mutex<Option<FileHashIndex>>> = Lazy::new(Mutex::default);
+
+fn lock_file_hash_index() -> Result<MutexGuard<'static, Option<FileHashIndex>>> {
+    FILE_HASH_INDEX
+        .lock()
+        .map_err(|_| anyhow!("poison error (Mutex on file hash index)."))
+}
+
 /// Get a hasher instance. Not for crypto !
 pub fn hasher() -> impl Hasher + Clone {
     Xxh3::new()
 }
 
+/// Keep checksums of files in memory, so that files are only hashed again when they change.
+/// Since file modifications cannot always be detected with metadata, the index must be invalidated by watching the file system (see [`invalidate_file_hashes`]).
+pub fn enable_file_hash_index() -> Result<()> {
+    let _ = lock_file_hash_index()?.get_or_insert_with(Default::default);
+    Ok(())
+}
+
+/// Forget checksums of the provided files, and of all the files they contain if they are directories.
+pub fn invalidate_file_hashes<'a, I: IntoIterator<Item = &'a Path>>(paths: I) -> Result<()> {
+    if let Some(index) = lock_file_hash_index()?.as_mut() {
+        for path in paths {
+            index.retain(|indexed, _| !indexed.starts_with(path));
+        }
+    }
+    Ok(())
+}
+
 /// Get a checksum for a file.
 /// Not for crypto !
 pub fn hash_file(path: &Path) -> Result<u64> {
+    if lock_file_hash_index()?.is_none() {
+        return compute_file_hash(path);
+    }
+
+    let metadata = std::fs::metadata(path)?;
+    let (len, modified) = (metadata.len(), metadata.modified()?);
+
+    if let Some((indexed_len, indexed_modified, hash)) = lock_file_hash_index()?
+        .as_ref()
+        .and_then(|index| index.get(path))
+    {
+        if (*indexed_len, *indexed_modified) == (len, modified) {
+            return Ok(*hash);
+        }
+    }
+
+    let hash = compute_file_hash(path)?;
+    if let Some(index) = lock_file_hash_index()?.as_mut() {
+        index.insert(path.to_owned(), (len, modified, hash));
+    }
+    Ok(hash)
+}
+
+fn compute_file_hash(path: &Path) -> Result<u64> {
     let mut buffer = [0_u8; 8192];
     let mut file = std::fs::File::open(path)?;
     let mut hasher = hasher();
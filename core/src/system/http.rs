@@ -0,0 +1,208 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{ErrorKind, Read, Write},
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::{HttpAuthentication, HttpDigestAuthentication},
+};
+use md5::Md5;
+use reqwest::{
+    blocking::{RequestBuilder, Response},
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    Method, StatusCode,
+};
+use sha2::{digest::Output, Digest, Sha256};
+use url::Url;
+
+use super::random::random_string;
+
+const DIGEST_NONCE_COUNT: &str = "00000001";
+const DOWNLOAD_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Send a request with optional HTTP authentication.
+/// Digest authentication requires a challenge from the server, so the request is sent a second time when the server answers with one.
+pub fn send_authenticated(
+    request: RequestBuilder,
+    authentication: Option<&HttpAuthentication>,
+) -> Result<Response> {
+    let digest = match authentication {
+        None => return Ok(request.send()?),
+        Some(HttpAuthentication::Basic(basic)) => {
+            return Ok(request
+                .basic_auth(basic.username(), Some(basic.password()))
+                .send()?)
+        }
+        Some(HttpAuthentication::Bearer(bearer)) => {
+            return Ok(request.bearer_auth(bearer.token()).send()?)
+        }
+        Some(HttpAuthentication::Digest(digest)) => digest,
+    };
+
+    let retry = request.try_clone();
+    let response = request.send()?;
+
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+
+    let Some(challenge) = response
+        .headers()
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(parse_digest_challenge)
+    else {
+        return Ok(response);
+    };
+
+    let not_cloneable = || anyhow!("request cannot be sent again for digest authentication");
+    let retry = retry.ok_or_else(not_cloneable)?;
+    let sent = retry.try_clone().ok_or_else(not_cloneable)?.build()?;
+
+    let authorization = digest_authorization(digest, sent.method(), sent.url(), &challenge)?;
+
+    Ok(retry.header(AUTHORIZATION, authorization).send()?)
+}
+
+/// Stream a response body to a new file at *path*, without holding it in memory.
+/// The hash of the body is computed with *D* while it is written.
+pub fn download_to_file<D: Digest>(mut response: Response, path: &Path) -> Result<Output<D>> {
+    let mut file =
+        File::create(path).with_context(|| format!("could not create {}", path.display()))?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0_u8; DOWNLOAD_BUFFER_SIZE];
+
+    loop {
+        let read = match response.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read])?;
+    }
+
+    file.flush()?;
+    Ok(hasher.finalize())
+}
+
+/// Parse the parameters of a `Digest` challenge, returning `None` for any other authentication scheme.
+fn parse_digest_challenge(header: &str) -> Option<HashMap<String, String>> {
+    let (scheme, params) = header.trim().split_once(char::is_whitespace)?;
+
+    if !scheme.eq_ignore_ascii_case("digest") {
+        return None;
+    }
+
+    let mut parsed = HashMap::new();
+    let mut chars = params.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+        let name = std::iter::from_fn(|| chars.next_if(|c| *c != '='))
+            .collect::<String>()
+            .trim()
+            .to_ascii_lowercase();
+
+        if name.is_empty() || chars.next().is_none() {
+            break;
+        }
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                match c {
+                    c if escaped => {
+                        value.push(c);
+                        escaped = false;
+                    }
+                    '\\' => escaped = true,
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+            value
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| *c != ','))
+                .collect::<String>()
+                .trim()
+                .to_owned()
+        };
+
+        parsed.insert(name, value);
+    }
+
+    Some(parsed)
+}
+
+/// Compute the `Authorization` header value answering a digest challenge (RFC 7616).
+fn digest_authorization(
+    digest: &HttpDigestAuthentication,
+    method: &Method,
+    url: &Url,
+    challenge: &HashMap<String, String>,
+) -> Result<String> {
+    let param = |name: &str| challenge.get(name).map(String::as_str);
+
+    let realm = param("realm").unwrap_or_default();
+    let nonce = param("nonce").ok_or_else(|| anyhow!("digest challenge has no nonce"))?;
+    let algorithm = param("algorithm").unwrap_or("MD5");
+
+    let hash: fn(&str) -> String = match algorithm.to_ascii_uppercase().as_str() {
+        "MD5" | "MD5-SESS" => |data| format!("{:x}", Md5::digest(data.as_bytes())),
+        "SHA-256" | "SHA-256-SESS" => |data| format!("{:x}", Sha256::digest(data.as_bytes())),
+        _ => bail!("unsupported digest algorithm {algorithm}"),
+    };
+
+    let qop = match param("qop") {
+        Some(qop) if qop.split(',').any(|qop| qop.trim() == "auth") => Some("auth"),
+        Some(qop) => bail!("unsupported digest quality of protection \"{qop}\""),
+        None => None,
+    };
+
+    let cnonce = random_string(16);
+    let uri = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_owned(),
+    };
+
+    let mut ha1 = hash(&format!(
+        "{}:{realm}:{}",
+        digest.username(),
+        digest.password()
+    ));
+    if algorithm.to_ascii_uppercase().ends_with("-SESS") {
+        ha1 = hash(&format!("{ha1}:{nonce}:{cnonce}"));
+    }
+    let ha2 = hash(&format!("{method}:{uri}"));
+
+    let response = match qop {
+        Some(qop) => hash(&format!(
+            "{ha1}:{nonce}:{DIGEST_NONCE_COUNT}:{cnonce}:{qop}:{ha2}"
+        )),
+        None => hash(&format!("{ha1}:{nonce}:{ha2}")),
+    };
+
+    let mut authorization = format!(
+        "Digest username=\"{}\", realm=\"{realm}\", nonce=\"{nonce}\", uri=\"{uri}\", algorithm={algorithm}, response=\"{response}\"",
+        digest.username()
+    );
+    if let Some(qop) = qop {
+        authorization.push_str(&format!(
+            ", qop={qop}, nc={DIGEST_NONCE_COUNT}, cnonce=\"{cnonce}\""
+        ));
+    }
+    if let Some(opaque) = param("opaque") {
+        authorization.push_str(&format!(", opaque=\"{opaque}\""));
+    }
+
+    Ok(authorization)
+}
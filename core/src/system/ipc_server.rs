@@ -13,8 +13,9 @@ use super::thread::{join, thread};
 use anyhow::Context;
 use blaze_common::error::{Error, Result};
 use interprocess::local_socket::{
-    traits::Listener, GenericFilePath, Listener as LocalSocketListener, ListenerNonblockingMode,
-    ListenerOptions, Stream, ToFsName,
+    traits::{Listener, Stream as _},
+    GenericFilePath, Listener as LocalSocketListener, ListenerNonblockingMode, ListenerOptions,
+    RecvHalf, SendHalf, Stream, ToFsName,
 };
 
 pub struct IpcServer<'scope> {
@@ -145,6 +146,20 @@ pub struct IpcClientConnection {
     stream: Stream,
 }
 
+impl IpcClientConnection {
+    /// Connect to an IPC server listening at the provided path.
+    pub fn connect(path: &Path) -> Result<Self> {
+        let stream = Stream::connect(ToFsName::to_fs_name::<GenericFilePath>(path)?)
+            .with_context(|| format!("could not connect to IPC server at {}", path.display()))?;
+        Ok(Self { stream })
+    }
+
+    /// Split the connection in order to receive and send data from different threads.
+    pub fn split(self) -> (RecvHalf, SendHalf) {
+        self.stream.split()
+    }
+}
+
 impl std::io::Read for IpcClientConnection {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.stream.read(buf)
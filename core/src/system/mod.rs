@@ -1,13 +1,18 @@
+pub mod archive;
 pub mod env;
 pub mod file_changes;
 pub mod glob;
 pub mod hash;
+pub mod http;
 pub mod ipc_server;
 pub mod locks;
 pub mod parallel_executor;
 pub mod process;
 pub mod random;
 pub mod repository;
+pub mod sandbox;
 pub mod shell;
+#[cfg(unix)]
+pub(crate) mod signals;
 pub mod thread;
 pub mod time;
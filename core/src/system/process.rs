@@ -1,16 +1,18 @@
 use std::{
+    cell::RefCell,
     collections::HashMap,
     fmt::Display,
     io::{Read, Write},
     path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
-    sync::{RwLock, RwLockWriteGuard},
+    sync::{Arc, Mutex, MutexGuard, RwLock, RwLockWriteGuard},
     thread::JoinHandle,
 };
 
 use anyhow::{anyhow, Context};
 
-use blaze_common::error::Result;
+use blaze_common::error::{Error, Result};
+use serde::{Deserialize, Serialize};
 use shared_child::SharedChild;
 
 use super::thread::{join, thread};
@@ -18,9 +20,257 @@ use super::thread::{join, thread};
 // both read operations for Stdout and Stderr are done in separate threads.
 type ReadThreadHandles = [JoinHandle<Result<()>>; 2];
 
+thread_local! {
+    static PROCESS_CONTEXT: RefCell<ProcessContext> = RefCell::new(ProcessContext::default());
+}
+
+/// Process related state of a thread, which is inherited by threads spawned with the `thread!` macro.
+#[derive(Clone, Default)]
+pub(crate) struct ProcessContext {
+    capture: Option<OutputCapture>,
+    trackers: Vec<ProcessTracker>,
+    redirection: Option<OutputRedirection>,
+}
+
+impl ProcessContext {
+    pub(crate) fn current() -> Self {
+        PROCESS_CONTEXT.with(|context| context.borrow().clone())
+    }
+
+    /// Same context, without capturing output of spawned processes.
+    pub(crate) fn without_capture(self) -> Self {
+        Self {
+            capture: None,
+            ..self
+        }
+    }
+
+    /// Same context, without capturing output nor tracking spawned processes.
+    /// Output of spawned processes is still redirected, if it was already.
+    pub(crate) fn detached(self) -> Self {
+        Self {
+            redirection: self.redirection,
+            ..Self::default()
+        }
+    }
+
+    /// Run the function *f* with this context as the current thread context.
+    pub(crate) fn enter<T, F: FnOnce() -> T>(self, f: F) -> T {
+        let previous = PROCESS_CONTEXT.with(|context| context.replace(self));
+        let result = f();
+        PROCESS_CONTEXT.with(|context| *context.borrow_mut() = previous);
+        result
+    }
+}
+
+/// Collects the displayed output (stdout and stderr) of all processes spawned from a thread.
+#[derive(Clone, Default)]
+pub struct OutputCapture(Arc<Mutex<Vec<u8>>>);
+
+impl OutputCapture {
+    /// Run the function *f* while capturing output of processes spawned from the current thread.
+    pub fn capturing<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        ProcessContext {
+            capture: Some(self.clone()),
+            ..ProcessContext::current()
+        }
+        .enter(f)
+    }
+
+    /// Get everything that was captured so far.
+    pub fn contents(&self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.lock()?).into_owned())
+    }
+
+    fn append(&self, data: &[u8]) -> Result<()> {
+        self.lock()?.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<Vec<u8>>> {
+        self.0
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on captured output)."))
+    }
+}
+
+/// Standard stream on which some output is displayed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+type RedirectionHandler = dyn Fn(OutputStream, &[u8]) + Send + Sync;
+
+/// Sends everything displayed from a thread (logs and output of spawned processes) to a handler, instead of the standard streams of the Blaze process.
+#[derive(Clone)]
+pub struct OutputRedirection(Arc<RedirectionHandler>);
+
+impl OutputRedirection {
+    pub fn new<F: Fn(OutputStream, &[u8]) + Send + Sync + 'static>(handler: F) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    /// Run the function *f* while redirecting output displayed from the current thread.
+    pub fn redirecting<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        ProcessContext {
+            redirection: Some(self.clone()),
+            ..ProcessContext::current()
+        }
+        .enter(f)
+    }
+}
+
+/// Run the function *f* while displaying everything that would be displayed on the standard output on the standard error instead,
+/// so that the standard output of the Blaze process is left for machine-readable results.
+pub(crate) fn display_stdout_on_stderr<T, F: FnOnce() -> T>(f: F) -> T {
+    let outer = ProcessContext::current().redirection;
+    OutputRedirection::new(move |_, data| match &outer {
+        Some(redirection) => (redirection.0)(OutputStream::Stderr, data),
+        None => {
+            let _ = write_to_stream(OutputStream::Stderr, data);
+        }
+    })
+    .redirecting(f)
+}
+
+/// Display some output on a standard stream, unless output of the current thread is redirected.
+pub(crate) fn display(stream: OutputStream, data: &[u8]) -> std::io::Result<()> {
+    if let Some(redirection) = ProcessContext::current().redirection {
+        (redirection.0)(stream, data);
+        return Ok(());
+    }
+
+    write_to_stream(stream, data)
+}
+
+fn write_to_stream(stream: OutputStream, data: &[u8]) -> std::io::Result<()> {
+    // when using a console on Windows, ChildStdout and ChildStderr do not support non-UTF8 streams
+    #[cfg(windows)]
+    let data = String::from_utf8_lossy(data);
+    #[cfg(windows)]
+    let data = data.as_bytes();
+
+    match stream {
+        OutputStream::Stdout => std::io::stdout().write_all(data),
+        OutputStream::Stderr => std::io::stderr().write_all(data),
+    }
+}
+
+/// Display and capture some output which is not produced by a spawned process, as if it was.
+pub(crate) fn forward_output(stream: OutputStream, data: &[u8]) -> Result<()> {
+    if let Some(capture) = ProcessContext::current().capture {
+        capture.append(data)?;
+    }
+    display(stream, data)?;
+    Ok(())
+}
+
+/// A writer for displayed output, see [`display`].
+pub(crate) struct DisplayWriter(pub OutputStream);
+
+impl Write for DisplayWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        display(self.0, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.0 {
+            OutputStream::Stdout => std::io::stdout().flush(),
+            OutputStream::Stderr => std::io::stderr().flush(),
+        }
+    }
+}
+
+/// Keeps track of all processes spawned from a thread, so that they can be terminated together.
+/// Trackers can be nested, a process is tracked by every tracker of the thread that spawned it.
+#[derive(Clone, Default)]
+pub struct ProcessTracker {
+    processes: Arc<Mutex<TrackedProcesses>>,
+    process_groups: bool,
+}
+
+#[derive(Default)]
+struct TrackedProcesses {
+    children: HashMap<u32, TrackedChild>,
+    killed: bool,
+}
+
+impl ProcessTracker {
+    /// Create a tracker whose processes are started in their own process group on Unix systems, so that their own child processes are terminated as well.
+    pub fn with_process_groups() -> Self {
+        Self {
+            process_groups: true,
+            ..Default::default()
+        }
+    }
+
+    /// Run the function *f* while tracking processes spawned from the current thread.
+    pub fn tracking<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let mut context = ProcessContext::current();
+        context.trackers.push(self.clone());
+        context.enter(f)
+    }
+
+    /// Force termination of all tracked processes that are still running.
+    /// Processes that are spawned afterwards are terminated as soon as they are tracked.
+    pub fn kill_all(&self) -> Result<()> {
+        let mut processes = self.lock()?;
+        processes.killed = true;
+        for (_, child) in processes.children.drain() {
+            child.kill()?;
+        }
+        Ok(())
+    }
+
+    fn track(&self, child: &TrackedChild) -> Result<()> {
+        let mut processes = self.lock()?;
+        if processes.killed {
+            return child.kill();
+        }
+        processes.children.insert(child.child.id(), child.clone());
+        Ok(())
+    }
+
+    fn untrack(&self, pid: u32) -> Result<()> {
+        let _ = self.lock()?.children.remove(&pid);
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<TrackedProcesses>> {
+        self.processes
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on tracked processes)."))
+    }
+}
+
+#[derive(Clone)]
+struct TrackedChild {
+    child: Arc<SharedChild>,
+    /// Whether the process is the leader of its own process group.
+    group_leader: bool,
+}
+
+impl TrackedChild {
+    fn kill(&self) -> Result<()> {
+        #[cfg(unix)]
+        if self.group_leader {
+            // the group might not exist anymore, in which case there is nothing to do.
+            let _ = unsafe { libc::killpg(self.child.id() as libc::pid_t, libc::SIGKILL) };
+        }
+
+        self.child
+            .kill()
+            .with_context(|| format!("could not kill process {}", self.child.id()))
+    }
+}
+
 /// A child process
 pub struct Process {
-    child: SharedChild,
+    child: TrackedChild,
+    trackers: Vec<ProcessTracker>,
     read_thread_handles: RwLock<Option<ReadThreadHandles>>,
 }
 
@@ -39,9 +289,34 @@ impl From<ExitStatus> for ProcessStatus {
     }
 }
 
+/// Error for a process that did not terminate successfully.
+/// It can be found in an error chain in order to get the process status code.
+#[derive(Debug)]
+pub struct ProcessFailure {
+    pub code: Option<i32>,
+}
+
+impl ProcessFailure {
+    /// Find the first process failure in the chain of an error.
+    pub fn find(error: &Error) -> Option<&Self> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+}
+
+impl Display for ProcessFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "process failed with status code {code}"),
+            None => f.write_str("process exited without any status code"),
+        }
+    }
+}
+
+impl std::error::Error for ProcessFailure {}
+
 impl Display for Process {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "process {}", self.child.id())
+        write!(f, "process {}", self.pid())
     }
 }
 
@@ -51,9 +326,12 @@ pub struct ProcessOptions {
     /// The current working directory.
     pub cwd: Option<PathBuf>,
     /// Should we display output (stderr and stdout).
+    /// Output is captured when the process is spawned from a capturing thread, whether it is displayed or not.
     pub display_output: bool,
     /// Environment variables for the process.
     pub environment: HashMap<String, String>,
+    /// Should the process start with an empty environment, instead of inheriting the environment of the current process.
+    pub clear_environment: bool,
 }
 
 impl Process {
@@ -67,6 +345,22 @@ impl Process {
         P: AsRef<Path>,
         S: AsRef<str>,
         I: IntoIterator<Item = S>,
+    {
+        Self::run_with_command_hook(program, arguments, options, |_| {})
+    }
+
+    /// Same as [`Process::run_with_options`], but the underlying command can be customized before the process is spawned.
+    pub fn run_with_command_hook<P, I, S, H>(
+        program: P,
+        arguments: I,
+        options: ProcessOptions,
+        hook: H,
+    ) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>,
+        H: FnOnce(&mut Command),
     {
         let mut builder = Command::new(program.as_ref());
 
@@ -78,6 +372,10 @@ impl Process {
             builder.arg(arg.as_ref());
         }
 
+        if options.clear_environment {
+            builder.env_clear();
+        }
+
         for (name, val) in options.environment {
             builder.env(name, val);
         }
@@ -86,18 +384,54 @@ impl Process {
         builder.stdout(Stdio::piped());
         builder.stderr(Stdio::piped());
 
-        let child = SharedChild::spawn(&mut builder)?;
+        hook(&mut builder);
+
+        let context = ProcessContext::current();
+        let group_leader = cfg!(unix)
+            && context
+                .trackers
+                .iter()
+                .any(|tracker| tracker.process_groups);
+
+        #[cfg(unix)]
+        if group_leader {
+            use std::os::unix::process::CommandExt;
+            builder.process_group(0);
+        }
+
+        let child = TrackedChild {
+            child: Arc::new(SharedChild::spawn(&mut builder)?),
+            group_leader,
+        };
+
+        // process groups do not receive interruptions from the terminal, so they are forwarded.
+        #[cfg(unix)]
+        if group_leader {
+            if let Err(err) = super::signals::register_process_group(child.child.id()) {
+                let _ = child.child.kill();
+                let _ = child.child.wait();
+                return Err(err);
+            }
+        }
+
+        for tracker in &context.trackers {
+            tracker.track(&child)?;
+        }
 
         let process = Process {
             child,
+            trackers: context.trackers,
             read_thread_handles: RwLock::new(None),
         };
 
-        if options.display_output {
-            fn pipe<F, T>(mut src: F, mut dst: T) -> JoinHandle<Result<()>>
+        if options.display_output || context.capture.is_some() {
+            fn pipe<F>(
+                mut src: F,
+                dst: Option<OutputStream>,
+                capture: Option<OutputCapture>,
+            ) -> JoinHandle<Result<()>>
             where
                 F: Read + Send + 'static,
-                T: Write + Send + 'static,
             {
                 thread!(move || {
                     let mut buffer = [0_u8; 512];
@@ -107,37 +441,45 @@ impl Process {
                             break;
                         }
 
-                        #[cfg(not(windows))]
-                        dst.write_all(&buffer[..read])?;
+                        if let Some(capture) = &capture {
+                            capture.append(&buffer[..read])?;
+                        }
 
-                        // when using a console on Windows, ChildStdout and ChildStderr do not support non-UTF8 streams
-                        #[cfg(windows)]
-                        dst.write_all(String::from_utf8_lossy(&buffer[..read]).as_bytes())?;
+                        if let Some(dst) = dst {
+                            display(dst, &buffer[..read])?;
+                        }
                     }
 
                     Ok(())
                 })
             }
 
+            let capture = context.capture;
+            let display_output = options.display_output;
+
             *process
                 .read_thread_handles
                 .write()
                 .map_err(|_| anyhow!("poison error for process read thread."))? = Some([
                 pipe(
                     process
+                        .child
                         .child
                         .take_stdout()
                         .take()
                         .ok_or_else(|| anyhow!("could not take stdout for {process}."))?,
-                    std::io::stdout(),
+                    display_output.then_some(OutputStream::Stdout),
+                    capture.clone(),
                 ),
                 pipe(
                     process
+                        .child
                         .child
                         .take_stderr()
                         .take()
                         .ok_or_else(|| anyhow!("could not take stderr for {process}."))?,
-                    std::io::stderr(),
+                    display_output.then_some(OutputStream::Stderr),
+                    capture,
                 ),
             ])
         }
@@ -148,6 +490,7 @@ impl Process {
     /// Write some data to standard input and close.
     pub fn stdin_write(&mut self, data: &[u8]) -> Result<()> {
         self.child
+            .child
             .take_stdin()
             .ok_or_else(|| anyhow!("stdin already closed"))?
             .write_all(data)
@@ -157,6 +500,7 @@ impl Process {
     /// Get stdout from child process
     pub fn stdout(&self) -> Result<impl Read> {
         self.child
+            .child
             .take_stdout()
             .ok_or_else(|| anyhow!("could not get process {self} stdout"))
     }
@@ -165,6 +509,7 @@ impl Process {
     /// This does not take ownership or a mutable reference.
     pub fn wait(&self) -> Result<ProcessStatus> {
         let status = self
+            .child
             .child
             .wait()
             .map(ProcessStatus::from)
@@ -176,18 +521,31 @@ impl Process {
             }
         }
 
+        // processes started by this process might still be running until output is closed.
+        self.untrack()?;
+
         Ok(status)
     }
 
-    /// Force termination of the process.
+    /// Force termination of the process (and its process group, if it has its own).
     pub fn kill(&self) -> Result<()> {
-        self.child.kill()?;
-        Ok(())
+        self.child.kill()
     }
 
     /// Get the process id.
     pub fn pid(&self) -> u32 {
-        self.child.id()
+        self.child.child.id()
+    }
+
+    fn untrack(&self) -> Result<()> {
+        #[cfg(unix)]
+        if self.child.group_leader {
+            super::signals::unregister_process_group(self.pid());
+        }
+        for tracker in &self.trackers {
+            tracker.untrack(self.pid())?;
+        }
+        Ok(())
     }
 
     fn thread_handle_write(&self) -> Result<RwLockWriteGuard<Option<ReadThreadHandles>>> {
@@ -196,3 +554,9 @@ impl Process {
             .map_err(|_| anyhow!("poison error (RwLock on process read thread)."))
     }
 }
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.untrack();
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use blaze_common::error::Result;
+use std::collections::HashSet;
 use std::io::{Seek, Write};
+use std::path::PathBuf;
 use std::{fs::OpenOptions, path::Path};
 
 const GITIGNORE_FILE: &str = ".gitignore";
@@ -36,3 +38,59 @@ where
     writeln!(file, "{to_write}")
         .with_context(|| format!("could not write rules to {}", path.display()))
 }
+
+/// List files that changed since the merge base of `base` and `head`.
+/// When `head` is not provided, changes are compared with the working directory (including untracked files).
+/// Returned paths are absolute.
+pub fn get_changed_files(root: &Path, base: &str, head: Option<&str>) -> Result<HashSet<PathBuf>> {
+    let repository = git2::Repository::discover(root)
+        .with_context(|| format!("could not find a git repository at {}", root.display()))?;
+    let workdir = dunce::canonicalize(
+        repository
+            .workdir()
+            .ok_or_else(|| anyhow!("git repository at {} is bare", root.display()))?,
+    )?;
+
+    let find_commit = |revision: &str| {
+        repository
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("could not resolve git revision {revision}"))
+    };
+
+    let base_commit = find_commit(base)?;
+    let head_commit = match head {
+        Some(revision) => find_commit(revision)?,
+        None => find_commit("HEAD")?,
+    };
+
+    let merge_base = repository
+        .merge_base(base_commit.id(), head_commit.id())
+        .with_context(|| {
+            format!(
+                "could not find a merge base between {base} and {}",
+                head.unwrap_or("HEAD")
+            )
+        })?;
+    let base_tree = repository.find_commit(merge_base)?.tree()?;
+
+    let mut options = git2::DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+
+    let diff = match head {
+        Some(_) => repository.diff_tree_to_tree(
+            Some(&base_tree),
+            Some(&head_commit.tree()?),
+            Some(&mut options),
+        ),
+        None => repository.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut options)),
+    }
+    .context("could not compute git diff")?;
+
+    Ok(diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .map(|path| workdir.join(path))
+        .collect())
+}
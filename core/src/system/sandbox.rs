@@ -0,0 +1,344 @@
+use std::{path::PathBuf, process::Command};
+
+use blaze_common::error::Result;
+
+/// Restrictions for a process and all of its children.
+#[derive(Debug, Default)]
+pub struct SandboxRules {
+    /// Paths (files or directories) where the process is allowed to write. Everything else is read-only.
+    pub writable: Vec<PathBuf>,
+    /// Whether the process is allowed to open network sockets.
+    pub network: bool,
+}
+
+/// Restrictions which are ready to be applied to a process before it starts.
+/// File system restrictions are enforced with Landlock, and network restrictions with a seccomp filter.
+pub struct Sandbox {
+    #[cfg(target_os = "linux")]
+    inner: linux::LinuxSandbox,
+}
+
+impl Sandbox {
+    /// Prepare the restrictions. This must be done in the parent process, since only async-signal-safe operations can be done before the process starts.
+    pub fn new(rules: &SandboxRules) -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        return Ok(Self {
+            inner: linux::LinuxSandbox::new(rules)?,
+        });
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = rules;
+            Ok(Self {})
+        }
+    }
+
+    /// Whether file system restrictions can be enforced on this system.
+    pub fn restricts_file_system(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        return self.inner.restricts_file_system();
+
+        #[cfg(not(target_os = "linux"))]
+        false
+    }
+
+    /// Whether network restrictions can be enforced on this system.
+    pub fn restricts_network(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        return self.inner.restricts_network();
+
+        #[cfg(not(target_os = "linux"))]
+        false
+    }
+
+    /// Apply the restrictions to the process started by the provided command.
+    /// The sandbox must be kept alive until the process is started.
+    pub fn apply(&self, command: &mut Command) {
+        #[cfg(target_os = "linux")]
+        self.inner.apply(command);
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = command;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        fs::OpenOptions,
+        io,
+        os::{
+            fd::{AsRawFd, FromRawFd, OwnedFd},
+            unix::{fs::OpenOptionsExt, process::CommandExt},
+        },
+        path::Path,
+        process::Command,
+    };
+
+    use anyhow::Context;
+    use blaze_common::error::Result;
+
+    use super::SandboxRules;
+
+    const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1;
+    const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+    const ACCESS_FS_EXECUTE: u64 = 1 << 0;
+    const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+    const ACCESS_FS_READ_FILE: u64 = 1 << 2;
+    const ACCESS_FS_READ_DIR: u64 = 1 << 3;
+    /// All access rights supported by the first Landlock ABI.
+    const ACCESS_FS_ABI_1: u64 = (1 << 13) - 1;
+    const ACCESS_FS_REFER: u64 = 1 << 13;
+    const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+    /// Access rights which can be granted on regular files (other rights only apply to directories).
+    const ACCESS_FS_FILE: u64 =
+        ACCESS_FS_EXECUTE | ACCESS_FS_WRITE_FILE | ACCESS_FS_READ_FILE | ACCESS_FS_TRUNCATE;
+    const ACCESS_FS_READ_ONLY: u64 = ACCESS_FS_EXECUTE | ACCESS_FS_READ_FILE | ACCESS_FS_READ_DIR;
+
+    #[repr(C)]
+    struct LandlockRulesetAttr {
+        handled_access_fs: u64,
+    }
+
+    #[repr(C, packed)]
+    struct LandlockPathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: i32,
+    }
+
+    const SECCOMP_MODE_FILTER: libc::c_ulong = 2;
+
+    pub struct LinuxSandbox {
+        ruleset: Option<OwnedFd>,
+        filter: Option<Vec<libc::sock_filter>>,
+    }
+
+    impl LinuxSandbox {
+        pub fn new(rules: &SandboxRules) -> Result<Self> {
+            Ok(Self {
+                ruleset: create_ruleset(rules)?,
+                filter: (!rules.network).then(network_filter).flatten(),
+            })
+        }
+
+        pub fn restricts_file_system(&self) -> bool {
+            self.ruleset.is_some()
+        }
+
+        pub fn restricts_network(&self) -> bool {
+            self.filter.is_some()
+        }
+
+        pub fn apply(&self, command: &mut Command) {
+            let ruleset = self.ruleset.as_ref().map(AsRawFd::as_raw_fd);
+            let filter = self.filter.clone();
+
+            // only async-signal-safe system calls are made between fork and exec.
+            unsafe {
+                command.pre_exec(move || {
+                    if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+
+                    if let Some(ruleset) = ruleset {
+                        if libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0) != 0 {
+                            return Err(io::Error::last_os_error());
+                        }
+                    }
+
+                    if let Some(filter) = &filter {
+                        let program = libc::sock_fprog {
+                            len: filter.len() as libc::c_ushort,
+                            filter: filter.as_ptr() as *mut libc::sock_filter,
+                        };
+                        if libc::prctl(
+                            libc::PR_SET_SECCOMP,
+                            SECCOMP_MODE_FILTER,
+                            &program as *const libc::sock_fprog,
+                        ) != 0
+                        {
+                            return Err(io::Error::last_os_error());
+                        }
+                    }
+
+                    Ok(())
+                });
+            }
+        }
+    }
+
+    /// Create a Landlock ruleset which allows reading everywhere, and writing only in the provided paths.
+    /// [`None`] is returned if Landlock is not supported by the kernel.
+    fn create_ruleset(rules: &SandboxRules) -> Result<Option<OwnedFd>> {
+        let abi = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                std::ptr::null::<LandlockRulesetAttr>(),
+                0,
+                LANDLOCK_CREATE_RULESET_VERSION,
+            )
+        };
+
+        if abi < 1 {
+            return Ok(None);
+        }
+
+        let mut handled_access = ACCESS_FS_ABI_1;
+        if abi >= 2 {
+            handled_access |= ACCESS_FS_REFER;
+        }
+        if abi >= 3 {
+            handled_access |= ACCESS_FS_TRUNCATE;
+        }
+
+        let attr = LandlockRulesetAttr {
+            handled_access_fs: handled_access,
+        };
+
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                &attr as *const LandlockRulesetAttr,
+                std::mem::size_of::<LandlockRulesetAttr>(),
+                0,
+            )
+        };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error()).context("could not create landlock ruleset");
+        }
+
+        let ruleset = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+
+        add_rule(
+            &ruleset,
+            Path::new("/"),
+            ACCESS_FS_READ_ONLY & handled_access,
+        )?;
+
+        // standard devices such as /dev/null must remain writable.
+        add_rule(
+            &ruleset,
+            Path::new("/dev"),
+            (ACCESS_FS_READ_FILE | ACCESS_FS_WRITE_FILE) & handled_access,
+        )?;
+
+        add_rule(&ruleset, &std::env::temp_dir(), handled_access)?;
+
+        for path in &rules.writable {
+            add_rule(&ruleset, path, handled_access)?;
+        }
+
+        Ok(Some(ruleset))
+    }
+
+    fn add_rule(ruleset: &OwnedFd, path: &Path, access: u64) -> Result<()> {
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_PATH | libc::O_CLOEXEC)
+            .open(path)
+            .with_context(|| format!("could not open {} for sandboxing", path.display()))?;
+
+        let access = if file.metadata()?.is_dir() {
+            access
+        } else {
+            access & ACCESS_FS_FILE
+        };
+
+        let attr = LandlockPathBeneathAttr {
+            allowed_access: access,
+            parent_fd: file.as_raw_fd(),
+        };
+
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_add_rule,
+                ruleset.as_raw_fd(),
+                LANDLOCK_RULE_PATH_BENEATH,
+                &attr as *const LandlockPathBeneathAttr,
+                0,
+            )
+        };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error())
+                .with_context(|| format!("could not add landlock rule for {}", path.display()));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    use seccomp::network_filter;
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    mod seccomp {
+        const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+        const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+
+        const BPF_LD_W_ABS: u16 = 0x20;
+        const BPF_JMP_JEQ_K: u16 = 0x15;
+        const BPF_JMP_JGE_K: u16 = 0x35;
+        const BPF_RET_K: u16 = 0x06;
+
+        /// Offsets of the `seccomp_data` fields.
+        const SECCOMP_DATA_NR: u32 = 0;
+        const SECCOMP_DATA_ARCH: u32 = 4;
+        const SECCOMP_DATA_FIRST_ARG: u32 = 16;
+
+        #[cfg(target_arch = "x86_64")]
+        const AUDIT_ARCH: u32 = 0xc000_003e;
+        #[cfg(target_arch = "aarch64")]
+        const AUDIT_ARCH: u32 = 0xc000_00b7;
+
+        /// System calls of the x32 ABI, which must be denied since they bypass syscall numbers checks.
+        #[cfg(target_arch = "x86_64")]
+        const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+        const fn statement(code: u16, k: u32) -> libc::sock_filter {
+            jump(code, k, 0, 0)
+        }
+
+        const fn jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+            libc::sock_filter { code, jt, jf, k }
+        }
+
+        /// Seccomp filter which denies creation of internet sockets. Local sockets are still allowed, since they are used for communicating with Blaze.
+        pub fn network_filter() -> Option<Vec<libc::sock_filter>> {
+            let denied = SECCOMP_RET_ERRNO | libc::EACCES as u32;
+
+            let mut filter = vec![
+                statement(BPF_LD_W_ABS, SECCOMP_DATA_ARCH),
+                jump(BPF_JMP_JEQ_K, AUDIT_ARCH, 1, 0),
+                statement(BPF_RET_K, denied),
+                statement(BPF_LD_W_ABS, SECCOMP_DATA_NR),
+            ];
+
+            #[cfg(target_arch = "x86_64")]
+            filter.extend([
+                jump(BPF_JMP_JGE_K, X32_SYSCALL_BIT, 0, 1),
+                statement(BPF_RET_K, denied),
+            ]);
+
+            filter.extend([
+                jump(BPF_JMP_JEQ_K, libc::SYS_socket as u32, 1, 0),
+                statement(BPF_RET_K, SECCOMP_RET_ALLOW),
+                statement(BPF_LD_W_ABS, SECCOMP_DATA_FIRST_ARG),
+                jump(BPF_JMP_JEQ_K, libc::AF_INET as u32, 2, 0),
+                jump(BPF_JMP_JEQ_K, libc::AF_INET6 as u32, 1, 0),
+                statement(BPF_RET_K, SECCOMP_RET_ALLOW),
+                statement(BPF_RET_K, denied),
+            ]);
+
+            Some(filter)
+        }
+    }
+
+    /// Network restrictions are not supported on this architecture.
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    fn network_filter() -> Option<Vec<libc::sock_filter>> {
+        None
+    }
+}
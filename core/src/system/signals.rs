@@ -0,0 +1,122 @@
+use std::{
+    ffi::c_void,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Once,
+    },
+};
+
+use anyhow::bail;
+use blaze_common::error::Result;
+use once_cell::sync::OnceCell;
+
+/// Maximum number of process groups which can receive forwarded interruptions at the same time.
+const MAX_PROCESS_GROUPS: usize = 1024;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_PROCESS_GROUP: AtomicI32 = AtomicI32::new(0);
+
+/// Process groups created by Blaze, which do not receive signals sent by the terminal to the Blaze process group.
+/// A fixed array of atomics is used, since it must be read from a signal handler.
+static PROCESS_GROUPS: [AtomicI32; MAX_PROCESS_GROUPS] = [NO_PROCESS_GROUP; MAX_PROCESS_GROUPS];
+
+static HANDLER: Once = Once::new();
+
+/// Handler which was installed before ours (by the application embedding Blaze for example), it is called after interruptions are forwarded.
+static PREVIOUS_HANDLER: OnceCell<libc::sigaction> = OnceCell::new();
+
+/// Forward interruptions (`SIGINT`) received by the Blaze process to a process group, until it is unregistered.
+pub(crate) fn register_process_group(pgid: u32) -> Result<()> {
+    HANDLER.call_once(install_handler);
+    for slot in &PROCESS_GROUPS {
+        if slot
+            .compare_exchange(0, pgid as i32, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+    bail!("too many process groups are running at the same time (maximum is {MAX_PROCESS_GROUPS}).")
+}
+
+pub(crate) fn unregister_process_group(pgid: u32) {
+    for slot in &PROCESS_GROUPS {
+        let _ = slot.compare_exchange(pgid as i32, 0, Ordering::SeqCst, Ordering::SeqCst);
+    }
+}
+
+fn install_handler() {
+    // SAFETY: a zeroed sigaction is a valid value (an empty mask with no flags), and it is only used as an output parameter here.
+    let mut previous = unsafe { std::mem::zeroed::<libc::sigaction>() };
+
+    // SAFETY: a null action only queries the current disposition of SIGINT into `previous`, which is a valid pointer.
+    if unsafe { libc::sigaction(libc::SIGINT, std::ptr::null(), &mut previous) } != 0 {
+        return;
+    }
+
+    // interruptions must stay ignored if they were (for background jobs for example).
+    if previous.sa_sigaction == libc::SIG_IGN {
+        return;
+    }
+
+    // SAFETY: same as above, every field is then initialized explicitly.
+    let mut action = unsafe { std::mem::zeroed::<libc::sigaction>() };
+    action.sa_sigaction = forward_interruption
+        as extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void)
+        as libc::sighandler_t;
+    action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+
+    // SAFETY: `action.sa_mask` is a valid pointer to a signal set owned by this function.
+    unsafe { libc::sigemptyset(&mut action.sa_mask) };
+
+    // the previous handler is stored before ours is installed, so that it is always visible from the signal handler.
+    let _ = PREVIOUS_HANDLER.set(previous);
+
+    // SAFETY: `action` is a valid pointer, and `forward_interruption` only does async-signal-safe operations.
+    unsafe { libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut()) };
+}
+
+/// Only async-signal-safe operations are done here.
+extern "C" fn forward_interruption(
+    signal: libc::c_int,
+    info: *mut libc::siginfo_t,
+    context: *mut c_void,
+) {
+    for slot in &PROCESS_GROUPS {
+        let pgid = slot.load(Ordering::SeqCst);
+        if pgid != 0 {
+            // SAFETY: killpg is async-signal-safe, and failures (for a group which already exited) are ignored.
+            unsafe { libc::killpg(pgid, signal) };
+        }
+    }
+
+    match PREVIOUS_HANDLER.get() {
+        Some(previous) if previous.sa_sigaction != libc::SIG_DFL => {
+            // SAFETY: the previous disposition is neither SIG_DFL nor SIG_IGN (see `install_handler`), so it is a handler function,
+            // whose signature is given by the SA_SIGINFO flag.
+            unsafe {
+                if previous.sa_flags & libc::SA_SIGINFO != 0 {
+                    let handler = std::mem::transmute::<
+                        libc::sighandler_t,
+                        extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void),
+                    >(previous.sa_sigaction);
+                    handler(signal, info, context);
+                } else {
+                    let handler = std::mem::transmute::<
+                        libc::sighandler_t,
+                        extern "C" fn(libc::c_int),
+                    >(previous.sa_sigaction);
+                    handler(signal);
+                }
+            }
+        }
+        // then terminate as if the signal was not handled.
+        _ => {
+            // SAFETY: signal and raise are async-signal-safe, restoring the default disposition is always valid.
+            unsafe {
+                libc::signal(signal, libc::SIG_DFL);
+                libc::raise(signal);
+            }
+        }
+    }
+}
@@ -1,14 +1,19 @@
 #[cfg(feature = "testing")]
 use std::{cell::Cell, sync::atomic::AtomicUsize};
 
+// spawned threads inherit the process context (output capture, process tracking) of their parent thread.
 #[cfg(not(feature = "testing"))]
 macro_rules! thread {
-    ($f:expr) => {
-        std::thread::spawn($f)
-    };
-    ($s:expr,$f:expr) => {
-        $s.spawn($f)
-    };
+    ($f:expr) => {{
+        use crate::system::process::ProcessContext;
+        let process_context = ProcessContext::current();
+        std::thread::spawn(move || process_context.enter($f))
+    }};
+    ($s:expr,$f:expr) => {{
+        use crate::system::process::ProcessContext;
+        let process_context = ProcessContext::current();
+        $s.spawn(move || process_context.enter($f))
+    }};
 }
 
 #[cfg(feature = "testing")]
@@ -25,19 +30,21 @@ thread_local! {
 #[cfg(feature = "testing")]
 macro_rules! thread {
     ($f:expr) => {{
-        use crate::system::thread::SCOPE_ID;
+        use crate::system::{process::ProcessContext, thread::SCOPE_ID};
         let current_scope_id = SCOPE_ID.with(|id| id.get());
+        let process_context = ProcessContext::current();
         std::thread::spawn(move || {
             SCOPE_ID.set(current_scope_id);
-            $f()
+            process_context.enter($f)
         })
     }};
     ($s:expr, $f:expr) => {{
-        use crate::system::thread::SCOPE_ID;
+        use crate::system::{process::ProcessContext, thread::SCOPE_ID};
         let current_scope_id = SCOPE_ID.with(|id| id.get());
+        let process_context = ProcessContext::current();
         $s.spawn(move || {
             SCOPE_ID.set(current_scope_id);
-            $f()
+            process_context.enter($f)
         })
     }};
 }
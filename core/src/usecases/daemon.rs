@@ -0,0 +1,434 @@
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::scope,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, logger::Logger};
+use interprocess::local_socket::SendHalf;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use possibly::possibly;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    executors::{lock::LockMode, ResolvedExecutors},
+    global_init,
+    logging::get_logger,
+    system::{
+        env::{MAIN_ENV_FILE, USER_ENV_FILE},
+        hash::{enable_file_hash_index, invalidate_file_hashes},
+        ipc_server::{IpcClientConnection, IpcServer},
+        process::{display, OutputRedirection, OutputStream},
+    },
+    workspace::{
+        configurations::{is_configuration_file, track_configuration_files},
+        template::HELPERS_FOLDER,
+        variables::VARIABLES_FILE_PATH,
+    },
+    WorkspaceGlobals,
+};
+
+use super::{
+    global::{find_workspace_root, JPATH_FILE},
+    run::run_with_globals,
+    GlobalOptions, RunOptions,
+};
+
+/// File where a running daemon advertises its socket, relative to the workspace root.
+const DAEMON_FILE: &str = ".blaze/daemon.json";
+
+const BLAZE_FOLDER: &str = ".blaze";
+
+/// How often the daemon checks whether it was asked to stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Serialize, Deserialize)]
+struct DaemonInfo {
+    pid: u32,
+    socket: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+enum DaemonRequest {
+    Run(RunOptions),
+    Stop,
+}
+
+#[derive(Serialize, Deserialize)]
+enum DaemonMessage {
+    /// Output displayed while running targets.
+    Output {
+        stream: OutputStream,
+        data: String,
+    },
+    /// Root targets which failed, or the chain of error messages if the run could not complete.
+    RunResult(std::result::Result<Vec<String>, Vec<String>>),
+    Stopped,
+}
+
+/// Result of a run performed by a daemon.
+#[derive(Debug)]
+pub struct DaemonRunResult {
+    root_failures: Vec<String>,
+}
+
+impl DaemonRunResult {
+    /// Execution doubles of the selected targets which failed or could not be executed.
+    pub fn root_failures(&self) -> &[String] {
+        &self.root_failures
+    }
+}
+
+/// Workspace state which is kept between runs.
+struct WarmState {
+    globals: WorkspaceGlobals<'static>,
+    executors: ResolvedExecutors,
+}
+
+/// Changes that happened in the workspace since the last run.
+#[derive(Default)]
+struct Invalidations {
+    /// Workspace configuration, project configurations (or the files they import), variables, helpers or dotenv files changed.
+    configuration: AtomicBool,
+    /// Any other file which changed, which may be part of a custom executor.
+    files: Mutex<BTreeSet<PathBuf>>,
+}
+
+struct Daemon<'a> {
+    root: &'a Path,
+    options: &'a GlobalOptions,
+    logger: &'a Logger,
+    state: Mutex<Option<WarmState>>,
+    invalidations: Arc<Invalidations>,
+    stopped: AtomicBool,
+}
+
+/// Start a daemon for the workspace, and serve runs requested with [`run_with_daemon`] until [`stop_daemon`] is called.
+/// The workspace configuration, its projects, input file checksums and resolved executors are kept in memory between runs,
+/// and invalidated when files change in the workspace.
+pub fn daemon<R: AsRef<Path>>(root: R, global_options: GlobalOptions) -> Result<()> {
+    let root = find_workspace_root(root.as_ref())?;
+    let logger = get_logger(global_options.get_log_level().unwrap_or_default());
+
+    if connect(&root)?.is_some() {
+        bail!("a daemon is already running for {}", root.display());
+    }
+
+    enable_file_hash_index()?;
+
+    let invalidations = Arc::new(Invalidations::default());
+    let _watcher = watch_workspace(&root, invalidations.clone())?;
+
+    let daemon = Daemon {
+        root: &root,
+        options: &global_options,
+        logger: &logger,
+        state: Mutex::default(),
+        invalidations,
+        stopped: AtomicBool::new(false),
+    };
+
+    let daemon_file = root.join(DAEMON_FILE);
+
+    scope(|scope| {
+        let server = IpcServer::create(
+            scope,
+            |connection| daemon.serve(connection),
+            |err| logger.error(format!("daemon client error: {err:?}")),
+        )
+        .context("could not create daemon server")?;
+
+        if let Err(err) = write_daemon_file(&daemon_file, server.get_path()) {
+            let _ = server.close();
+            return Err(err);
+        }
+
+        logger.info(format!(
+            "daemon is listening for {} on {server}",
+            root.display()
+        ));
+
+        while !daemon.stopped.load(Ordering::SeqCst) {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+
+        let _ = std::fs::remove_file(&daemon_file);
+        server.close()
+    })?;
+
+    logger.info("daemon stopped");
+
+    Ok(())
+}
+
+/// Run a target using the daemon of the workspace.
+/// Returns [`None`] if no daemon is running, or if global options were provided (since the daemon uses its own).
+/// Output is displayed just like when running locally, but targets are executed with the environment of the daemon,
+/// and the run is not canceled when the calling process is interrupted.
+pub fn run_with_daemon<R: AsRef<Path>>(
+    root: R,
+    options: &RunOptions,
+    global_options: &GlobalOptions,
+) -> Result<Option<DaemonRunResult>> {
+    if !global_options.is_default() {
+        return Ok(None);
+    }
+
+    let root = find_workspace_root(root.as_ref())?;
+
+    let connection = match connect(&root)? {
+        Some(connection) => connection,
+        None => return Ok(None),
+    };
+
+    let options = options
+        .clone()
+        .with_absolute_paths(&std::env::current_dir()?);
+
+    let (receiver, mut sender) = connection.split();
+    send(&mut sender, &DaemonRequest::Run(options))?;
+
+    for line in BufReader::new(receiver).lines() {
+        match parse_message(&line.context("could not read daemon message")?)? {
+            DaemonMessage::Output { stream, data } => display(stream, data.as_bytes())?,
+            DaemonMessage::RunResult(Ok(root_failures)) => {
+                return Ok(Some(DaemonRunResult { root_failures }))
+            }
+            DaemonMessage::RunResult(Err(messages)) => return Err(into_error(messages)),
+            DaemonMessage::Stopped => break,
+        }
+    }
+
+    bail!("daemon connection was closed before the end of the run")
+}
+
+/// Stop the daemon of the workspace. Returns false if no daemon is running.
+pub fn stop_daemon<R: AsRef<Path>>(root: R) -> Result<bool> {
+    let root = find_workspace_root(root.as_ref())?;
+
+    let connection = match connect(&root)? {
+        Some(connection) => connection,
+        None => return Ok(false),
+    };
+
+    let (receiver, mut sender) = connection.split();
+    send(&mut sender, &DaemonRequest::Stop)?;
+
+    for line in BufReader::new(receiver).lines() {
+        if let DaemonMessage::Stopped =
+            parse_message(&line.context("could not read daemon message")?)?
+        {
+            return Ok(true);
+        }
+    }
+
+    bail!("daemon connection was closed before it stopped")
+}
+
+impl Daemon<'_> {
+    fn serve(&self, connection: IpcClientConnection) -> Result<()> {
+        let (receiver, sender) = connection.split();
+        let sender = Arc::new(Mutex::new(sender));
+
+        let mut line = String::new();
+        BufReader::new(receiver)
+            .read_line(&mut line)
+            .context("could not read daemon request")?;
+
+        let request = serde_json::from_str::<DaemonRequest>(&line)
+            .with_context(|| format!("invalid daemon request: {line}"))?;
+
+        let message = match request {
+            DaemonRequest::Run(options) => {
+                let redirected_sender = sender.clone();
+                let redirection = OutputRedirection::new(move |stream, data| {
+                    if let Ok(mut sender) = redirected_sender.lock() {
+                        let _ = send(
+                            &mut sender,
+                            &DaemonMessage::Output {
+                                stream,
+                                data: String::from_utf8_lossy(data).into_owned(),
+                            },
+                        );
+                    }
+                });
+                DaemonMessage::RunResult(
+                    redirection
+                        .redirecting(|| self.run(options))
+                        .map_err(|err| err.chain().map(ToString::to_string).collect()),
+                )
+            }
+            DaemonRequest::Stop => {
+                self.stopped.store(true, Ordering::SeqCst);
+                DaemonMessage::Stopped
+            }
+        };
+
+        send(
+            &mut *sender
+                .lock()
+                .map_err(|_| anyhow!("poison error (Mutex on daemon client sender)."))?,
+            &message,
+        )
+    }
+
+    /// Run a target with the warm state, and get the root targets which failed.
+    /// Runs are serialized, since executions must not overlap in the same workspace.
+    fn run(&self, options: RunOptions) -> Result<Vec<String>> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on daemon state)."))?;
+
+        if self
+            .invalidations
+            .configuration
+            .swap(false, Ordering::SeqCst)
+        {
+            self.logger.debug("workspace configuration changed");
+            *state = None;
+        }
+
+        let changed_files = std::mem::take(
+            &mut *self
+                .invalidations
+                .files
+                .lock()
+                .map_err(|_| anyhow!("poison error (Mutex on daemon changed files)."))?,
+        );
+
+        let warm = match state.take() {
+            Some(warm) => warm,
+            None => {
+                track_configuration_files()?;
+                WarmState {
+                    globals: WorkspaceGlobals::new(self.root, self.options.clone())?,
+                    executors: ResolvedExecutors::default(),
+                }
+            }
+        };
+        let warm = state.insert(warm);
+
+        // the lockfile must be checked in locked mode
+        if matches!(options.get_lock_mode(), LockMode::Frozen) {
+            warm.executors.retain_standard();
+        } else {
+            warm.executors
+                .invalidate_sources(changed_files.iter().map(PathBuf::as_path));
+        }
+
+        global_init(&warm.globals)?;
+
+        let run_result = run_with_globals(&options, &warm.globals, &mut warm.executors)?;
+
+        Ok(run_result
+            .root_executions()
+            .values()
+            .filter_map(|execution_result| possibly!(&execution_result.result, Some(Err(_))|None => execution_result.execution.get_double()))
+            .collect())
+    }
+}
+
+/// Connect to the daemon of the workspace, if there is one running.
+fn connect(root: &Path) -> Result<Option<IpcClientConnection>> {
+    let daemon_file = root.join(DAEMON_FILE);
+
+    let info = match std::fs::read_to_string(&daemon_file) {
+        Ok(content) => serde_json::from_str::<DaemonInfo>(&content)
+            .with_context(|| format!("invalid daemon file {}", daemon_file.display()))?,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    // the daemon file is left behind if the daemon was killed
+    Ok(IpcClientConnection::connect(&info.socket).ok())
+}
+
+fn write_daemon_file(path: &Path, socket: &Path) -> Result<()> {
+    let info = DaemonInfo {
+        pid: std::process::id(),
+        socket: socket.to_owned(),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(&info)?)
+        .with_context(|| format!("could not write {}", path.display()))
+}
+
+fn send<T: Serialize>(sender: &mut SendHalf, message: &T) -> Result<()> {
+    writeln!(sender, "{}", serde_json::to_string(message)?).context("could not send daemon message")
+}
+
+fn parse_message(line: &str) -> Result<DaemonMessage> {
+    serde_json::from_str(line).with_context(|| format!("invalid daemon message: {line}"))
+}
+
+/// Rebuild an error from its chain of messages, starting with the outermost one.
+fn into_error(messages: Vec<String>) -> anyhow::Error {
+    let mut messages = messages.into_iter().rev();
+    let root_cause = anyhow!(messages.next().unwrap_or_default());
+    messages.fold(root_cause, |err, message| err.context(message))
+}
+
+/// Watch the workspace in order to invalidate the warm state when files change.
+fn watch_workspace(root: &Path, invalidations: Arc<Invalidations>) -> Result<RecommendedWatcher> {
+    let watched_root = root.to_owned();
+
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<Event>| {
+            // the whole state is dropped when changes cannot be known precisely.
+            let invalidate_all = || invalidations.configuration.store(true, Ordering::SeqCst);
+
+            let paths = match event {
+                Ok(event) => event.paths,
+                Err(_) => return invalidate_all(),
+            };
+
+            if invalidate_file_hashes(paths.iter().map(PathBuf::as_path)).is_err() {
+                return invalidate_all();
+            }
+
+            let Ok(mut changed_files) = invalidations.files.lock() else {
+                return invalidate_all();
+            };
+
+            for path in &paths {
+                match path.strip_prefix(&watched_root) {
+                    Ok(relative) if is_workspace_file(relative) => invalidate_all(),
+                    _ if is_configuration_file(path).unwrap_or(true) => invalidate_all(),
+                    Ok(relative) if !relative.starts_with(BLAZE_FOLDER) => {
+                        changed_files.insert(path.to_owned());
+                    }
+                    _ => {}
+                }
+            }
+        },
+        notify::Config::default(),
+    )
+    .context("could not create file watcher")?;
+
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .with_context(|| format!("could not watch {}", root.display()))?;
+
+    Ok(watcher)
+}
+
+/// Whether a file (relative to the workspace root) is read when loading the workspace, besides configuration files.
+/// Configuration files and their imports are tracked while they are evaluated (see [`is_configuration_file`]).
+fn is_workspace_file(relative: &Path) -> bool {
+    relative.starts_with(HELPERS_FOLDER)
+        || relative == Path::new(JPATH_FILE)
+        || relative.with_extension("") == Path::new(VARIABLES_FILE_PATH)
+        || [MAIN_ENV_FILE, USER_ENV_FILE]
+            .into_iter()
+            .any(|file| relative == Path::new(file))
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashSet, path::Path};
+
+use anyhow::Context;
+use blaze_common::{error::Result, executor::ExecutorReference, target::Target};
+
+use crate::{
+    executors::{
+        lock::{ExecutorLock, LockMode, LOCKFILE_NAME},
+        resolve_executors, CustomResolutionContext, ResolvedExecutors,
+    },
+    global_init,
+    workspace::project_handle::{ProjectHandle, ProjectOptions},
+    GlobalOptions, WorkspaceGlobals,
+};
+
+/// Resolve executors, using and updating the workspace lockfile according to *mode*.
+pub(super) fn resolve_locked_executors(
+    references: &HashSet<ExecutorReference>,
+    mode: LockMode,
+    globals: &WorkspaceGlobals<'_>,
+) -> Result<ResolvedExecutors> {
+    let logger = globals.logger();
+    let workspace = globals.workspace_handle().inner();
+    let lock = ExecutorLock::load(workspace.root(), mode)?;
+
+    let executors = resolve_executors(
+        references,
+        CustomResolutionContext {
+            cache: globals.local_cache(),
+            workspace,
+            logger: &logger,
+            lock: &lock,
+        },
+    )
+    .context("error while resolving executors")?;
+
+    lock.save()?;
+
+    Ok(executors)
+}
+
+/// Resolve all executors referenced in the workspace, ignoring locked versions, and rewrite the lockfile.
+pub fn update_executors(root: &Path, global_options: GlobalOptions) -> Result<()> {
+    let globals = WorkspaceGlobals::new(root, global_options)?;
+    global_init(&globals)?;
+
+    let workspace = globals.workspace_handle().inner();
+    let logger = globals.logger();
+
+    let mut references = HashSet::new();
+
+    for (name, project_ref) in workspace.projects() {
+        let project = ProjectHandle::from_root(
+            workspace.root().join(project_ref.path()),
+            ProjectOptions {
+                name,
+                deserialization_context: globals.deserialization_context(),
+            },
+        )
+        .with_context(|| format!("error while loading project {name}."))?
+        .unwrap_inner();
+
+        references.extend(
+            project
+                .targets()
+                .values()
+                .filter_map(Target::executor)
+                .cloned(),
+        );
+    }
+
+    logger.info(format!(
+        "{} executor reference(s) will be updated ({:?})",
+        references.len(),
+        references
+            .iter()
+            .map(|reference| reference.to_string())
+            .collect::<Vec<_>>()
+    ));
+
+    resolve_locked_executors(&references, LockMode::Update, &globals)?;
+
+    logger.info(format!("{LOCKFILE_NAME} was updated"));
+
+    Ok(())
+}
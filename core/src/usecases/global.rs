@@ -18,15 +18,16 @@ use crate::{
     workspace::{
         cache_store::CacheStore,
         configurations::DeserializationContext,
+        project_handle::LoadedProjects,
         template::TemplateData,
         variables::{load_variables_from_root, LoadVariablesOptions},
         workspace_handle::{OpenWorkspaceOptions, WorkspaceHandle},
     },
 };
 
-const JPATH_FILE: &str = ".blaze/.jpath";
+pub(super) const JPATH_FILE: &str = ".blaze/.jpath";
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct GlobalOptions {
     log_level: Option<LogLevel>,
     no_cache: bool,
@@ -67,6 +68,11 @@ impl GlobalOptions {
     pub fn get_variable_overrides(&self) -> &[VariablesOverride] {
         &self.variable_overrides
     }
+
+    /// Whether no option was set, so that the workspace is loaded as it is.
+    pub(super) fn is_default(&self) -> bool {
+        self.log_level.is_none() && !self.no_cache && self.variable_overrides.is_empty()
+    }
 }
 
 pub struct WorkspaceGlobals<'a> {
@@ -74,8 +80,10 @@ pub struct WorkspaceGlobals<'a> {
     logger: Logger,
     log_level: LogLevel,
     cache: Option<CacheStore>,
+    local_cache: Option<CacheStore>,
     template_data: TemplateData<'a>,
     jpath: HashSet<PathBuf>,
+    loaded_projects: LoadedProjects,
 }
 
 impl<'a> WorkspaceGlobals<'a> {
@@ -95,27 +103,28 @@ impl<'a> WorkspaceGlobals<'a> {
         self.cache.as_ref()
     }
 
+    /// Executors resolution state and evaluated project configurations refer to files in the workspace, so they are always cached locally.
+    pub fn local_cache(&self) -> Option<&CacheStore> {
+        self.local_cache.as_ref()
+    }
+
+    /// Projects which were loaded from this workspace so far.
+    pub fn loaded_projects(&self) -> &LoadedProjects {
+        &self.loaded_projects
+    }
+
     pub fn deserialization_context(&'a self) -> DeserializationContext<'a> {
         DeserializationContext {
             jpath: &self.jpath,
             template_data: &self.template_data,
+            cache: self.local_cache.as_ref(),
         }
     }
 }
 
 impl WorkspaceGlobals<'_> {
     pub fn new(root: &Path, options: GlobalOptions) -> Result<Self> {
-        let mut root = dunce::canonicalize(root)
-            .with_context(|| format!("could not canonicalize root directory {}", root.display()))?;
-        let base_root = root.clone();
-        loop {
-            if WorkspaceHandle::exists_at_root(&root)? {
-                break;
-            }
-            if !root.pop() {
-                bail!("{} is not part of a Blaze workspace.", base_root.display())
-            }
-        }
+        let root = find_workspace_root(root)?;
 
         let jpath = get_jpath(&root).context("error while reading jpath file")?;
 
@@ -166,21 +175,48 @@ impl WorkspaceGlobals<'_> {
         template_data.extend_with_workspace(workspace_handle.inner())?;
 
         let cache = (!options.no_cache)
-            .then(|| CacheStore::load(&root))
+            .then(|| {
+                CacheStore::load_with_backend(
+                    &root,
+                    workspace_handle.inner().settings().cache_backend(),
+                )
+            })
             .transpose()
             .context("error while loading workspace cache")?;
 
+        let local_cache = (!options.no_cache)
+            .then(|| CacheStore::load(&root))
+            .transpose()
+            .context("error while loading local cache")?;
+
         Ok(Self {
             cache,
+            local_cache,
             workspace_handle,
             log_level,
             template_data,
             logger,
             jpath,
+            loaded_projects: LoadedProjects::default(),
         })
     }
 }
 
+/// Find the root of the workspace which contains the provided directory.
+pub(super) fn find_workspace_root(path: &Path) -> Result<PathBuf> {
+    let mut root = dunce::canonicalize(path)
+        .with_context(|| format!("could not canonicalize root directory {}", path.display()))?;
+    let base_root = root.clone();
+    loop {
+        if WorkspaceHandle::exists_at_root(&root)? {
+            return Ok(root);
+        }
+        if !root.pop() {
+            bail!("{} is not part of a Blaze workspace.", base_root.display())
+        }
+    }
+}
+
 fn get_jpath(root: &Path) -> Result<HashSet<PathBuf>> {
     Ok(match File::open(root.join(JPATH_FILE)) {
         Ok(path) => {
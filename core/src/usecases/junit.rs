@@ -0,0 +1,167 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::Context;
+use blaze_common::error::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::executions::graph::{ExecutedGraph, ExecutedNode};
+
+use super::ExecutionDetails;
+
+static ANSI_ESCAPE_SEQUENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
+
+#[derive(Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+impl Counts {
+    fn add(&mut self, node: &ExecutedNode<ExecutionDetails>) {
+        self.tests += 1;
+        match &node.result {
+            Some(Ok(ExecutionDetails::Executed { execution_time, .. })) => {
+                self.time += *execution_time
+            }
+            Some(Ok(_)) => {}
+            Some(Err(_)) => self.failures += 1,
+            None => self.skipped += 1,
+        }
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\"",
+            self.tests,
+            self.failures,
+            self.skipped,
+            self.time.as_secs_f64()
+        )
+    }
+}
+
+/// Write a JUnit XML report to a file, with one test suite per project and one test case per executed target.
+/// Captured outputs are indexed by execution double.
+pub(super) fn write_junit_report_file(
+    path: &Path,
+    graph: &ExecutedGraph<ExecutionDetails>,
+    outputs: &HashMap<String, String>,
+) -> Result<()> {
+    let mut file = std::fs::File::create(path)
+        .with_context(|| format!("could not create JUnit report file {}", path.display()))?;
+    write_junit_report(&mut file, graph, outputs)
+        .with_context(|| format!("could not write JUnit report to {}", path.display()))
+}
+
+fn write_junit_report<O: Write>(
+    output: &mut O,
+    graph: &ExecutedGraph<ExecutionDetails>,
+    outputs: &HashMap<String, String>,
+) -> Result<()> {
+    let mut suites = BTreeMap::<&str, Vec<(&String, &ExecutedNode<ExecutionDetails>)>>::new();
+    let mut total = Counts::default();
+
+    for (double, node) in graph.execution() {
+        suites
+            .entry(node.execution.get_project().name())
+            .or_default()
+            .push((double, node));
+        total.add(node);
+    }
+
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        output,
+        r#"<testsuites name="blaze" {}>"#,
+        total.attributes()
+    )?;
+
+    for (project, nodes) in suites {
+        let mut counts = Counts::default();
+        for (_, node) in &nodes {
+            counts.add(node);
+        }
+
+        writeln!(
+            output,
+            r#"  <testsuite name="{}" {}>"#,
+            escape(project),
+            counts.attributes()
+        )?;
+
+        for (double, node) in nodes {
+            let time = match &node.result {
+                Some(Ok(ExecutionDetails::Executed { execution_time, .. })) => *execution_time,
+                _ => Duration::ZERO,
+            };
+
+            writeln!(
+                output,
+                r#"    <testcase name="{}" classname="{}" time="{:.3}">"#,
+                escape(node.execution.get_target_name()),
+                escape(project),
+                time.as_secs_f64()
+            )?;
+
+            match &node.result {
+                Some(Err(err)) => writeln!(
+                    output,
+                    r#"      <failure message="{}">{}</failure>"#,
+                    escape(&err.to_string()),
+                    escape(
+                        &err.chain()
+                            .map(|cause| cause.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    )
+                )?,
+                None => writeln!(output, r#"      <skipped message="not executed"/>"#)?,
+                Some(Ok(_)) => {}
+            }
+
+            if let Some(captured) = outputs.get(double).filter(|captured| !captured.is_empty()) {
+                writeln!(
+                    output,
+                    "      <system-out>{}</system-out>",
+                    escape(captured)
+                )?;
+            }
+
+            writeln!(output, "    </testcase>")?;
+        }
+
+        writeln!(output, "  </testsuite>")?;
+    }
+
+    writeln!(output, "</testsuites>")?;
+
+    Ok(())
+}
+
+/// Escape text for XML attributes and contents, and remove terminal escape sequences and characters that XML does not allow.
+fn escape(text: &str) -> String {
+    let text = ANSI_ESCAPE_SEQUENCE.replace_all(text, "");
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
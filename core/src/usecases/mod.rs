@@ -1,15 +1,24 @@
+mod daemon;
 mod describe;
+mod executors;
 mod global;
 mod init;
+mod junit;
 mod render;
+mod report;
 mod rm_cache;
 mod run;
 mod spawn;
+mod watch;
 
+pub use daemon::*;
 pub use describe::*;
+pub use executors::*;
 pub use global::*;
 pub use init::*;
 pub use render::*;
+pub use report::*;
 pub use rm_cache::*;
 pub use run::*;
 pub use spawn::*;
+pub use watch::*;
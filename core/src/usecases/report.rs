@@ -0,0 +1,173 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    path::Path,
+};
+
+use anyhow::Context;
+use blaze_common::{error::Result, unit_enum_from_str};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+use crate::executions::{
+    graph::{ExecutedGraph, ExecutedNode},
+    hermetic::HermeticViolations,
+};
+
+use super::ExecutionDetails;
+
+/// How results are printed on the standard output after a run.
+#[derive(EnumIter, Display, Default, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum RunOutputFormat {
+    /// Colored tree of executions (only when the execution graph is displayed).
+    #[default]
+    Tree,
+    /// A [`RunReport`] serialized as JSON.
+    Json,
+}
+
+unit_enum_from_str!(RunOutputFormat);
+
+/// Final status of a single target execution.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TargetStatus {
+    Executed,
+    Cached,
+    Noop,
+    Failed,
+    Ignored,
+}
+
+/// Report for a single target execution.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetReport {
+    pub project: String,
+    pub target: String,
+    pub root: bool,
+    pub dependencies: BTreeSet<String>,
+    pub status: TargetStatus,
+    /// Execution time in milliseconds, only for executed targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Number of attempts, only for executed targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<usize>,
+    /// Error messages from the outermost to the root cause, only for failed targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<String>>,
+    /// Files accessed outside of declared inputs and outputs, only for executed targets when the hermeticity check is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hermetic_violations: Option<HermeticViolations>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct RunStats {
+    pub executed: usize,
+    pub cached: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
+
+/// Serializable summary of a run, indexed by execution double.
+#[derive(Serialize, Debug)]
+pub struct RunReport {
+    pub targets: BTreeMap<String, TargetReport>,
+    pub stats: RunStats,
+}
+
+impl RunStats {
+    pub fn new(graph: &ExecutedGraph<ExecutionDetails>) -> Self {
+        let mut stats: RunStats = Default::default();
+
+        for result in graph.execution().values() {
+            *match result {
+                ExecutedNode {
+                    result: Some(Ok(ExecutionDetails::Cached { .. })),
+                    ..
+                } => &mut stats.cached,
+                ExecutedNode {
+                    result: Some(Ok(ExecutionDetails::Executed { .. } | ExecutionDetails::Noop)),
+                    ..
+                } => &mut stats.executed,
+                ExecutedNode {
+                    result: Some(Err(_)),
+                    ..
+                } => &mut stats.failed,
+                ExecutedNode { result: None, .. } => &mut stats.pending,
+            } += 1;
+        }
+
+        stats
+    }
+}
+
+impl RunReport {
+    pub fn new(graph: &ExecutedGraph<ExecutionDetails>) -> Self {
+        Self {
+            targets: graph
+                .execution()
+                .iter()
+                .map(|(double, node)| (double.to_owned(), TargetReport::new(node)))
+                .collect(),
+            stats: RunStats::new(graph),
+        }
+    }
+
+    /// Write the report as JSON.
+    pub fn write_json<O: Write>(&self, output: O) -> Result<()> {
+        serde_json::to_writer_pretty(output, self).context("could not serialize run report")
+    }
+
+    /// Write the report as JSON to a file, which is created or truncated.
+    pub fn write_json_file(&self, path: &Path) -> Result<()> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("could not create report file {}", path.display()))?;
+        self.write_json(file)
+    }
+}
+
+impl TargetReport {
+    fn new(node: &ExecutedNode<ExecutionDetails>) -> Self {
+        let (status, duration_ms, attempts, errors) = match &node.result {
+            Some(Ok(ExecutionDetails::Executed {
+                execution_time,
+                attempts,
+                ..
+            })) => (
+                TargetStatus::Executed,
+                Some(execution_time.as_millis().try_into().unwrap_or(u64::MAX)),
+                Some(*attempts),
+                None,
+            ),
+            Some(Ok(ExecutionDetails::Cached { .. })) => (TargetStatus::Cached, None, None, None),
+            Some(Ok(ExecutionDetails::Noop)) => (TargetStatus::Noop, None, None, None),
+            Some(Err(err)) => (
+                TargetStatus::Failed,
+                None,
+                None,
+                Some(err.chain().map(|cause| cause.to_string()).collect()),
+            ),
+            None => (TargetStatus::Ignored, None, None, None),
+        };
+
+        Self {
+            project: node.execution.get_project().name().to_owned(),
+            target: node.execution.get_target_name().to_owned(),
+            root: node.root,
+            dependencies: node.dependencies.iter().cloned().collect(),
+            status,
+            duration_ms,
+            attempts,
+            errors,
+            hermetic_violations: match &node.result {
+                Some(Ok(ExecutionDetails::Executed {
+                    hermetic_violations,
+                    ..
+                })) => hermetic_violations.clone(),
+                _ => None,
+            },
+        }
+    }
+}
@@ -4,10 +4,7 @@ use anyhow::anyhow;
 use blaze_common::{error::Result, parallelism::Parallelism};
 
 use crate::{
-    executions::{
-        check::ExecutionCacheState,
-        graph::{ExecutionGraph, ExecutionGraphOptions},
-    },
+    executions::graph::{ExecutionGraph, ExecutionGraphOptions},
     workspace::selection::Selection,
     GlobalOptions, SelectorSource, WorkspaceGlobals,
 };
@@ -55,6 +52,7 @@ pub fn rm_execution_caches(
         ExecutionGraphOptions {
             deserialization_context: globals.deserialization_context(),
             workspace: globals.workspace_handle().inner(),
+            loaded_projects: globals.loaded_projects(),
             max_depth: options.depth,
         },
     )?;
@@ -63,13 +61,8 @@ pub fn rm_execution_caches(
         .cache()
         .ok_or_else(|| anyhow!("cache unavailable"))?;
 
-    let results = graph.execute(Parallelism::None, |execution, _| {
-        let key = execution.get_cache_key();
-        Ok(cache
-            .restore::<ExecutionCacheState>(&key)?
-            .map(|_| cache.invalidate(&execution.get_cache_key()))
-            .transpose()?
-            .is_some())
+    let results = graph.execute(Parallelism::None, false, |execution, _| {
+        execution.invalidate_cache(cache)
     })?;
 
     results.fmt(&mut std::io::stdout(), |node| {
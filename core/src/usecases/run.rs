@@ -1,40 +1,82 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     hash::{Hash, Hasher},
-    path::Path,
-    sync::Arc,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+        Mutex,
+    },
+    thread::scope,
     time::{Duration, Instant},
 };
 
-use anyhow::Context;
-use blaze_common::{error::Result, logger::Logger, parallelism::Parallelism};
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::{Error, Result},
+    executor::{ExecutorReference, Location},
+    logger::{LogLevel, Logger},
+    parallelism::Parallelism,
+    target::TargetRetry,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
 use colored::{ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     executions::{
         execution::{
             CachedDependencyExecution, CachedExecutionContext, CachedExecutionState,
-            TargetExecution,
+            ExecutionOutput, TargetExecution,
         },
-        graph::{ExecutedGraph, ExecutedNode, ExecutionGraph, ExecutionGraphOptions},
+        graph::{ChildExecutionResult, ExecutedGraph, ExecutionGraph, ExecutionGraphOptions},
+        hermetic::{HermeticCheck, HermeticViolations},
     },
-    executors::{resolve_executors, CustomResolutionContext, ExecutorContext},
+    executors::{lock::LockMode, ExecutorContext, ResolvedExecutor, ResolvedExecutors, WorkerPool},
     global_init,
     logging::{colorize, get_contextual_logger},
-    system::{hash::hasher, locks::ProcessLock},
-    workspace::selection::{Selection, SelectorSource},
+    system::{
+        hash::hasher,
+        locks::ProcessLock,
+        process::{
+            display_stdout_on_stderr, DisplayWriter, OutputCapture, OutputStream, ProcessFailure,
+            ProcessTracker,
+        },
+        thread::{join, thread},
+    },
+    workspace::{
+        cache_store::CacheStore,
+        selection::{Selection, SelectorSource},
+        template::TemplateData,
+    },
     WorkspaceGlobals,
 };
 
-use super::GlobalOptions;
+use super::{
+    executors::resolve_locked_executors, junit::write_junit_report_file, GlobalOptions,
+    RunOutputFormat, RunReport,
+};
+
+/// How often a target with a timeout is checked for completion or cancellation.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct RunOptions {
     selector: Option<SelectorSource>,
     target: String,
     parallelism: Option<Parallelism>,
+    fail_fast: Option<bool>,
+    persistent_workers: Option<bool>,
+    hermetic_check: bool,
     is_dry_run: bool,
+    is_locked: bool,
     display_graph: bool,
     dependencies_depth: Option<usize>,
+    output_format: RunOutputFormat,
+    report_file: Option<PathBuf>,
+    junit_report_file: Option<PathBuf>,
 }
 
 impl RunOptions {
@@ -60,11 +102,42 @@ impl RunOptions {
         self
     }
 
+    /// Stop executing targets as soon as one of them fails.
+    pub fn failing_fast(mut self) -> Self {
+        self.fail_fast = Some(true);
+        self
+    }
+
+    /// Keep executing targets that do not depend on failed targets.
+    pub fn keeping_going(mut self) -> Self {
+        self.fail_fast = Some(false);
+        self
+    }
+
+    /// Keep bridge processes of custom executors alive during the run, in order to reuse them for several targets.
+    pub fn with_persistent_workers(mut self, enabled: bool) -> Self {
+        self.persistent_workers = Some(enabled);
+        self
+    }
+
+    /// Verify that standard executors only access files covered by the cache configuration of their target.
+    /// Violations are reported for each executed target. Targets are then executed one at a time, whatever the parallelism.
+    pub fn with_hermetic_check(mut self) -> Self {
+        self.hermetic_check = true;
+        self
+    }
+
     pub fn as_dry_run(mut self) -> Self {
         self.is_dry_run = true;
         self
     }
 
+    /// Fail if the executors lockfile is missing or not up to date, instead of updating it.
+    pub fn as_locked(mut self) -> Self {
+        self.is_locked = true;
+        self
+    }
+
     pub fn displaying_graph(mut self) -> Self {
         self.display_graph = true;
         self
@@ -74,17 +147,114 @@ impl RunOptions {
         self.dependencies_depth = Some(max);
         self
     }
+
+    pub fn with_output_format(mut self, format: RunOutputFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Write a JSON [`RunReport`] to the provided path after the run.
+    pub fn with_report_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.report_file = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Write a JUnit XML report to the provided path after the run.
+    /// Output of processes spawned by executors will be captured for this purpose.
+    pub fn with_junit_report_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.junit_report_file = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Make report file paths absolute, using *cwd* for relative paths.
+    pub(super) fn with_absolute_paths(mut self, cwd: &Path) -> Self {
+        for path in [&mut self.report_file, &mut self.junit_report_file]
+            .into_iter()
+            .flatten()
+        {
+            if path.is_relative() {
+                *path = cwd.join(&*path);
+            }
+        }
+        self
+    }
+
+    pub(super) fn captures_output(&self) -> bool {
+        self.junit_report_file.is_some()
+    }
+
+    pub(super) fn checks_hermeticity(&self) -> bool {
+        self.hermetic_check
+    }
+
+    pub(super) fn is_dry_run(&self) -> bool {
+        self.is_dry_run
+    }
+
+    pub(super) fn get_lock_mode(&self) -> LockMode {
+        if self.is_locked {
+            LockMode::Frozen
+        } else {
+            LockMode::Honor
+        }
+    }
+
+    pub(super) fn get_fail_fast(&self, workspace: &Workspace) -> bool {
+        self.fail_fast
+            .or(workspace.settings().fail_fast())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn get_persistent_workers(&self, workspace: &Workspace) -> bool {
+        self.persistent_workers
+            .or(workspace.settings().persistent_workers())
+            .unwrap_or_default()
+    }
+
+    /// Targets are always executed one at a time when the hermeticity check is enabled,
+    /// since file accesses are observed for the whole project tree and could not be attributed to a single target.
+    pub(super) fn get_parallelism(&self, workspace: &Workspace) -> Parallelism {
+        if self.hermetic_check {
+            return Parallelism::None;
+        }
+        self.parallelism
+            .or(workspace.settings().parallelism())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug)]
 pub enum ExecutionDetails {
-    Cached,
+    Cached {
+        /// Output restored from cache.
+        output: Value,
+    },
     Noop,
-    Executed { execution_time: Duration },
+    Executed {
+        execution_time: Duration,
+        /// Number of attempts, greater than 1 if the target was retried.
+        attempts: usize,
+        /// Output returned by the executor.
+        output: Value,
+        /// Files accessed outside of the target declared inputs and outputs, only when the hermeticity check is enabled.
+        hermetic_violations: Option<HermeticViolations>,
+    },
+}
+
+impl ExecutionOutput for ExecutionDetails {
+    fn output(&self) -> Option<&Value> {
+        match self {
+            Self::Cached { output } | Self::Executed { output, .. } => Some(output),
+            Self::Noop => None,
+        }
+    }
 }
 
 pub type RunResult = Result<ExecutedGraph<ExecutionDetails>>;
 
+type ChildExecutions<'a, 'b> =
+    [ChildExecutionResult<'a, 'b, CachedExecutionState<ExecutionDetails>>];
+
 /// Run a target across a selection of projects.
 pub fn run<R: AsRef<Path>>(
     root: R,
@@ -94,19 +264,128 @@ pub fn run<R: AsRef<Path>>(
     let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
     global_init(&globals)?;
 
+    run_with_globals(&options, &globals, &mut ResolvedExecutors::default())
+}
+
+/// Run a target with an already loaded workspace.
+/// Executors which are not found in *executors* are resolved and added to it.
+pub(super) fn run_with_globals<'a>(
+    options: &RunOptions,
+    globals: &'a WorkspaceGlobals<'a>,
+    executors: &mut ResolvedExecutors,
+) -> RunResult {
+    let logger = globals.logger();
+
+    // when results are printed as JSON, logs and output of executions must not be mixed with them.
+    let (execution_results, captured_outputs) = match options.output_format {
+        RunOutputFormat::Json => {
+            display_stdout_on_stderr(|| execute_run(options, globals, executors))
+        }
+        RunOutputFormat::Tree => execute_run(options, globals, executors),
+    }?;
+
+    report_results(&execution_results, &captured_outputs, options, &logger)?;
+
+    Ok(execution_results)
+}
+
+/// Execute the target graph of a run, and get the execution results along with the captured output of each execution.
+fn execute_run<'a>(
+    options: &RunOptions,
+    globals: &'a WorkspaceGlobals<'a>,
+    executors: &mut ResolvedExecutors,
+) -> Result<(ExecutedGraph<ExecutionDetails>, HashMap<String, String>)> {
     let workspace = globals.workspace_handle().inner();
     let logger = globals.logger();
-    let cache = globals.cache();
 
+    let execution_graph = build_execution_graph(options, globals)?;
+
+    if execution_graph.targets().is_empty() {
+        logger.warn("nothing to execute");
+        return Ok((ExecutedGraph::empty(), HashMap::new()));
+    }
+
+    let captured_outputs = Mutex::<HashMap<String, String>>::default();
+
+    let execution_results = if options.is_dry_run {
+        execution_graph.ignore_all()?
+    } else {
+        resolve_graph_executors(&execution_graph, options, globals, executors)?;
+        let workers = options
+            .get_persistent_workers(workspace)
+            .then(|| WorkerPool::new(logger.clone()));
+
+        execute_graph(
+            execution_graph,
+            GraphExecutionContext {
+                workspace,
+                logger: &logger,
+                log_level: globals.log_level(),
+                template_data: globals.deserialization_context().template_data,
+                cache: globals.cache(),
+                executors,
+                parallelism: options.get_parallelism(workspace),
+                fail_fast: options.get_fail_fast(workspace),
+                reused: None,
+                canceled: None,
+                captured_outputs: options.captures_output().then_some(&captured_outputs),
+                workers: workers.as_ref(),
+                hermetic_check: options.checks_hermeticity(),
+                processes: None,
+            },
+        )?
+        .map_inner(into_execution_details)
+    };
+
+    let captured_outputs = captured_outputs
+        .into_inner()
+        .map_err(|_| anyhow!("poison error (Mutex on captured outputs)."))?;
+
+    Ok((execution_results, captured_outputs))
+}
+
+/// Data needed for executing an [`ExecutionGraph`] once all executors are resolved.
+pub(super) struct GraphExecutionContext<'a> {
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
+    pub log_level: LogLevel,
+    /// Used for rendering target options which reference outputs of dependencies.
+    pub template_data: &'a TemplateData<'a>,
+    pub cache: Option<&'a CacheStore>,
+    pub executors: &'a ResolvedExecutors,
+    pub parallelism: Parallelism,
+    /// When set, running processes are killed and no more targets are executed as soon as a target fails.
+    pub fail_fast: bool,
+    /// Targets that must not be executed, along with the hash of their last cached state (if any) and their output.
+    pub reused: Option<&'a HashMap<String, (Option<u64>, Value)>>,
+    /// When set, targets that have not started yet are not executed anymore, and running executors are asked to stop.
+    /// The flag is also set when a target fails with fail-fast enabled.
+    pub canceled: Option<&'a AtomicBool>,
+    /// When set, output of processes spawned by executors is captured and stored by execution double.
+    pub captured_outputs: Option<&'a Mutex<HashMap<String, String>>>,
+    /// When set, bridge processes of custom executors are reused across target executions.
+    pub workers: Option<&'a WorkerPool>,
+    /// When set, file accesses of standard executors are verified against the cache configuration of their target.
+    pub hermetic_check: bool,
+    /// When set, processes spawned by executors are tracked with this tracker, so that they can be killed when the run is canceled.
+    pub processes: Option<&'a ProcessTracker>,
+}
+
+pub(super) fn build_execution_graph<'a>(
+    options: &RunOptions,
+    globals: &'a WorkspaceGlobals<'a>,
+) -> Result<ExecutionGraph> {
     let execution_graph = ExecutionGraph::try_new(
         &options
             .selector
+            .clone()
             .map(Selection::from_source)
             .unwrap_or_default(),
         &options.target,
         ExecutionGraphOptions {
-            workspace,
+            workspace: globals.workspace_handle().inner(),
             deserialization_context: globals.deserialization_context(),
+            loaded_projects: globals.loaded_projects(),
             max_depth: options.dependencies_depth,
         },
     )
@@ -114,201 +393,488 @@ pub fn run<R: AsRef<Path>>(
 
     let targets_to_be_executed = execution_graph.targets();
 
-    if targets_to_be_executed.is_empty() {
-        logger.warn("nothing to execute");
-        return Ok(ExecutedGraph::empty());
+    if !targets_to_be_executed.is_empty() {
+        globals.logger().info(format!(
+            "{} target(s) will be executed ({:?})",
+            targets_to_be_executed.len(),
+            targets_to_be_executed
+        ));
+    }
+
+    Ok(execution_graph)
+}
+
+/// Resolve executors referenced by the execution graph, unless they are already found in *resolved*.
+pub(super) fn resolve_graph_executors(
+    execution_graph: &ExecutionGraph,
+    options: &RunOptions,
+    globals: &WorkspaceGlobals<'_>,
+    resolved: &mut ResolvedExecutors,
+) -> Result<()> {
+    let logger = globals.logger();
+    let executor_references = resolved.unresolved(&execution_graph.get_executor_references());
+
+    if executor_references.is_empty() {
+        return Ok(());
     }
 
     logger.info(format!(
-        "{} target(s) will be executed ({:?})",
-        targets_to_be_executed.len(),
-        targets_to_be_executed
+        "{} executor reference(s) will be resolved ({:?})",
+        executor_references.len(),
+        executor_references
+            .iter()
+            .map(|reference| reference.to_string())
+            .collect::<Vec<_>>()
     ));
 
-    let parallelism = options
-        .parallelism
-        .or(workspace.settings().parallelism())
-        .unwrap_or_default();
+    resolved.extend(resolve_locked_executors(
+        &executor_references,
+        options.get_lock_mode(),
+        globals,
+    )?);
 
-    let execution_results = if options.is_dry_run {
-        execution_graph.ignore_all()?
-    } else {
-        let executor_references = execution_graph.get_executor_references();
-
-        logger.info(format!(
-            "{} executor reference(s) will be resolved ({:?})",
-            executor_references.len(),
-            executor_references
-                .iter()
-                .map(|reference| reference.to_string())
-                .collect::<Vec<_>>()
-        ));
+    Ok(())
+}
 
-        let executor_resolutions = Arc::new(
-            resolve_executors(
-                &executor_references,
-                CustomResolutionContext {
-                    cache,
-                    workspace,
-                    logger: &logger,
-                },
-            )
-            .context("error while resolving executors")?,
+pub(super) fn execute_graph(
+    execution_graph: ExecutionGraph,
+    context: GraphExecutionContext<'_>,
+) -> Result<ExecutedGraph<CachedExecutionState<ExecutionDetails>>> {
+    let GraphExecutionContext {
+        workspace,
+        logger,
+        log_level,
+        template_data,
+        cache,
+        executors,
+        parallelism,
+        fail_fast,
+        reused,
+        canceled,
+        captured_outputs,
+        workers,
+        hermetic_check,
+        processes,
+    } = context;
+
+    let processes = processes.cloned().unwrap_or_default();
+
+    // executors observe the same flag whether the run is canceled by the caller or stopped by fail-fast.
+    let run_canceled = AtomicBool::new(false);
+    let canceled = canceled.unwrap_or(&run_canceled);
+
+    let execute = |execution: &TargetExecution, child_executions: &ChildExecutions| {
+        let executor_reference = match execution.get_target().executor() {
+            Some(reference) => reference,
+            None => return Ok(ExecutionDetails::Noop),
+        };
+
+        let double = execution.get_double();
+
+        if canceled.load(Ordering::SeqCst) {
+            bail!("execution of target {double} was canceled");
+        }
+
+        let options = get_target_options(execution, child_executions, template_data)?;
+
+        let executor_resolution = executors.get_for_reference(executor_reference).unwrap();
+
+        let remote = matches!(
+            executor_reference,
+            ExecutorReference::Custom { location, .. }
+                if !matches!(location, Location::LocalFileSystem { .. })
         );
 
-        let cache_arc_0 = Arc::new(cache);
+        logger.debug(format!("executing target {double}..."));
 
-        let arc_workspace = Arc::new(workspace);
-        let logger_2 = logger.clone();
-        let logger_3 = logger.clone();
+        let executor_logger = get_contextual_logger(log_level, double.as_str());
 
-        let log_level = globals.log_level();
+        // processes of persistent workers are not started by the target execution, so their output cannot be captured.
+        let workers = workers.filter(|_| captured_outputs.is_none());
 
-        let execute = |execution: &TargetExecution| {
-            let executor_reference = match execution.get_target().executor() {
-                Some(reference) => reference,
-                None => return Ok(ExecutionDetails::Noop),
+        let start = Instant::now();
+        let run_executor = |canceled: Option<&AtomicBool>| {
+            executor_resolution.executor().execute(
+                ExecutorContext {
+                    project: &execution.get_project(),
+                    workspace,
+                    logger: &executor_logger,
+                    target: execution.get_target_name(),
+                    canceled,
+                    workers,
+                    remote,
+                },
+                options.clone(),
+            )
+        };
+
+        let run_attempt = || -> Result<Value> {
+            let result = match execution.get_target().timeout() {
+                Some(timeout) => {
+                    run_until_timeout(timeout.duration(), Some(canceled), |attempt_canceled| {
+                        run_executor(Some(attempt_canceled))
+                    })?
+                    .ok_or_else(|| anyhow!("target {double} timed out after {timeout}"))?
+                }
+                None => run_executor(Some(canceled)),
             };
+            result.with_context(|| format!("executor failed for target {double}"))
+        };
 
-            let double = execution.get_double();
-            let executor_resolution = executor_resolutions
-                .get_for_reference(executor_reference)
-                .unwrap();
-
-            logger_2.debug(format!("executing target {double}..."));
-
-            let executor_logger = get_contextual_logger(log_level, double.as_str());
-
-            let start = Instant::now();
-            Ok(ExecutionDetails::Executed {
-                execution_time: executor_resolution
-                    .executor()
-                    .execute(
-                        ExecutorContext {
-                            project: &execution.get_project(),
-                            workspace: &arc_workspace.clone(),
-                            logger: &executor_logger,
-                            target: execution.get_target_name(),
-                        },
-                        execution.get_target().options().clone(),
-                    )
-                    .with_context(|| format!("executor failed for target {double}"))
-                    .map(|_| start.elapsed())?,
-            })
+        let run_attempts = || {
+            let mut attempts = 1;
+            loop {
+                match (run_attempt(), execution.get_target().retry()) {
+                    (Err(err), Some(retry))
+                        if attempts < retry.max_attempts()
+                            && is_retryable(retry, &err)
+                            && !canceled.load(Ordering::SeqCst) =>
+                    {
+                        let delay = retry.delay_after(attempts);
+                        executor_logger.warn(format!(
+                            "attempt {attempts}/{} failed ({err:#}), retrying in {delay:?}...",
+                            retry.max_attempts()
+                        ));
+                        if wait_unless_canceled(delay, canceled) {
+                            return (Err(err), attempts);
+                        }
+                        attempts += 1;
+                        executor_logger.info(format!(
+                            "starting attempt {attempts}/{}",
+                            retry.max_attempts()
+                        ));
+                    }
+                    (result, _) => return (result, attempts),
+                }
+            }
         };
 
-        fn maybe_locked<T, F>(
-            root: &Path,
-            execution: &TargetExecution,
-            logger: Logger,
-            f: F,
-        ) -> Result<T>
-        where
-            F: FnOnce() -> T,
-        {
-            let target = execution.get_target();
-            if target.stateless() {
-                return Ok(f());
+        let hermetic_check = match executor_resolution {
+            ResolvedExecutor::Standard(_) if hermetic_check => {
+                HermeticCheck::start(execution, workspace.root())
+                    .with_context(|| format!("could not start hermeticity check for {double}"))?
             }
+            _ => None,
+        };
+
+        let (execution_result, attempts) = match captured_outputs {
+            Some(outputs) => {
+                let capture = OutputCapture::default();
+                let result = capture.capturing(run_attempts);
+                outputs
+                    .lock()
+                    .map_err(|_| anyhow!("poison error (Mutex on captured outputs)."))?
+                    .insert(double.clone(), capture.contents()?);
+                result
+            }
+            None => run_attempts(),
+        };
+
+        let execution_time = start.elapsed();
+
+        let hermetic_violations = hermetic_check
+            .map(HermeticCheck::finish)
+            .transpose()
+            .with_context(|| format!("could not check hermeticity of {double}"))?;
+
+        for path in hermetic_violations
+            .iter()
+            .flat_map(|violations| &violations.undeclared_writes)
+        {
+            logger.warn(format!(
+                "{double} wrote {} which is not a declared output",
+                path.display()
+            ));
+        }
+
+        for path in hermetic_violations
+            .iter()
+            .flat_map(|violations| &violations.undeclared_reads)
+        {
+            logger.warn(format!(
+                "{double} read {} which is not a declared input",
+                path.display()
+            ));
+        }
+
+        let output = execution_result.map_err(|err| match attempts {
+            1 => err,
+            _ => err.context(format!("target {double} failed after {attempts} attempts")),
+        })?;
+
+        Ok(ExecutionDetails::Executed {
+            execution_time,
+            attempts,
+            output,
+            hermetic_violations,
+        })
+    };
+
+    processes.tracking(|| {
+        execution_graph.execute(parallelism, fail_fast, |execution, child_executions| {
             let double = execution.get_double();
-            let mut hasher = hasher();
-            double.hash(&mut hasher);
-            let mut lock = ProcessLock::try_new(root, hasher.finish())?;
-            lock.on_wait(move || {
-                logger.warn(format!(
-                    "waiting for {double} to terminate in another process"
-                ))
+
+            if let Some((reused_hash, output)) = reused.and_then(|reused| reused.get(&double)) {
+                logger.debug(format!("target {double} is unchanged"));
+                return Ok(match reused_hash {
+                    Some(hash) => CachedExecutionState::Cached(*hash, output.clone()),
+                    None => CachedExecutionState::NoCache(ExecutionDetails::Cached {
+                        output: output.clone(),
+                    }),
+                });
+            }
+
+            maybe_locked(workspace.root(), execution, logger.clone(), || {
+                let cached_execution_result = match cache {
+                    None => execute(execution, child_executions).map(CachedExecutionState::NoCache),
+                    Some(cache) => execution
+                        .cached(
+                            child_executions
+                                .iter()
+                                .map(|child| CachedDependencyExecution {
+                                    double: child.execution.get_double(),
+                                    state: child.result,
+                                    source: child.dependency.as_ref(),
+                                })
+                                .collect::<Vec<_>>()
+                                .as_slice(),
+                            execution
+                                .get_target()
+                                .executor()
+                                .and_then(|reference| executors.get_for_reference(reference))
+                                .and_then(|resolution| resolution.resolution_cache()),
+                            CachedExecutionContext {
+                                cache,
+                                logger,
+                                workspace,
+                            },
+                            || execute(execution, child_executions),
+                        )
+                        .with_context(|| {
+                            format!("cached execution failed unexpectedly for target {double}")
+                        }),
+                };
+
+                match &cached_execution_result {
+                    Ok(CachedExecutionState::Cached(hash, _)) => {
+                        logger.debug(format!("target {double} is cached ({hash:0>16x})"))
+                    }
+                    Ok(_) => logger.debug(format!("target {double} is done")),
+                    Err(err) => logger.error(format!("target {double} has failed: {err:?}")),
+                };
+
+                if fail_fast && cached_execution_result.is_err() {
+                    // in-process executors and persistent workers are not stopped by killing processes.
+                    canceled.store(true, Ordering::SeqCst);
+                    processes.kill_all()?;
+                }
+
+                cached_execution_result
+            })?
+        })
+    })
+}
+
+pub(super) fn into_execution_details(
+    state: CachedExecutionState<ExecutionDetails>,
+) -> ExecutionDetails {
+    match state {
+        CachedExecutionState::Cached(_, output) => ExecutionDetails::Cached { output },
+        CachedExecutionState::New(_, details) | CachedExecutionState::NoCache(details) => details,
+    }
+}
+
+/// Get the options of a target, rendering the templates which reference outputs of its dependencies.
+fn get_target_options(
+    execution: &TargetExecution,
+    child_executions: &ChildExecutions,
+    template_data: &TemplateData<'_>,
+) -> Result<Value> {
+    let mut options = execution.get_target().options().clone();
+
+    if !TemplateData::references_dependencies(&options) {
+        return Ok(options);
+    }
+
+    let mut outputs = BTreeMap::<String, BTreeMap<String, Value>>::new();
+
+    for child in child_executions {
+        if let Some(output) = child
+            .result
+            .and_then(|result| result.as_ref().ok())
+            .and_then(ExecutionOutput::output)
+        {
+            outputs
+                .entry(child.execution.get_project().name().to_owned())
+                .or_default()
+                .insert(child.execution.get_target_name().to_owned(), output.clone());
+        }
+    }
+
+    let project = execution.get_project();
+
+    template_data
+        .with_project(project.name(), project.root())?
+        .with_dependencies(to_value(outputs)?)
+        .render(&mut options)
+        .with_context(|| {
+            format!(
+                "could not render options of target {} with outputs of its dependencies",
+                execution.get_double()
+            )
+        })?;
+
+    Ok(options)
+}
+
+/// Whether a failed attempt can be retried according to the target retry policy.
+fn is_retryable(retry: &TargetRetry, error: &Error) -> bool {
+    match retry.exit_codes() {
+        None => true,
+        Some(codes) => ProcessFailure::find(error)
+            .and_then(|failure| failure.code)
+            .is_some_and(|code| codes.contains(&code)),
+    }
+}
+
+/// Run the function *f* in a separate thread, and cancel it if it does not return before the timeout expires.
+/// *f* receives its own cancellation flag, which is set when the timeout expires or when the whole run is *canceled*.
+/// On timeout, all processes started by *f* are killed as well, so that every kind of executor stops shortly after.
+/// [`None`] is returned if the timeout has expired.
+fn run_until_timeout<T, F>(
+    timeout: Duration,
+    canceled: Option<&AtomicBool>,
+    f: F,
+) -> Result<Option<T>>
+where
+    T: Send,
+    F: FnOnce(&AtomicBool) -> T + Send,
+{
+    let tracker = ProcessTracker::with_process_groups();
+    let attempt_canceled = AtomicBool::new(false);
+    // a timeout which cannot be represented as an instant is the same as no timeout.
+    let deadline = Instant::now().checked_add(timeout);
+
+    scope(|scope| {
+        let (done_send, done_recv) = channel::<()>();
+        let thread_tracker = tracker.clone();
+        let thread_canceled = &attempt_canceled;
+        let handle = thread!(scope, move || {
+            let result = thread_tracker.tracking(|| f(thread_canceled));
+            let _ = done_send.send(());
+            result
+        });
+
+        let timed_out = loop {
+            let remaining = deadline.map_or(CANCELLATION_POLL_INTERVAL, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
             });
-            let result = lock.locked(f)?;
-            Ok(result)
+            match done_recv.recv_timeout(remaining.min(CANCELLATION_POLL_INTERVAL)) {
+                Err(RecvTimeoutError::Timeout)
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) =>
+                {
+                    break true
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if canceled.is_some_and(|canceled| canceled.load(Ordering::SeqCst)) {
+                        attempt_canceled.store(true, Ordering::SeqCst);
+                    }
+                }
+                _ => break false,
+            }
+        };
+
+        if timed_out {
+            attempt_canceled.store(true, Ordering::SeqCst);
+            tracker.kill_all()?;
         }
 
-        match cache_arc_0.as_ref() {
-            None => execution_graph.execute(parallelism, |execution, _| {
-                maybe_locked(arc_workspace.root(), execution, logger_3.clone(), || {
-                    let result = execute(execution);
-
-                    let double = execution.get_double();
-
-                    match &result {
-                        Ok(_) => logger_3.debug(format!("target {double} is done")),
-                        Err(err) => logger_3.error(format!("target {double} has failed: {err:?}")),
-                    };
-
-                    result
-                })?
-            })?,
-            Some(cache) => execution_graph
-                .execute(parallelism, |execution, child_executions| {
-                    maybe_locked(arc_workspace.root(), execution, logger_2.clone(), || {
-                        let double = execution.get_double();
-
-                        let cached_execution_result = execution
-                            .cached(
-                                child_executions
-                                    .iter()
-                                    .map(|child| CachedDependencyExecution {
-                                        double: child.execution.get_double(),
-                                        state: child.result,
-                                        source: child.dependency.as_ref(),
-                                    })
-                                    .collect::<Vec<_>>()
-                                    .as_slice(),
-                                execution
-                                    .get_target()
-                                    .executor()
-                                    .and_then(|reference| {
-                                        executor_resolutions.get_for_reference(reference)
-                                    })
-                                    .and_then(|resolution| resolution.resolution_cache()),
-                                CachedExecutionContext {
-                                    cache,
-                                    logger: &logger,
-                                    workspace: &arc_workspace,
-                                },
-                                || execute(execution),
-                            )
-                            .with_context(|| {
-                                format!("cached execution failed unexpectedly for target {double}")
-                            });
-
-                        let double = execution.get_double();
-
-                        match &cached_execution_result {
-                            Ok(CachedExecutionState::Cached(hash)) => {
-                                logger_2.debug(format!("target {double} is cached ({hash:0>16x})"))
-                            }
-                            Ok(_) => logger_2.debug(format!("target {double} is done")),
-                            Err(err) => {
-                                logger_2.error(format!("target {double} has failed: {err:?}"))
-                            }
-                        };
-
-                        cached_execution_result
-                    })?
-                })
-                .map(|executed_graph| {
-                    executed_graph.map_inner(
-                        |cached_execution_result| match cached_execution_result {
-                            CachedExecutionState::Cached(_) => ExecutionDetails::Cached,
-                            CachedExecutionState::New(_, details)
-                            | CachedExecutionState::NoCache(details) => details,
-                        },
-                    )
-                })?,
+        let result = join!(handle);
+
+        Ok((!timed_out).then_some(result))
+    })
+}
+
+/// Wait for *delay*, unless the run is *canceled* in the meantime.
+/// Returns whether the run was canceled.
+fn wait_unless_canceled(delay: Duration, canceled: &AtomicBool) -> bool {
+    let deadline = Instant::now().checked_add(delay);
+    loop {
+        if canceled.load(Ordering::SeqCst) {
+            return true;
         }
-    };
+        let remaining = deadline.map_or(CANCELLATION_POLL_INTERVAL, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        });
+        if remaining.is_zero() {
+            return false;
+        }
+        std::thread::sleep(remaining.min(CANCELLATION_POLL_INTERVAL));
+    }
+}
+
+fn maybe_locked<T, F>(root: &Path, execution: &TargetExecution, logger: Logger, f: F) -> Result<T>
+where
+    F: FnOnce() -> T,
+{
+    let target = execution.get_target();
+    if target.stateless() {
+        return Ok(f());
+    }
+    let double = execution.get_double();
+    let mut hasher = hasher();
+    double.hash(&mut hasher);
+    let mut lock = ProcessLock::try_new(root, hasher.finish())?;
+    lock.on_wait(move || {
+        logger.warn(format!(
+            "waiting for {double} to terminate in another process"
+        ))
+    });
+    let result = lock.locked(f)?;
+    Ok(result)
+}
+
+/// Display the execution graph (if requested), write reports and log statistics about a run.
+pub(super) fn report_results(
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+    captured_outputs: &HashMap<String, String>,
+    options: &RunOptions,
+    logger: &Logger,
+) -> Result<()> {
+    let report = RunReport::new(execution_results);
+
+    if let Some(path) = &options.report_file {
+        report.write_json_file(path)?;
+        logger.debug(format!("run report was written to {}", path.display()));
+    }
+
+    if let Some(path) = &options.junit_report_file {
+        write_junit_report_file(path, execution_results, captured_outputs)?;
+        logger.debug(format!("JUnit report was written to {}", path.display()));
+    }
 
-    if options.display_graph {
-        print!("\nExecution graph results:\n\n");
-        execution_results.fmt(
-            &mut std::io::stdout(),
-            |execution_result| match &execution_result.result {
-                Some(Ok(ExecutionDetails::Executed { execution_time })) => format!(
-                    "{} (executed in {execution_time:?})",
+    let mut stdout = DisplayWriter(OutputStream::Stdout);
+
+    if let RunOutputFormat::Json = options.output_format {
+        report.write_json(&mut stdout)?;
+        writeln!(stdout)?;
+    } else if options.display_graph {
+        write!(stdout, "\nExecution graph results:\n\n")?;
+        execution_results.fmt(&mut stdout, |execution_result| {
+            match &execution_result.result {
+                Some(Ok(ExecutionDetails::Executed {
+                    execution_time,
+                    attempts,
+                    ..
+                })) => format!(
+                    "{} (executed in {execution_time:?}{})",
                     colorize(execution_result.execution.get_double(), |colored| colored
                         .bold()
-                        .bright_green())
+                        .bright_green()),
+                    match attempts {
+                        1 => String::new(),
+                        _ => format!(", after {attempts} attempts"),
+                    }
                 ),
                 Some(Ok(ExecutionDetails::Noop)) => format!(
                     "{} (done)",
@@ -317,7 +883,7 @@ pub fn run<R: AsRef<Path>>(
                         ColoredString::green
                     )
                 ),
-                Some(Ok(ExecutionDetails::Cached)) => format!(
+                Some(Ok(ExecutionDetails::Cached { .. })) => format!(
                     "{} (cached)",
                     colorize(
                         execution_result.execution.get_double(),
@@ -333,50 +899,16 @@ pub fn run<R: AsRef<Path>>(
                     err.root_cause()
                 ),
                 None => format!("{} (ignored)", execution_result.execution.get_double()),
-            },
-        )?;
-        println!();
+            }
+        })?;
+        writeln!(stdout)?;
     }
 
-    let stats = RunStats::new(&execution_results);
+    let stats = &report.stats;
     logger.debug(format!("executed target(s): {}", stats.executed));
     logger.debug(format!("failed target(s): {}", stats.failed));
     logger.debug(format!("cached target(s): {}", stats.cached));
     logger.debug(format!("pending target(s): {}", stats.pending));
 
-    Ok(execution_results)
-}
-
-#[derive(Debug, Default)]
-struct RunStats {
-    executed: usize,
-    cached: usize,
-    failed: usize,
-    pending: usize,
-}
-
-impl RunStats {
-    fn new(graph: &ExecutedGraph<ExecutionDetails>) -> Self {
-        let mut stats: RunStats = Default::default();
-
-        for result in graph.execution().values() {
-            *match result {
-                ExecutedNode {
-                    result: Some(Ok(ExecutionDetails::Cached)),
-                    ..
-                } => &mut stats.cached,
-                ExecutedNode {
-                    result: Some(Ok(ExecutionDetails::Executed { .. } | ExecutionDetails::Noop)),
-                    ..
-                } => &mut stats.executed,
-                ExecutedNode {
-                    result: Some(Err(_)),
-                    ..
-                } => &mut stats.failed,
-                ExecutedNode { result: None, .. } => &mut stats.pending,
-            } += 1;
-        }
-
-        stats
-    }
+    Ok(())
 }
@@ -0,0 +1,346 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError},
+        Mutex,
+    },
+    thread::scope,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{cache::FileChangesMatcher, error::Result, logger::Logger, value::Value};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use possibly::possibly;
+
+use crate::{
+    executions::{
+        execution::{CachedExecutionState, ExecutionOutput},
+        fingerprint::input_files_hash,
+        graph::ExecutionGraph,
+    },
+    executors::{ResolvedExecutors, WorkerPool},
+    global_init,
+    system::process::ProcessTracker,
+    WorkspaceGlobals,
+};
+
+use super::{
+    run::{
+        build_execution_graph, execute_graph, into_execution_details, report_results,
+        resolve_graph_executors, GraphExecutionContext,
+    },
+    GlobalOptions, RunOptions, RunResult,
+};
+
+/// File system events are aggregated until no new event is received for this duration.
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(200);
+
+/// How often a running graph is checked for completion while waiting for file system events.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+type FileEvents = Receiver<notify::Result<Event>>;
+
+/// Input files of a single target execution.
+struct WatchedInputs<'a> {
+    double: String,
+    default_root: PathBuf,
+    matchers: &'a BTreeSet<FileChangesMatcher>,
+}
+
+impl WatchedInputs<'_> {
+    fn roots(&self) -> impl Iterator<Item = &Path> {
+        self.matchers
+            .iter()
+            .map(|matcher| matcher.root().unwrap_or(&self.default_root))
+    }
+
+    fn hash(&self) -> Result<u64> {
+        input_files_hash(&self.default_root, self.matchers)
+            .with_context(|| format!("could not compute input files hash for {}", self.double))
+    }
+}
+
+/// Run a target across a selection of projects, and run it again each time input files change.
+/// Only targets with changed input files (and the targets depending on them) are executed again.
+/// The *on_run* callback is called after each complete run, watching stops as soon as it returns false.
+pub fn watch<R, F>(
+    root: R,
+    options: RunOptions,
+    globals_options: GlobalOptions,
+    mut on_run: F,
+) -> Result<()>
+where
+    R: AsRef<Path>,
+    F: FnMut(RunResult) -> bool,
+{
+    if options.is_dry_run() {
+        bail!("watch mode cannot be used with a dry run");
+    }
+
+    let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
+    global_init(&globals)?;
+
+    let workspace = globals.workspace_handle().inner();
+    let logger = globals.logger();
+
+    let execution_graph = build_execution_graph(&options, &globals)?;
+
+    if execution_graph.targets().is_empty() {
+        logger.warn("nothing to execute");
+        return Ok(());
+    }
+
+    let mut executors = ResolvedExecutors::default();
+    resolve_graph_executors(&execution_graph, &options, &globals, &mut executors)?;
+    let workers = options
+        .get_persistent_workers(workspace)
+        .then(|| WorkerPool::new(logger.clone()));
+
+    let watched = get_watched_inputs(&execution_graph);
+
+    let (sender, events) = channel();
+    let mut watcher = RecommendedWatcher::new(sender, notify::Config::default())
+        .context("could not create file watcher")?;
+
+    for root in get_watched_roots(&watched) {
+        logger.debug(format!("watching {}", root.display()));
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("could not watch {}", root.display()))?;
+    }
+
+    // targets that succeeded during the last runs, along with their last cache hash (if any) and their output
+    let mut succeeded = HashMap::<String, (Option<u64>, Value)>::new();
+    let mut changed = HashSet::<String>::new();
+
+    loop {
+        let invalidated = execution_graph.with_dependents(changed.iter().map(String::as_str));
+        succeeded.retain(|double, _| !invalidated.contains(double));
+        changed.clear();
+
+        let baseline = hash_inputs(&watched, &logger);
+        let canceled = AtomicBool::new(false);
+        let processes = ProcessTracker::default();
+        let captured_outputs = Mutex::<HashMap<String, String>>::default();
+
+        let execution_result = scope(|scope| {
+            let handle = scope.spawn(|| {
+                execute_graph(
+                    execution_graph.clone(),
+                    GraphExecutionContext {
+                        workspace,
+                        logger: &logger,
+                        log_level: globals.log_level(),
+                        template_data: globals.deserialization_context().template_data,
+                        cache: globals.cache(),
+                        executors: &executors,
+                        parallelism: options.get_parallelism(workspace),
+                        fail_fast: options.get_fail_fast(workspace),
+                        reused: Some(&succeeded),
+                        canceled: Some(&canceled),
+                        captured_outputs: options.captures_output().then_some(&captured_outputs),
+                        workers: workers.as_ref(),
+                        hermetic_check: options.checks_hermeticity(),
+                        processes: Some(&processes),
+                    },
+                )
+            });
+
+            while !handle.is_finished() {
+                let paths = match next_changes(&events, Some(POLL_INTERVAL))? {
+                    Some(paths) => paths,
+                    None => continue,
+                };
+
+                let changes = get_changed_doubles(&watched, &baseline, &paths, &logger);
+                if !changes.is_empty() {
+                    changed.extend(changes);
+                    if !canceled.swap(true, Ordering::SeqCst) {
+                        logger.warn(format!(
+                            "input files changed during execution ({changed:?}), canceling current run"
+                        ));
+                        // processes spawned after this point are killed as soon as they start.
+                        processes.kill_all()?;
+                    }
+                }
+            }
+
+            handle
+                .join()
+                .map_err(|_| anyhow!("execution thread panicked"))
+        })?;
+
+        if let Ok(executed_graph) = &execution_result {
+            for (double, node) in executed_graph.execution() {
+                let state = match possibly!(&node.result, Some(Ok(state)) => state) {
+                    Some(state) => state,
+                    None => {
+                        succeeded.remove(double);
+                        continue;
+                    }
+                };
+                let hash = match state {
+                    CachedExecutionState::Cached(hash, _) | CachedExecutionState::New(hash, _) => {
+                        Some(*hash)
+                    }
+                    CachedExecutionState::NoCache(_) => None,
+                };
+                succeeded.insert(
+                    double.to_owned(),
+                    (hash, state.output().cloned().unwrap_or(Value::Null)),
+                );
+            }
+        }
+
+        // the run was canceled because input files changed (the flag is also set by fail-fast).
+        if !changed.is_empty() {
+            continue;
+        }
+
+        let run_result =
+            execution_result.map(|executed_graph| executed_graph.map_inner(into_execution_details));
+
+        if let Ok(executed_graph) = &run_result {
+            report_results(
+                executed_graph,
+                &captured_outputs
+                    .into_inner()
+                    .map_err(|_| anyhow!("poison error (Mutex on captured outputs)."))?,
+                &options,
+                &logger,
+            )?;
+        }
+
+        if !on_run(run_result) {
+            return Ok(());
+        }
+
+        if watched.is_empty() {
+            logger.warn("no target has input files to watch");
+            return Ok(());
+        }
+
+        // executed targets might have modified some input files
+        let baseline = hash_inputs(&watched, &logger);
+
+        logger.info("waiting for changes...");
+
+        while changed.is_empty() {
+            if let Some(paths) = next_changes(&events, None)? {
+                changed.extend(get_changed_doubles(&watched, &baseline, &paths, &logger));
+            }
+        }
+
+        logger.info(format!("input files changed for {changed:?}"));
+    }
+}
+
+fn get_watched_inputs(execution_graph: &ExecutionGraph) -> Vec<WatchedInputs<'_>> {
+    execution_graph
+        .executions()
+        .filter_map(|execution| {
+            let matchers = execution
+                .get_target()
+                .cache()?
+                .invalidate_when()
+                .input_changes()?;
+            Some(WatchedInputs {
+                double: execution.get_double(),
+                default_root: execution.get_project().root().to_owned(),
+                matchers,
+            })
+        })
+        .collect()
+}
+
+/// Get the minimal set of directories to watch recursively.
+fn get_watched_roots(watched: &[WatchedInputs<'_>]) -> BTreeSet<PathBuf> {
+    let roots = watched
+        .iter()
+        .flat_map(WatchedInputs::roots)
+        .filter(|root| root.is_dir())
+        .collect::<BTreeSet<_>>();
+
+    roots
+        .iter()
+        .filter(|root| {
+            !roots
+                .iter()
+                .any(|other| other != *root && root.starts_with(other))
+        })
+        .map(|root| root.to_path_buf())
+        .collect()
+}
+
+fn hash_inputs(watched: &[WatchedInputs<'_>], logger: &Logger) -> HashMap<String, u64> {
+    watched
+        .iter()
+        .filter_map(|inputs| match inputs.hash() {
+            Ok(hash) => Some((inputs.double.to_owned(), hash)),
+            Err(err) => {
+                logger.warn(format!("{err:?}"));
+                None
+            }
+        })
+        .collect()
+}
+
+/// Get all executions doubles for which input files have changed, compared to a previous state.
+fn get_changed_doubles(
+    watched: &[WatchedInputs<'_>],
+    baseline: &HashMap<String, u64>,
+    paths: &HashSet<PathBuf>,
+    logger: &Logger,
+) -> HashSet<String> {
+    watched
+        .iter()
+        .filter(|inputs| {
+            inputs
+                .roots()
+                .any(|root| paths.iter().any(|path| path.starts_with(root)))
+        })
+        .filter(|inputs| match inputs.hash() {
+            Ok(hash) => baseline.get(&inputs.double) != Some(&hash),
+            Err(err) => {
+                logger.debug(format!("{err:?}"));
+                true
+            }
+        })
+        .map(|inputs| inputs.double.to_owned())
+        .collect()
+}
+
+/// Wait for file system events and return all changed paths, once no more events are received for [`DEBOUNCE_DELAY`].
+/// If a timeout is provided and no event is received until it expires, [`None`] is returned.
+fn next_changes(
+    events: &FileEvents,
+    timeout: Option<Duration>,
+) -> Result<Option<HashSet<PathBuf>>> {
+    let receive = |timeout: Option<Duration>| -> Result<Option<Event>> {
+        let event = match timeout {
+            Some(timeout) => match events.recv_timeout(timeout) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => bail!("file watcher was disconnected"),
+            },
+            None => events
+                .recv()
+                .map_err(|_| anyhow!("file watcher was disconnected"))?,
+        };
+        Ok(Some(event.context("file watcher error")?))
+    };
+
+    let mut paths = match receive(timeout)? {
+        Some(event) => HashSet::from_iter(event.paths),
+        None => return Ok(None),
+    };
+
+    while let Some(event) = receive(Some(DEBOUNCE_DELAY))? {
+        paths.extend(event.paths);
+    }
+
+    Ok(Some(paths))
+}
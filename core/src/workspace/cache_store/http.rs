@@ -0,0 +1,118 @@
+use std::io::Read;
+
+use anyhow::{bail, Context};
+use blaze_common::{
+    error::Result, executor::HttpAuthentication, settings::HttpCacheBackendOptions,
+};
+use reqwest::{
+    blocking::{Body, Client, RequestBuilder, Response},
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method, StatusCode,
+};
+use url::Url;
+
+use crate::system::http::send_authenticated;
+
+use super::{entry_name, CacheStorage};
+
+/// Stores cache entries on a remote HTTP server.
+/// Each entry is a resource under the configured base URL, which is read with `GET`, written with `PUT` and removed with `DELETE`.
+pub struct HttpCacheStorage {
+    client: Client,
+    base_url: Url,
+    authentication: Option<HttpAuthentication>,
+}
+
+impl HttpCacheStorage {
+    pub fn new(options: &HttpCacheBackendOptions) -> Result<Self> {
+        let headers = options
+            .transport()
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::try_from(name.as_str())
+                        .with_context(|| format!("invalid header name {name}"))?,
+                    HeaderValue::try_from(value.as_str())
+                        .with_context(|| format!("invalid value for header {name}"))?,
+                ))
+            })
+            .collect::<Result<HeaderMap>>()?;
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .danger_accept_invalid_certs(options.transport().insecure())
+            .build()
+            .context("could not create HTTP client")?;
+
+        let mut base_url = options.url().clone();
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+
+        Ok(Self {
+            client,
+            base_url,
+            authentication: options.authentication().cloned(),
+        })
+    }
+
+    fn request(&self, method: Method, key: &str) -> Result<RequestBuilder> {
+        let url = self.base_url.join(&entry_name(key))?;
+        Ok(self.client.request(method, url))
+    }
+
+    fn send(&self, request: RequestBuilder, key: &str) -> Result<Response> {
+        send_authenticated(request, self.authentication.as_ref())
+            .with_context(|| format!("could not reach remote cache for entry {key}"))
+    }
+}
+
+impl CacheStorage for HttpCacheStorage {
+    fn get(&self, key: &str) -> Result<Option<Box<dyn Read>>> {
+        let response = self.send(self.request(Method::GET, key)?, key)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(Box::new(response))),
+            status => bail!("remote cache returned status {status} when reading entry {key}"),
+        }
+    }
+
+    fn put(&self, key: &str, mut content: Box<dyn Read + Send>, length: u64) -> Result<()> {
+        // digest authentication sends the request twice, so its body must be buffered.
+        let body = match self.authentication {
+            Some(HttpAuthentication::Digest(_)) => {
+                let mut buffer = Vec::with_capacity(length as usize);
+                content
+                    .read_to_end(&mut buffer)
+                    .with_context(|| format!("could not read content of entry {key}"))?;
+                Body::from(buffer)
+            }
+            _ => Body::sized(content, length),
+        };
+        let response = self.send(self.request(Method::PUT, key)?.body(body), key)?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("remote cache returned status {status} when writing entry {key}")
+        }
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let response = self.send(self.request(Method::DELETE, key)?, key)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(()),
+            status if status.is_success() => Ok(()),
+            status => bail!("remote cache returned status {status} when removing entry {key}"),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        let response = self.send(self.request(Method::HEAD, key)?, key)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => bail!("remote cache returned status {status} when looking up entry {key}"),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use blaze_common::error::Result;
+
+use crate::system::random::random_string;
+
+use super::{entry_name, CacheStorage};
+
+const CACHE_FOLDER_NAME: &str = ".blaze/cache";
+
+/// Stores cache entries as files in the workspace cache directory.
+pub struct LocalCacheStorage {
+    root: PathBuf,
+}
+
+impl LocalCacheStorage {
+    pub fn load(root: &Path) -> Result<Self> {
+        let root = root.join(CACHE_FOLDER_NAME);
+
+        let is_dir = match fs::metadata(&root) {
+            Ok(metadata) => metadata.is_dir(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+
+        if !is_dir {
+            std::fs::create_dir_all(&root).with_context(|| {
+                format!("failed to created cache directory at {}", root.display())
+            })?;
+        }
+
+        Ok(Self { root })
+    }
+
+    fn get_entry_filename(&self, key: &str) -> PathBuf {
+        self.root.join(entry_name(key))
+    }
+}
+
+impl CacheStorage for LocalCacheStorage {
+    fn get(&self, key: &str) -> Result<Option<Box<dyn Read>>> {
+        let path = self.get_entry_filename(key);
+
+        match File::open(&path) {
+            Ok(file) => Ok(Some(Box::new(BufReader::new(file)))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err)
+                .with_context(|| format!("could not read cache entry at {}", path.display())),
+        }
+    }
+
+    fn put(&self, key: &str, mut content: Box<dyn Read + Send>, _: u64) -> Result<()> {
+        let path = self.get_entry_filename(key);
+
+        // entries are written to a unique temporary file, then renamed, so that readers never observe a partial entry.
+        let temporary_path = path.with_extension(format!("{}.tmp", random_string(12)));
+        let written = File::create(&temporary_path)
+            .and_then(|mut file| io::copy(&mut content, &mut file))
+            .and_then(|_| fs::rename(&temporary_path, &path));
+
+        if let Err(err) = written {
+            let _ = fs::remove_file(&temporary_path);
+            return Err(err)
+                .with_context(|| format!("could not write cache entry at {}", path.display()));
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let path = self.get_entry_filename(key);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err)
+                .with_context(|| format!("could not remove cache entry at {}", path.display())),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        Ok(self.get_entry_filename(key).try_exists()?)
+    }
+}
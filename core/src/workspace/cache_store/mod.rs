@@ -0,0 +1,212 @@
+mod http;
+mod local;
+
+use std::{
+    fs::{self, File},
+    io::{self, Cursor, Read},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context};
+use blaze_common::{error::Result, settings::CacheBackend};
+
+use serde::{de::DeserializeOwned, Serialize};
+use xxhash_rust::xxh3;
+
+use crate::system::{hash::hash_file, random::random_string};
+
+use self::{http::HttpCacheStorage, local::LocalCacheStorage};
+
+const ARTIFACTS_KEY_PREFIX: &str = "artifacts";
+
+/// A raw storage for cache entries, identified by their key.
+pub trait CacheStorage: Send + Sync {
+    /// Open an entry for reading. Returns [`Ok(None)`] if the entry does not exist.
+    fn get(&self, key: &str) -> Result<Option<Box<dyn Read>>>;
+
+    /// Create or replace an entry, with content of the given length read from `content`.
+    fn put(&self, key: &str, content: Box<dyn Read + Send>, length: u64) -> Result<()>;
+
+    /// Remove an entry. Removing an entry that does not exist is not an error.
+    fn remove(&self, key: &str) -> Result<()>;
+
+    /// Check if an entry exists.
+    fn contains(&self, key: &str) -> Result<bool>;
+}
+
+/// Get the permission bits of a file, so that they can be applied again when it is restored.
+/// Always returns [`None`] on platforms other than Unix.
+#[cfg(unix)]
+pub fn get_file_mode(path: &Path) -> Result<Option<u32>> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata = fs::metadata(path)
+        .with_context(|| format!("could not read metadata of {}", path.display()))?;
+    Ok(Some(metadata.permissions().mode() & 0o7777))
+}
+
+#[cfg(not(unix))]
+pub fn get_file_mode(_: &Path) -> Result<Option<u32>> {
+    Ok(None)
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: Option<u32>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| format!("could not set permissions of {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_: &Path, _: Option<u32>) -> Result<()> {
+    Ok(())
+}
+
+/// Name of a cache entry in the underlying storage.
+fn entry_name(key: &str) -> String {
+    format!("{:0>16x}", xxh3::xxh3_64(key.as_bytes()))
+}
+
+pub struct CacheStore {
+    storage: Box<dyn CacheStorage>,
+}
+
+impl CacheStore {
+    /// Cache an object.
+    pub fn cache<T>(&self, key: &str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let content = serde_cbor::to_vec(value)
+            .with_context(|| format!("could not serialize cache entry {key}"))?;
+        let length = content.len() as u64;
+        self.storage
+            .put(key, Box::new(Cursor::new(content)), length)
+    }
+
+    /// Invalidate a cache key and remove it.
+    pub fn invalidate(&self, key: &str) -> Result<()> {
+        self.storage.remove(key)
+    }
+
+    /// Tries to restore an object from cache based on its type and key.
+    /// If the key does not exist, it will return [`Ok(None)`].
+    /// Otherwise, it will return [`Ok(Some(T))`].
+    pub fn restore<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.storage
+            .get(key)?
+            .map(|content| {
+                serde_cbor::from_reader(content)
+                    .with_context(|| format!("could not deserialize cache entry {key}"))
+            })
+            .transpose()
+    }
+
+    /// Store a file in the content-addressed artifacts store.
+    /// Returns the checksum of the file, which is used as the artifact key.
+    pub fn store_artifact(&self, path: &Path) -> Result<u64> {
+        let checksum = hash_file(path)
+            .with_context(|| format!("could not compute checksum for {}", path.display()))?;
+        let key = get_artifact_key(checksum);
+
+        if self.storage.contains(&key)? {
+            return Ok(checksum);
+        }
+
+        let file = File::open(path)
+            .with_context(|| format!("could not read artifact at {}", path.display()))?;
+        let length = file.metadata()?.len();
+        self.storage
+            .put(&key, Box::new(file), length)
+            .with_context(|| format!("could not store artifact {}", path.display()))?;
+
+        Ok(checksum)
+    }
+
+    /// Check whether an artifact exists in the store.
+    pub fn contains_artifact(&self, checksum: u64) -> Result<bool> {
+        self.storage.contains(&get_artifact_key(checksum))
+    }
+
+    /// Restore a file from the artifacts store at the given path, with the provided unix mode (see [`get_file_mode`]).
+    /// The file content is left untouched if it already has the expected content.
+    /// Returns [`Ok(false)`] if the artifact does not exist in the store.
+    pub fn restore_artifact(&self, checksum: u64, path: &Path, mode: Option<u32>) -> Result<bool> {
+        let is_up_to_date = match hash_file(path) {
+            Ok(current_checksum) => current_checksum == checksum,
+            Err(err)
+                if err
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|err| err.kind() == io::ErrorKind::NotFound) =>
+            {
+                false
+            }
+            Err(err) => return Err(err),
+        };
+
+        if is_up_to_date {
+            set_file_mode(path, mode)?;
+            return Ok(true);
+        }
+
+        let mut content = match self.storage.get(&get_artifact_key(checksum))? {
+            Some(content) => content,
+            None => return Ok(false),
+        };
+
+        let parent = path
+            .parent()
+            .ok_or_else(|| anyhow!("{} has no parent directory", path.display()))?;
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create directory {}", parent.display()))?;
+
+        // write to a unique temporary file first, so that concurrent restores never observe a partial file.
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("{} has no file name", path.display()))?
+            .to_string_lossy();
+        let tmp_path = parent.join(format!(".{file_name}.{}.tmp", random_string(12)));
+
+        let written = File::create(&tmp_path)
+            .and_then(|mut file| io::copy(&mut content, &mut file))
+            .and_then(|_| fs::rename(&tmp_path, path));
+        if let Err(err) = written {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err)
+                .with_context(|| format!("could not restore artifact at {}", path.display()));
+        }
+
+        set_file_mode(path, mode)?;
+
+        Ok(true)
+    }
+
+    /// Load the local cache store, located in the workspace.
+    pub fn load(root: &Path) -> Result<Self> {
+        Ok(Self {
+            storage: Box::new(LocalCacheStorage::load(root)?),
+        })
+    }
+
+    /// Load a cache store using the configured backend.
+    pub fn load_with_backend(root: &Path, backend: &CacheBackend) -> Result<Self> {
+        match backend {
+            CacheBackend::Local => Self::load(root),
+            CacheBackend::Http(options) => Ok(Self {
+                storage: Box::new(
+                    HttpCacheStorage::new(options)
+                        .context("could not initialize HTTP cache backend")?,
+                ),
+            }),
+        }
+    }
+}
+
+fn get_artifact_key(checksum: u64) -> String {
+    format!("{ARTIFACTS_KEY_PREFIX}/{checksum:0>16x}")
+}
@@ -1,19 +1,65 @@
-use std::collections::HashSet;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::OpenOptions;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Mutex, MutexGuard};
 
 use anyhow::Context;
 use anyhow::{anyhow, bail};
 use blaze_common::error::Error;
 use blaze_common::IntoEnumIterator;
 use blaze_common::{configuration_file::ConfigurationFileFormat, error::Result, value::Value};
-use jrsonnet_evaluator::{FileImportResolver, Val};
-use serde::de::DeserializeOwned;
+use jrsonnet_evaluator::{parser::SourcePath, FileImportResolver, ImportResolver, Val};
+use jrsonnet_gcmodule::Trace;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::template::TemplateData;
+use crate::system::hash::{hash_file, hasher};
+
+use super::{cache_store::CacheStore, template::TemplateData};
 
 const JSONNET_EXTVAR_NAME: &str = "blaze";
 
+const CONFIGURATIONS_KEY_PREFIX: &str = "configurations";
+
+/// Files which were read in order to evaluate configurations, when enabled with [`track_configuration_files`].
+static CONFIGURATION_FILES: Lazy<Mutex<Option<BTreeSet<PathBuf>>>> = Lazy::new(Mutex::default);
+
+fn lock_configuration_files() -> Result<MutexGuard<'static, Option<BTreeSet<PathBuf>>>> {
+    CONFIGURATION_FILES
+        .lock()
+        .map_err(|_| anyhow!("poison error (Mutex on configuration files)."))
+}
+
+/// Start keeping track of the files read in order to evaluate configurations (configuration files and their Jsonnet imports).
+/// Files which were tracked so far are forgotten.
+pub fn track_configuration_files() -> Result<()> {
+    *lock_configuration_files()? = Some(BTreeSet::new());
+    Ok(())
+}
+
+/// Whether a file was read in order to evaluate a configuration, since [`track_configuration_files`] was called.
+pub fn is_configuration_file(path: &Path) -> Result<bool> {
+    Ok(lock_configuration_files()?
+        .as_ref()
+        .is_some_and(|files| files.contains(path)))
+}
+
+fn record_configuration_files<'a, I: IntoIterator<Item = &'a PathBuf>>(files: I) -> Result<()> {
+    if let Some(tracked) = lock_configuration_files()?.as_mut() {
+        // paths are compared with the ones reported by the file system watcher.
+        tracked.extend(
+            files
+                .into_iter()
+                .map(|file| dunce::canonicalize(file).unwrap_or_else(|_| file.to_owned())),
+        );
+    }
+    Ok(())
+}
+
 /// Get corresponding extensions for a given file type. The returned tuple's first element is the main extension.
 /// The second tuple element is a list of other extensions that are supported as well.
 pub fn get_format_extensions(format: ConfigurationFileFormat) -> (&'static str, Vec<&'static str>) {
@@ -64,6 +110,87 @@ pub fn infer_configuration_file_path<P: AsRef<Path>, S: AsRef<str>>(
 pub struct DeserializationContext<'a> {
     pub template_data: &'a TemplateData<'a>,
     pub jpath: &'a HashSet<PathBuf>,
+    /// When set, evaluated project configurations are cached (see [`deserialize_cached_configuration`]).
+    pub cache: Option<&'a CacheStore>,
+}
+
+/// Files which were involved in the evaluation of a configuration.
+#[derive(Default)]
+struct EvaluatedFiles {
+    /// The configuration file itself, and the files it imported.
+    loaded: BTreeSet<PathBuf>,
+    /// Import candidates which did not exist, and would have been imported instead of the loaded files otherwise
+    /// (for example, when an import is resolved with a Jsonnet library directory).
+    missing: BTreeSet<PathBuf>,
+}
+
+/// Resolves Jsonnet imports just like [`FileImportResolver`], and keeps track of all the files that were loaded.
+struct TrackingImportResolver {
+    inner: FileImportResolver,
+    /// Library directories, in the same order as the ones of the inner resolver.
+    library_paths: Vec<PathBuf>,
+    files: Rc<RefCell<EvaluatedFiles>>,
+}
+
+impl Trace for TrackingImportResolver {
+    fn is_type_tracked() -> bool {
+        false
+    }
+}
+
+impl ImportResolver for TrackingImportResolver {
+    fn resolve_from(
+        &self,
+        from: &SourcePath,
+        path: &str,
+    ) -> jrsonnet_evaluator::Result<SourcePath> {
+        let resolved = self.inner.resolve_from(from, path)?;
+
+        // the import is looked up relatively to the importing file first, then in each library directory.
+        let direct = from.path().map(|from| {
+            if from.is_dir() {
+                from.join(path)
+            } else {
+                from.parent().unwrap_or(from).join(path)
+            }
+        });
+
+        self.files.borrow_mut().missing.extend(
+            direct
+                .into_iter()
+                .chain(self.library_paths.iter().map(|library| library.join(path)))
+                .take_while(|candidate| !candidate.exists()),
+        );
+
+        Ok(resolved)
+    }
+
+    fn resolve(&self, path: &Path) -> jrsonnet_evaluator::Result<SourcePath> {
+        self.inner.resolve(path)
+    }
+
+    fn load_file_contents(&self, resolved: &SourcePath) -> jrsonnet_evaluator::Result<Vec<u8>> {
+        if let Some(path) = resolved.path() {
+            self.files.borrow_mut().loaded.insert(path.to_owned());
+        }
+        self.inner.load_file_contents(resolved)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A rendered configuration, along with what is needed in order to know if it is still up to date.
+#[derive(Serialize, Deserialize)]
+struct CachedConfiguration {
+    /// Hash of the template data and Jsonnet library paths the configuration was evaluated with.
+    context: u64,
+    /// Checksums of the configuration file, of the files it imported and of the template helpers.
+    files: BTreeMap<PathBuf, u64>,
+    /// Import candidates which must still not exist.
+    missing: BTreeSet<PathBuf>,
+    value: Value,
 }
 
 pub fn deserialize_configuration<T>(
@@ -74,6 +201,95 @@ pub fn deserialize_configuration<T>(
 where
     T: DeserializeOwned,
 {
+    let (mut value, _) = evaluate_configuration(path, file_type, context)?;
+
+    context.template_data.render(&mut value)?;
+
+    Ok(T::deserialize(value)?)
+}
+
+/// Same as [`deserialize_configuration`], except that the rendered value is restored from *cache* when the configuration did not change since it was last evaluated.
+/// The value is only cached once *validate* succeeds, and if rendering it again would give the same result (the `shell` and `random` template helpers are not used).
+/// Template helpers written in Rhai are assumed to be pure : their output must only depend on their arguments and on the template data.
+pub fn deserialize_cached_configuration<V>(
+    path: &Path,
+    file_type: ConfigurationFileFormat,
+    context: DeserializationContext,
+    cache: &CacheStore,
+    validate: V,
+) -> Result<Value>
+where
+    V: FnOnce(&Value) -> Result<()>,
+{
+    let key = format!("{CONFIGURATIONS_KEY_PREFIX}/{}", path.display());
+    let context_hash = get_context_hash(context);
+
+    let cached = match cache.restore::<CachedConfiguration>(&key) {
+        Ok(cached) => cached,
+        // entries which cannot be read (for example, written by another version of Blaze) are evaluated again.
+        Err(_) => {
+            cache.invalidate(&key).with_context(|| {
+                format!(
+                    "could not invalidate cached configuration for {}",
+                    path.display()
+                )
+            })?;
+            None
+        }
+    };
+
+    if let Some(cached) = cached {
+        if cached.context == context_hash
+            && are_files_unchanged(&cached.files)?
+            && cached.missing.iter().all(|file| !file.exists())
+        {
+            record_configuration_files(cached.files.keys().chain(&cached.missing))?;
+            return Ok(cached.value);
+        }
+    }
+
+    let (mut value, evaluated_files) = evaluate_configuration(path, file_type, context)?;
+    let is_cacheable = !TemplateData::uses_impure_helpers(&value);
+
+    context.template_data.render(&mut value)?;
+    validate(&value)?;
+
+    if !is_cacheable {
+        return Ok(value);
+    }
+
+    let files = evaluated_files
+        .loaded
+        .into_iter()
+        .chain(context.template_data.helper_scripts().map(Path::to_owned))
+        .map(|file| {
+            let checksum = hash_file(&file)
+                .with_context(|| format!("could not compute checksum for {}", file.display()))?;
+            Ok((file, checksum))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    let cached = CachedConfiguration {
+        context: context_hash,
+        files,
+        missing: evaluated_files.missing,
+        value,
+    };
+
+    cache
+        .cache(&key, &cached)
+        .with_context(|| format!("could not cache configuration for {}", path.display()))?;
+
+    Ok(cached.value)
+}
+
+/// Evaluate a configuration file, without rendering it.
+/// Files which were involved in the evaluation are returned along with the value.
+fn evaluate_configuration(
+    path: &Path,
+    file_type: ConfigurationFileFormat,
+    context: DeserializationContext,
+) -> Result<(Value, EvaluatedFiles)> {
     let get_stream = || {
         OpenOptions::new()
             .read(true)
@@ -81,26 +297,81 @@ where
             .with_context(|| format!("could not open configuration file at {}", path.display()))
     };
 
-    let mut value: Value = match file_type {
-        ConfigurationFileFormat::Json => serde_json::from_reader(get_stream()?)
-            .with_context(|| format!("could not deserialize JSON at {}", path.display()))?,
-        ConfigurationFileFormat::Yaml => serde_yaml::from_reader(get_stream()?)
-            .with_context(|| format!("could not deserialize YAML at {}", path.display()))?,
+    let single_file = || EvaluatedFiles {
+        loaded: BTreeSet::from([path.to_owned()]),
+        missing: BTreeSet::new(),
+    };
+
+    let (value, evaluated_files) = match file_type {
+        ConfigurationFileFormat::Json => (
+            serde_json::from_reader(get_stream()?)
+                .with_context(|| format!("could not deserialize JSON at {}", path.display()))?,
+            single_file(),
+        ),
+        ConfigurationFileFormat::Yaml => (
+            serde_yaml::from_reader(get_stream()?)
+                .with_context(|| format!("could not deserialize YAML at {}", path.display()))?,
+            single_file(),
+        ),
         ConfigurationFileFormat::Jsonnet => {
-            let state = create_jsonnet_evaluation_state(context.template_data, context.jpath)?;
+            let (state, files) =
+                create_jsonnet_evaluation_state(context.template_data, context.jpath)?;
 
             let val = state
                 .import(path)
                 .map_err(convert_jrsonnet_error)
                 .with_context(|| format!("could not evaluate jsonnet file {}", path.display()))?;
 
-            jrsonnet_val_to_value(val)?
+            let value = jrsonnet_val_to_value(val)?;
+            (value, files.take())
         }
     };
 
-    context.template_data.render(&mut value)?;
+    record_configuration_files(
+        evaluated_files
+            .loaded
+            .iter()
+            .chain(&evaluated_files.missing),
+    )?;
 
-    Ok(T::deserialize(value)?)
+    Ok((value, evaluated_files))
+}
+
+/// Hash everything a configuration can depend on, besides files.
+/// Template data contains all the environment variables of the Blaze process (which are available to configurations),
+/// so any change to the environment invalidates cached configurations.
+fn get_context_hash(context: DeserializationContext) -> u64 {
+    let mut hasher = hasher();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    context.template_data.inner().hash(&mut hasher);
+    context
+        .jpath
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check that files still have the provided checksums.
+fn are_files_unchanged(files: &BTreeMap<PathBuf, u64>) -> Result<bool> {
+    for (file, checksum) in files {
+        match hash_file(file) {
+            Ok(current) if current == *checksum => {}
+            Ok(_) => return Ok(false),
+            Err(err)
+                if err
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|err| err.kind() == std::io::ErrorKind::NotFound) =>
+            {
+                return Ok(false)
+            }
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("could not compute checksum for {}", file.display()))
+            }
+        }
+    }
+    Ok(true)
 }
 
 fn convert_jrsonnet_error(error: jrsonnet_evaluator::Error) -> Error {
@@ -131,11 +402,15 @@ fn convert_jrsonnet_error(error: jrsonnet_evaluator::Error) -> Error {
 fn create_jsonnet_evaluation_state(
     template_data: &TemplateData,
     jpath: &HashSet<PathBuf>,
-) -> Result<jrsonnet_evaluator::State> {
+) -> Result<(jrsonnet_evaluator::State, Rc<RefCell<EvaluatedFiles>>)> {
     let state = jrsonnet_evaluator::State::default();
-    state.set_import_resolver(FileImportResolver::new(
-        jpath.iter().map(PathBuf::to_owned).collect(),
-    ));
+    let files = Rc::<RefCell<EvaluatedFiles>>::default();
+    let library_paths = jpath.iter().map(PathBuf::to_owned).collect::<Vec<_>>();
+    state.set_import_resolver(TrackingImportResolver {
+        inner: FileImportResolver::new(library_paths.clone()),
+        library_paths,
+        files: files.clone(),
+    });
     let ctx = jrsonnet_stdlib::ContextInitializer::new(
         state.clone(),
         jrsonnet_evaluator::trace::PathResolver::new_cwd_fallback(),
@@ -145,7 +420,7 @@ fn create_jsonnet_evaluation_state(
         value_to_jrsonnet_val(template_data.inner())?,
     );
     state.set_context_initializer(ctx);
-    Ok(state)
+    Ok((state, files))
 }
 
 fn jrsonnet_val_to_value(val: jrsonnet_evaluator::Val) -> Result<Value> {
@@ -217,7 +492,7 @@ where
         ConfigurationFileFormat::Json => serde_json::from_str(code)?,
         ConfigurationFileFormat::Yaml => serde_yaml::from_str(code)?,
         ConfigurationFileFormat::Jsonnet => {
-            let state = create_jsonnet_evaluation_state(context.template_data, context.jpath)?;
+            let (state, _) = create_jsonnet_evaluation_state(context.template_data, context.jpath)?;
 
             let val = state
                 .evaluate_snippet("inline code", code)
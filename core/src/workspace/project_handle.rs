@@ -1,4 +1,8 @@
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
 use blaze_common::{
     error::Result,
@@ -14,7 +18,8 @@ use crate::workspace::schemas::create_schema;
 
 use super::{
     configurations::{
-        deserialize_configuration, infer_configuration_file_path, DeserializationContext,
+        deserialize_cached_configuration, deserialize_configuration, infer_configuration_file_path,
+        DeserializationContext,
     },
     schemas::validate_json,
 };
@@ -55,24 +60,43 @@ impl ProjectHandle {
         // Remove filename from configuration file path to get root
         let _ = root.pop();
 
-        let deserialized_project_value = deserialize_configuration::<Value>(
-            &configuration_file_path,
-            file_type,
-            DeserializationContext {
-                jpath: options.deserialization_context.jpath,
-                template_data: &options
-                    .deserialization_context
-                    .template_data
-                    .with_project(options.name, &root)?,
-            },
-        )?;
-
-        validate_json(&PROJECT_JSON_SCHEMA, &deserialized_project_value).with_context(|| {
-            format!(
-                "invalid project configuration at {}",
-                configuration_file_path.display()
-            )
-        })?;
+        let template_data = options
+            .deserialization_context
+            .template_data
+            .with_project(options.name, &root)?;
+
+        let deserialization_context = DeserializationContext {
+            template_data: &template_data,
+            ..options.deserialization_context
+        };
+
+        let validate = |value: &Value| {
+            validate_json(&PROJECT_JSON_SCHEMA, value).with_context(|| {
+                format!(
+                    "invalid project configuration at {}",
+                    configuration_file_path.display()
+                )
+            })
+        };
+
+        let deserialized_project_value = match deserialization_context.cache {
+            Some(cache) => deserialize_cached_configuration(
+                &configuration_file_path,
+                file_type,
+                deserialization_context,
+                cache,
+                validate,
+            )?,
+            None => {
+                let value = deserialize_configuration::<Value>(
+                    &configuration_file_path,
+                    file_type,
+                    deserialization_context,
+                )?;
+                validate(&value)?;
+                value
+            }
+        };
 
         let project_configuration = ProjectConfiguration::deserialize(deserialized_project_value)
             .with_context(|| {
@@ -93,3 +117,28 @@ impl ProjectHandle {
         self.0
     }
 }
+
+/// Projects which were already loaded during the lifetime of the workspace globals, indexed by name.
+#[derive(Default)]
+pub struct LoadedProjects(Mutex<HashMap<String, Arc<Project>>>);
+
+impl LoadedProjects {
+    /// Get a loaded project, or load it with *load* if it was not loaded yet.
+    pub fn get_or_try_load<F>(&self, name: &str, load: F) -> Result<Arc<Project>>
+    where
+        F: FnOnce() -> Result<Project>,
+    {
+        if let Some(project) = self.lock()?.get(name) {
+            return Ok(project.clone());
+        }
+        let project = Arc::new(load()?);
+        self.lock()?.insert(name.to_owned(), project.clone());
+        Ok(project)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<HashMap<String, Arc<Project>>>> {
+        self.0
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on loaded projects)."))
+    }
+}
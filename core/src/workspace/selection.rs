@@ -1,7 +1,8 @@
 use anyhow::{anyhow, bail, Context};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     str::FromStr,
 };
 
@@ -11,11 +12,21 @@ use blaze_common::{
     workspace::{ProjectRef, Workspace},
 };
 
+use crate::system::repository::get_changed_files;
+
+use super::{
+    configurations::DeserializationContext,
+    project_handle::{LoadedProjects, ProjectHandle, ProjectOptions},
+};
+
+#[derive(Clone, Copy)]
 pub struct SelectionContext<'a> {
     pub workspace: &'a Workspace,
+    pub deserialization_context: DeserializationContext<'a>,
+    pub loaded_projects: &'a LoadedProjects,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SelectorSource {
     Provided(ProjectSelector),
     Named(String),
@@ -60,6 +71,9 @@ impl Selection {
                 self.select_include_exclude_project_refs(ctx, include, exclude)
             }
             ProjectSelector::Tagged(tags) => Ok(self.select_tagged_project_refs(ctx, tags)),
+            ProjectSelector::Affected { base, head } => {
+                self.select_affected_project_refs(ctx, base, head.as_deref())
+            }
         }
     }
 
@@ -128,6 +142,107 @@ impl Selection {
         })
     }
 
+    fn select_affected_project_refs<'s>(
+        &self,
+        ctx: SelectionContext<'s>,
+        base: &str,
+        head: Option<&str>,
+    ) -> Result<HashMap<&'s String, &'s ProjectRef>> {
+        let workspace_root = ctx.workspace.root();
+        let changed_files = get_changed_files(workspace_root, base, head)
+            .context("could not list changed files")?;
+
+        // changed files are absolute canonical paths, so project roots must be canonical as well.
+        let project_roots = ctx
+            .workspace
+            .projects()
+            .iter()
+            .map(|(name, project_ref)| {
+                let root = workspace_root.join(project_ref.path());
+                Ok((
+                    name.as_str(),
+                    dunce::canonicalize(&root).with_context(|| {
+                        format!("could not resolve project root {}", root.display())
+                    })?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut affected = HashSet::<&str>::new();
+
+        for file in &changed_files {
+            // with nested projects, the most specific project root is used
+            if let Some((name, _)) = project_roots
+                .iter()
+                .filter(|(_, root)| file.starts_with(root))
+                .max_by_key(|(_, root)| root.components().count())
+            {
+                affected.insert(*name);
+            }
+        }
+
+        let dependents = self.get_project_dependents(ctx)?;
+        let mut pending = affected.iter().copied().collect::<VecDeque<_>>();
+
+        while let Some(name) = pending.pop_front() {
+            for dependent in dependents.get(name).into_iter().flatten() {
+                if affected.insert(*dependent) {
+                    pending.push_back(*dependent);
+                }
+            }
+        }
+
+        Ok(self.select_predicated_project_refs(ctx, |(name, _)| affected.contains(name)))
+    }
+
+    /// Maps each project name to the names of the projects that have at least one target depending on it.
+    fn get_project_dependents<'s>(
+        &self,
+        ctx: SelectionContext<'s>,
+    ) -> Result<HashMap<&'s str, HashSet<&'s str>>> {
+        let mut dependents = HashMap::<&str, HashSet<&str>>::new();
+
+        for (name, project_ref) in ctx.workspace.projects() {
+            let project = ctx.loaded_projects.get_or_try_load(name, || {
+                Ok(ProjectHandle::from_root(
+                    ctx.workspace.root().join(project_ref.path()),
+                    ProjectOptions {
+                        name,
+                        deserialization_context: ctx.deserialization_context,
+                    },
+                )
+                .with_context(|| format!("error while reading \"{name}\" project configuration"))?
+                .unwrap_inner())
+            })?;
+
+            for dependency in project
+                .targets()
+                .values()
+                .flat_map(|target| target.dependencies())
+            {
+                let selected = match dependency.projects() {
+                    None => continue,
+                    Some(ProjectSelector::Affected { .. }) => bail!(
+                        "affected projects selector cannot be used in target dependencies (in project \"{name}\")"
+                    ),
+                    Some(selector) => {
+                        Selection::from_source(SelectorSource::Provided(selector.clone()))
+                            .select(ctx)?
+                    }
+                };
+
+                for dependency_name in selected.keys() {
+                    dependents
+                        .entry(dependency_name.as_str())
+                        .or_default()
+                        .insert(name.as_str());
+                }
+            }
+        }
+
+        Ok(dependents)
+    }
+
     fn select_predicated_project_refs<'s, P: Fn((&str, &ProjectRef)) -> bool>(
         &self,
         ctx: SelectionContext<'s>,
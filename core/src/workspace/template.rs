@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fmt::Debug,
     io::{self, Read},
     num::NonZeroUsize,
@@ -16,11 +16,13 @@ use blaze_common::{
     workspace::Workspace,
 };
 use handlebars::{no_escape, Handlebars, HelperDef, RenderError, RenderErrorReason};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::Deserialize;
 
 use crate::system::{
     env::Env,
-    process::{Process, ProcessOptions},
+    process::{Process, ProcessContext, ProcessOptions},
     random::random_string,
     shell::ShellFormatter,
 };
@@ -38,6 +40,16 @@ const ENVIRONMENT_KEY: &str = "environment";
 
 const VARIABLES_KEY: &str = "vars";
 
+const DEPENDENCIES_KEY: &str = "deps";
+
+/// Matches templates referencing dependencies outputs, which can only be rendered right before execution.
+static DEPENDENCIES_TEMPLATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{([^}]*[^\w.])?deps\.").unwrap());
+
+/// Matches templates using helpers which may render differently each time.
+static IMPURE_HELPERS_TEMPLATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{[^}]*\b(shell|random)\b").unwrap());
+
 const PLATFORM_KEY: &str = "platform";
 const FAMILY_KEY: &str = "family";
 const ARCHITECTURE_KEY: &str = "architecture";
@@ -55,6 +67,7 @@ const SEPARATOR: &str = "\\";
 pub struct TemplateData<'reg> {
     data: Value,
     generator: Handlebars<'reg>,
+    helper_scripts: BTreeSet<PathBuf>,
 }
 
 impl Debug for TemplateData<'_> {
@@ -103,6 +116,7 @@ impl<'reg> TemplateData<'reg> {
         Ok(Self {
             data: Self::initial_data(workspace_root)?,
             generator: Self::generator(workspace_root, &scripts)?,
+            helper_scripts: scripts.into_iter().collect(),
         })
     }
 
@@ -135,18 +149,56 @@ impl<'reg> TemplateData<'reg> {
         Ok(copy)
     }
 
+    /// Add outputs of dependencies, indexed by project name and then by target name.
+    pub fn with_dependencies(&self, outputs: Value) -> Self {
+        let mut copy = self.clone();
+        copy.data
+            .overwrite(Value::object([(DEPENDENCIES_KEY, outputs)]));
+        copy
+    }
+
     pub fn inner(&self) -> &Value {
         &self.data
     }
 
+    /// Paths of the Rhai scripts registered as template helpers.
+    pub fn helper_scripts(&self) -> impl Iterator<Item = &Path> {
+        self.helper_scripts.iter().map(PathBuf::as_path)
+    }
+
+    /// Whether the value contains strings referencing dependencies outputs.
+    pub fn references_dependencies(value: &Value) -> bool {
+        match value {
+            Value::String(string) => DEPENDENCIES_TEMPLATE.is_match(string.as_str()),
+            Value::Object(object) => object.values().any(Self::references_dependencies),
+            Value::Array(array) => array.iter().any(Self::references_dependencies),
+            _ => false,
+        }
+    }
+
+    /// Whether the value contains strings using the `shell` or `random` helpers.
+    pub fn uses_impure_helpers(value: &Value) -> bool {
+        match value {
+            Value::String(string) => IMPURE_HELPERS_TEMPLATE.is_match(string.as_str()),
+            Value::Object(object) => object.values().any(Self::uses_impure_helpers),
+            Value::Array(array) => array.iter().any(Self::uses_impure_helpers),
+            _ => false,
+        }
+    }
+
     pub fn render_str(&self, view: &str) -> Result<String> {
         self.generator
             .render_template(view, &self.data)
             .context("could not compile view template")
     }
 
+    /// Render all strings in the value.
+    /// Strings referencing dependencies outputs are left as is, unless dependencies were provided using [`Self::with_dependencies`].
     pub fn render(&self, value: &mut Value) -> Result<()> {
         if let Some(string) = value.as_str() {
+            if self.data.at(DEPENDENCIES_KEY).is_none() && DEPENDENCIES_TEMPLATE.is_match(string) {
+                return Ok(());
+            }
             *value = Value::string(self.render_str(string)?);
         } else if let Some(mut obj) = value.as_mut_object() {
             for field in obj.values_mut() {
@@ -262,20 +314,25 @@ impl HelperDef for ShHelper {
             ))
         })?;
 
-        let process = Process::run_with_options(
-            program,
-            arguments,
-            ProcessOptions {
-                cwd: Some(params.cwd.unwrap_or_else(|| self.root.to_owned())),
-                display_output: false,
-                ..Default::default()
-            },
-        )
-        .map_err(|err| {
-            generic_error(&format!(
-                "could not create process for \"{command}\" ({err})"
-            ))
-        })?;
+        // helper output is read as the result of the helper, so it must not be captured.
+        let process = ProcessContext::current()
+            .without_capture()
+            .enter(|| {
+                Process::run_with_options(
+                    program,
+                    arguments,
+                    ProcessOptions {
+                        cwd: Some(params.cwd.unwrap_or_else(|| self.root.to_owned())),
+                        display_output: false,
+                        ..Default::default()
+                    },
+                )
+            })
+            .map_err(|err| {
+                generic_error(&format!(
+                    "could not create process for \"{command}\" ({err})"
+                ))
+            })?;
 
         let mut stdout = process
             .stdout()
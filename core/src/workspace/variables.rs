@@ -41,6 +41,7 @@ pub fn load_variables_from_root(root: &Path, options: LoadVariablesOptions) -> R
     let deserialization_context = DeserializationContext {
         jpath: options.jpath,
         template_data: options.template_data,
+        cache: None,
     };
 
     if pathinfo.is_none() {
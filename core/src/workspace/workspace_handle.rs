@@ -55,6 +55,7 @@ impl WorkspaceHandle {
             DeserializationContext {
                 jpath: options.jpath,
                 template_data: options.template_data,
+                cache: None,
             },
         )
         .with_context(|| {
@@ -1,16 +1,21 @@
 import process from 'node:process'
 import { Socket, connect } from 'node:net'
 import { EOL } from 'node:os'
-import { ExecutorContext, executorFunctionSchema, projectSchema, valueSchema, workspaceSchema } from '@blaze-repo/node-devkit'
+import { createInterface } from 'node:readline'
+import { ExecutorContext, Value, executorFunctionSchema, projectSchema, valueSchema, workspaceSchema } from '@blaze-repo/node-devkit'
 import { z } from 'zod'
 
-const bridgeInputMessageSchema = z.object({
+const PROTOCOL_VERSION = 1
+
+const EXECUTOR_ERROR = -32000
+const METHOD_NOT_FOUND_ERROR = -32601
+
+const executeParamsSchema = z.object({
     executorParams: z.tuple([
         z.object({
             workspace: workspaceSchema,
             project: projectSchema,
-            target: z.string().min(1),
-            logger: z.string().min(1)
+            target: z.string().min(1)
         }),
         valueSchema
     ]),
@@ -19,64 +24,178 @@ const bridgeInputMessageSchema = z.object({
     })
 })
 
-type BridgeInputMessage = z.infer<typeof bridgeInputMessageSchema>
+const rpcErrorSchema = z.object({
+    code: z.number(),
+    message: z.string(),
+    data: valueSchema.optional()
+})
 
-type BridgeContext = ExecutorContext & {
-    logger: {
-        _drop: () => void
-    }
-}
+const rpcMessageSchema = z.object({
+    jsonrpc: z.literal('2.0'),
+    id: z.number().int().min(0).optional(),
+    method: z.string().optional(),
+    params: valueSchema.optional(),
+    result: valueSchema.optional(),
+    error: rpcErrorSchema.optional()
+})
+
+type RpcMessage = z.infer<typeof rpcMessageSchema>
 
-function dropContext(context: BridgeContext): void {
-    context.logger._drop()
+type RpcError = z.infer<typeof rpcErrorSchema>
+
+type ExecuteRequest = {
+    id: number
+    params: unknown
 }
 
-function connectPipe(path: string): Promise<Socket> {
-    return new Promise((resolve, reject) => {
-        const stream = connect(path)
-        const connectErrorListener = (err: Error) => reject(err)
-        
-        stream.once('error', connectErrorListener)
-        stream.once('ready', () => {
-            stream.removeListener('error', connectErrorListener)
-            resolve(stream)
+const cancelParamsSchema = z.object({
+    execution: z.number().int().min(0)
+})
+
+/**
+ * Connection to Blaze, using the bridge protocol (JSON-RPC messages over a local socket).
+ */
+class BridgeConnection {
+
+    private nextId = 0
+    private readonly pending = new Map<number, { resolve: (result: Value) => void, reject: (err: Error) => void }>()
+    private executeRequestListener: ((request: ExecuteRequest) => void) | null = null
+    private shutdownListener: (() => void) | null = null
+    /** IDs of canceled executions. */
+    readonly canceled = new Set<number>()
+
+    private constructor(private readonly socket: Socket) {
+        createInterface({ input: socket }).on('line', line => this.receive(line))
+        socket.on('close', () => {
+            for (const { reject } of this.pending.values())
+                reject(Error('connection with Blaze was closed'))
+            this.pending.clear()
+            this.shutdownListener?.()
         })
-    })
+    }
+
+    static connect(path: string): Promise<BridgeConnection> {
+        return new Promise((resolve, reject) => {
+            const socket = connect(path)
+            const connectErrorListener = (err: Error) => reject(err)
+
+            socket.once('error', connectErrorListener)
+            socket.once('ready', () => {
+                socket.removeListener('error', connectErrorListener)
+                socket.on('error', err => console.error(`bridge connection error (${err})`))
+                resolve(new BridgeConnection(socket))
+            })
+        })
+    }
+
+    private receive(line: string): void {
+        let message: RpcMessage
+        try {
+            message = rpcMessageSchema.parse(JSON.parse(line))
+        } catch (err) {
+            console.error(`could not parse message from Blaze: ${line} (${err})`)
+            return
+        }
+
+        const { id, method } = message
+
+        if (id !== undefined && method !== undefined) {
+            if (method === 'execute' && this.executeRequestListener) {
+                this.executeRequestListener({ id, params: message.params })
+            } else {
+                this.send({
+                    jsonrpc: '2.0',
+                    id,
+                    error: { code: METHOD_NOT_FOUND_ERROR, message: `unknown method ${method}` }
+                })
+            }
+        } else if (method === 'cancel') {
+            const parsed = cancelParamsSchema.safeParse(message.params)
+            if (parsed.success)
+                this.canceled.add(parsed.data.execution)
+        } else if (method === 'shutdown') {
+            this.shutdownListener?.()
+        } else if (id !== undefined) {
+            const pending = this.pending.get(id)
+            if (!pending)
+                return
+            this.pending.delete(id)
+            if (message.error)
+                pending.reject(Error(`request failed: ${message.error.message}`))
+            else
+                pending.resolve(message.result ?? null)
+        }
+    }
+
+    send(message: RpcMessage): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this.socket.write(JSON.stringify(message) + EOL, err => err ? reject(err) : resolve())
+        })
+    }
+
+    notify(method: string, params: Value): Promise<void> {
+        return this.send({ jsonrpc: '2.0', method, params })
+    }
+
+    request(method: string, params: Value): Promise<Value> {
+        const id = this.nextId++
+        return new Promise((resolve, reject) => {
+            this.pending.set(id, { resolve, reject })
+            this.send({ jsonrpc: '2.0', id, method, params }).catch(err => {
+                this.pending.delete(id)
+                reject(err)
+            })
+        })
+    }
+
+    respond(id: number, result: { result: Value } | { error: RpcError }): Promise<void> {
+        return this.send({ jsonrpc: '2.0', id, ...result })
+    }
+
+    /**
+     * Handle `execute` requests until Blaze asks for shutdown (or closes the connection).
+     */
+    serve(listener: (request: ExecuteRequest) => void): Promise<void> {
+        this.executeRequestListener = listener
+        return new Promise(resolve => {
+            this.shutdownListener = () => {
+                this.executeRequestListener = null
+                this.shutdownListener = null
+                resolve()
+            }
+        })
+    }
+
+    close(): void {
+        this.socket.end()
+    }
 }
 
-async function convertContext(inputContext: BridgeInputMessage['executorParams'][0]): Promise<BridgeContext> {
-    const logStream = await connectPipe(inputContext.logger)
-    
-    const logger: BridgeContext['logger'] = {
-        _drop() {
-            logStream.end()
-        },
+function createContext(
+    connection: BridgeConnection,
+    execution: number,
+    inputContext: z.infer<typeof executeParamsSchema>['executorParams'][0]
+): ExecutorContext {
+
+    const logger: ExecutorContext['logger'] = {
         log(message, level){
-            logStream.write(
-                JSON.stringify({
-                    level,
-                    message
-                }) + EOL, 
-                err => {
-                    if (!err)
-                        return
-                    // switch back to Node console in case of error
-                    switch(level){
-                        case 'Info':
-                            console.log(message)
-                            break
-                        case 'Error':
-                            console.error(message)
-                            break
-                        case 'Debug':
-                            console.debug(message)
-                            break
-                        case 'Warn':
-                            console.warn(message)
-                            break
-                    }
+            connection.notify('log', { message, level, execution }).catch(() => {
+                // switch back to Node console in case of error
+                switch(level){
+                    case 'Info':
+                        console.log(message)
+                        break
+                    case 'Error':
+                        console.error(message)
+                        break
+                    case 'Debug':
+                        console.debug(message)
+                        break
+                    case 'Warn':
+                        console.warn(message)
+                        break
                 }
-            )
+            })
         },
         debug(message) {
             this.log(message, 'Debug')
@@ -92,13 +211,22 @@ async function convertContext(inputContext: BridgeInputMessage['executorParams']
         },
     }
 
-    logStream.on('error', err => {
-        console.error(`log channel error (${err})`)
-    })
-
-    const context = {
+    const context: ExecutorContext = {
         ...inputContext,
-        logger
+        logger,
+        isCanceled() {
+            return connection.canceled.has(execution)
+        },
+        progress(progress) {
+            connection.notify('progress', { ...progress, execution } as Value).catch(err => {
+                console.error(`could not report progress (${err})`)
+            })
+        },
+        async resolvePath(path) {
+            const { path: resolved } = await z.object({ path: z.string() })
+                .parseAsync(await connection.request('workspace/resolvePath', { path, execution }))
+            return resolved
+        }
     }
 
     Object.freeze(context)
@@ -106,53 +234,84 @@ async function convertContext(inputContext: BridgeInputMessage['executorParams']
     return context
 }
 
-let convertedContext: null | BridgeContext = null
-
-const start = async (): Promise<void> => {
+/**
+ * Run the executor requested by an `execute` request, and send its result to Blaze.
+ * Resolves to whether the execution was successful.
+ */
+async function execute(connection: BridgeConnection, { id, params }: ExecuteRequest): Promise<boolean> {
+    let context: ExecutorContext | null = null
 
-    const inputMessage: unknown = JSON.parse(process.argv[process.argv.length - 1])
+    try {
+        const {
+            metadata: { module },
+            executorParams: [inputContext, options]
+        } = await executeParamsSchema.parseAsync(params)
 
-    const {
-        metadata: { module },
-        executorParams: [context, options]
-    } = await bridgeInputMessageSchema.parseAsync(inputMessage)
+        context = createContext(connection, id, inputContext)
 
-    let defaultExport: unknown
-    try {
-        defaultExport = (await import(`file://${module}`)).default
-    } catch (err) {
-        throw Error(`Executor module could not be found at ${module}, please check the value of \`blaze.path\` in your package.json file (${err})`)
-    }
+        // persistent workers run executions of several projects, which are never running at the same time.
+        process.chdir(inputContext.project.root)
 
-    convertedContext = await convertContext(context)
+        let defaultExport: unknown
+        try {
+            defaultExport = (await import(`file://${module}`)).default
+        } catch (err) {
+            throw Error(`Executor module could not be found at ${module}, please check the value of \`blaze.path\` in your package.json file (${err})`)
+        }
 
-    try {
         const executor = await executorFunctionSchema.parseAsync(defaultExport)
-        await executor(
-            convertedContext,
-            options
-        )
+        const output = await executor(context, options)
+        await connection.respond(id, { result: { output: (output as Value | undefined) ?? null } })
+        return true
     } catch(err){
-        if (err instanceof Error){
-            convertedContext.logger.error(err.message)
-            convertedContext.logger.error(err.stack ?? 'no stack')
-        } else {
-            convertedContext.logger.error(`${err}`)
-        }
-        process.exit(1)
+        const message = err instanceof Error ? err.message : `${err}`
+        if (err instanceof Error && context)
+            context.logger.error(err.stack ?? message)
+        else if (err instanceof Error)
+            console.error(err.stack ?? message)
+        await connection.respond(id, {
+            error: {
+                code: EXECUTOR_ERROR,
+                message,
+                data: { causes: [message] }
+            }
+        })
+        return false
+    } finally {
+        connection.canceled.delete(id)
     }
+}
+
+let connection: BridgeConnection | null = null
+
+const start = async (): Promise<void> => {
 
-    process.exit(0)
+    connection = await BridgeConnection.connect(process.argv[process.argv.length - 1])
+
+    const executions: Promise<boolean>[] = []
+
+    const served = connection.serve(request => {
+        if (connection)
+            executions.push(execute(connection, request))
+    })
+
+    await connection.request('initialize', { protocolVersion: PROTOCOL_VERSION })
+
+    // executions run concurrently, until Blaze asks for shutdown or closes the connection.
+    await served
+
+    const results = await Promise.allSettled(executions)
+
+    if (results.some(result => result.status === 'rejected' || !result.value))
+        process.exitCode = 1
 }
 
 start()
     .catch(err => {
-        const logError = (message: string) => convertedContext?.logger.error(message) ?? console.error(message)
-        logError(err instanceof Error && err.stack ? err.stack : `${err}`)
-        process.exit(1)
+        console.error(err instanceof Error && err.stack ? err.stack : `${err}`)
+        process.exitCode = 1
     })
     .finally(() => {
-        if (convertedContext) {
-            dropContext(convertedContext)
-        }
+        connection?.close()
+        process.exit()
     })
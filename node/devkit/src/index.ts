@@ -76,7 +76,9 @@ const rebuildStrategySchema = z.union([
 
 const executorKindSchema = z.union([
     z.literal('Rust'),
-    z.literal('Node')
+    z.literal('Node'),
+    z.literal('Python'),
+    z.literal('Wasm')
 ])
 
 const gitOptionsProperties = {
@@ -198,7 +200,16 @@ const executorContextSchema = z.object({
     logger: loggerSchema,
     workspace: workspaceSchema,
     project: projectSchema,
-    target: z.string().min(1)
+    target: z.string().min(1),
+    isCanceled: z.function().returns(z.boolean()),
+    progress: z.function()
+        .args(z.object({
+            message: z.string().optional(),
+            completed: z.number().int().min(0).optional(),
+            total: z.number().int().min(0).optional()
+        }))
+        .returns(z.void()),
+    resolvePath: z.function().args(z.string()).returns(z.promise(z.string()))
 })
 
 export type ExecutorContext = z.infer<typeof executorContextSchema>
@@ -209,7 +220,8 @@ export const executorFunctionSchema = z.function()
         valueSchema
     )
     .returns(z.union([
-        z.promise(z.void()),
+        z.promise(z.union([valueSchema, z.void()])),
+        valueSchema,
         z.void()
     ]))
 
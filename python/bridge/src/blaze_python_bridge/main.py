@@ -0,0 +1,290 @@
+"""
+Blaze Python executors bridge.
+
+This script is sent by Blaze to the executor virtual environment interpreter through stdin.
+It connects to Blaze, using the bridge protocol (JSON-RPC messages over a local socket), and runs executor functions.
+"""
+
+import asyncio
+import importlib
+import inspect
+import json
+import os
+import queue
+import socket
+import sys
+import threading
+import traceback
+
+PROTOCOL_VERSION = 1
+
+EXECUTOR_ERROR = -32000
+METHOD_NOT_FOUND_ERROR = -32601
+INTERNAL_ERROR = -32603
+
+LOG_LEVELS = ("Debug", "Info", "Warn", "Error")
+
+
+class BridgeConnection:
+    """
+    Connection to Blaze, using the bridge protocol.
+    """
+
+    def __init__(self, path):
+        if os.name == "nt":
+            raise RuntimeError("python executors are not supported on Windows yet")
+        self._socket = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        self._socket.connect(path)
+        self._reader = self._socket.makefile("r", encoding="utf-8", newline="\n")
+        self._send_lock = threading.Lock()
+        self._lock = threading.Lock()
+        self._next_id = 0
+        self._pending = {}
+        # cancellation flags of running executions, indexed by their `execute` request ID.
+        self.canceled = {}
+        # `execute` requests, `None` is received when Blaze asks for shutdown or closes the connection.
+        self.incoming = queue.Queue()
+        threading.Thread(target=self._receive, daemon=True).start()
+
+    def _receive(self):
+        try:
+            for line in self._reader:
+                try:
+                    message = json.loads(line)
+                except ValueError as err:
+                    print(f"could not parse message from Blaze: {line} ({err})", file=sys.stderr)
+                    continue
+                self._dispatch(message)
+        except OSError as err:
+            print(f"bridge connection error ({err})", file=sys.stderr)
+        finally:
+            with self._lock:
+                pending = list(self._pending.values())
+                self._pending.clear()
+            for slot in pending:
+                slot.put({"error": {"code": INTERNAL_ERROR, "message": "connection with Blaze was closed"}})
+            self.incoming.put(None)
+
+    def _dispatch(self, message):
+        id = message.get("id")
+        method = message.get("method")
+
+        if id is not None and method is not None:
+            if method == "execute":
+                with self._lock:
+                    self.canceled[id] = threading.Event()
+                self.incoming.put((id, message.get("params")))
+            else:
+                self.send({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {"code": METHOD_NOT_FOUND_ERROR, "message": f"unknown method {method}"},
+                })
+        elif method == "cancel":
+            execution = (message.get("params") or {}).get("execution")
+            with self._lock:
+                canceled = self.canceled.get(execution)
+            if canceled is not None:
+                canceled.set()
+        elif method == "shutdown":
+            self.incoming.put(None)
+        elif id is not None:
+            with self._lock:
+                slot = self._pending.pop(id, None)
+            if slot is not None:
+                slot.put(message)
+
+    def get_canceled(self, execution):
+        with self._lock:
+            return self.canceled.get(execution) or threading.Event()
+
+    def release(self, execution):
+        with self._lock:
+            self.canceled.pop(execution, None)
+
+    def send(self, message):
+        data = (json.dumps(message) + "\n").encode("utf-8")
+        with self._send_lock:
+            self._socket.sendall(data)
+
+    def notify(self, method, params):
+        self.send({"jsonrpc": "2.0", "method": method, "params": params})
+
+    def request(self, method, params):
+        slot = queue.Queue(maxsize=1)
+        with self._lock:
+            id = self._next_id
+            self._next_id += 1
+            self._pending[id] = slot
+        self.send({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+        response = slot.get()
+        if "error" in response:
+            raise RuntimeError(f"{method} request failed: {response['error'].get('message')}")
+        return response.get("result")
+
+    def respond(self, id, **result):
+        self.send({"jsonrpc": "2.0", "id": id, **result})
+
+    def close(self):
+        try:
+            self._socket.shutdown(socket.SHUT_RDWR)
+        except OSError:
+            pass
+        self._socket.close()
+
+
+class BridgeLogger:
+    """
+    Logger which sends entries to Blaze logging system.
+    """
+
+    def __init__(self, connection, execution):
+        self._connection = connection
+        self._execution = execution
+
+    def log(self, message, level):
+        if level not in LOG_LEVELS:
+            raise ValueError(f"invalid log level {level!r}")
+        try:
+            self._connection.notify("log", {"message": str(message), "level": level, "execution": self._execution})
+        except OSError:
+            # switch back to standard streams in case of error
+            print(message, file=sys.stderr if level in ("Warn", "Error") else sys.stdout)
+
+    def debug(self, message):
+        self.log(message, "Debug")
+
+    def info(self, message):
+        self.log(message, "Info")
+
+    def warn(self, message):
+        self.log(message, "Warn")
+
+    def error(self, message):
+        self.log(message, "Error")
+
+
+class BridgeContext:
+    """
+    Context passed to executor functions, see `blaze_devkit.ExecutorContext`.
+    """
+
+    def __init__(self, connection, execution, canceled, input_context):
+        self._connection = connection
+        self._execution = execution
+        self._canceled = canceled
+        self.workspace = input_context["workspace"]
+        self.project = input_context["project"]
+        self.target = input_context["target"]
+        self.logger = BridgeLogger(connection, execution)
+
+    def is_canceled(self):
+        return self._canceled.is_set()
+
+    def progress(self, message=None, completed=None, total=None):
+        params = {"execution": self._execution}
+        for key, value in (("message", message), ("completed", completed), ("total", total)):
+            if value is not None:
+                params[key] = value
+        try:
+            self._connection.notify("progress", params)
+        except OSError as err:
+            print(f"could not report progress ({err})", file=sys.stderr)
+
+    def resolve_path(self, path):
+        result = self._connection.request("workspace/resolvePath", {"path": os.fspath(path), "execution": self._execution})
+        return result["path"]
+
+
+def get_causes(error):
+    causes = []
+    while error is not None and len(causes) < 32:
+        causes.append(str(error) or type(error).__name__)
+        error = error.__cause__ or error.__context__
+    return causes
+
+
+def load_executor(module, function):
+    try:
+        executor = getattr(importlib.import_module(module), function)
+    except (ImportError, AttributeError) as err:
+        raise RuntimeError(
+            f"executor function {module}.{function} could not be found, please check the `tool.blaze` table in your pyproject.toml file"
+        ) from err
+    if not callable(executor):
+        raise RuntimeError(f"{module}.{function} is not a function")
+    return executor
+
+
+def execute(connection, id, params):
+    """
+    Run the executor requested by an `execute` request, and send its result to Blaze.
+    Returns whether the execution was successful.
+    """
+    canceled = connection.get_canceled(id)
+
+    context = None
+    try:
+        metadata = params["metadata"]
+        input_context, options = params["executorParams"]
+        context = BridgeContext(connection, id, canceled, input_context)
+
+        # persistent workers run executions of several projects, which are never running at the same time.
+        os.chdir(context.project["root"])
+
+        executor = load_executor(metadata["module"], metadata["function"])
+        output = executor(context, options)
+        if inspect.isawaitable(output):
+            output = asyncio.run(output)
+
+        connection.respond(id, result={"output": output})
+        return True
+    except Exception as err:
+        message = str(err) or type(err).__name__
+        if context is not None:
+            context.logger.error("".join(traceback.format_exception(type(err), err, err.__traceback__)))
+        else:
+            traceback.print_exc()
+        try:
+            connection.respond(id, error={"code": EXECUTOR_ERROR, "message": message, "data": {"causes": get_causes(err)}})
+        except OSError as send_err:
+            print(f"could not send execution result: {send_err}", file=sys.stderr)
+        return False
+    finally:
+        connection.release(id)
+
+
+def main():
+    connection = BridgeConnection(sys.argv[-1])
+
+    connection.request("initialize", {"protocolVersion": PROTOCOL_VERSION})
+
+    results = []
+    executions = []
+
+    def run(id, params):
+        results.append(execute(connection, id, params))
+
+    # executions run concurrently, until Blaze asks for shutdown or closes the connection.
+    while True:
+        request = connection.incoming.get()
+        if request is None:
+            break
+        thread = threading.Thread(target=run, args=request)
+        thread.start()
+        executions.append(thread)
+
+    for thread in executions:
+        thread.join()
+
+    connection.close()
+
+    # executions which did not report a result were interrupted (for example with `sys.exit`).
+    failures = len(executions) - len([success for success in results if success])
+    if failures > 0:
+        print(f"{failures} execution(s) failed.", file=sys.stderr)
+        sys.exit(1)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,115 @@
+"""
+Type definitions for writing Blaze executors in Python.
+"""
+
+from typing import Any, Awaitable, Callable, Dict, List, Literal, Optional, Protocol, TypedDict, Union
+
+__all__ = [
+    "Value",
+    "LogLevel",
+    "ConfigurationFileFormat",
+    "ProjectRef",
+    "WorkspaceSettings",
+    "Workspace",
+    "Project",
+    "Logger",
+    "ExecutorContext",
+    "ExecutorResult",
+    "Executor",
+]
+
+Value = Union[None, bool, int, float, str, List["Value"], Dict[str, "Value"]]
+
+LogLevel = Literal["Debug", "Info", "Warn", "Error"]
+
+ConfigurationFileFormat = Literal["Json", "Jsonnet", "Yaml"]
+
+
+class _ProjectRefBase(TypedDict):
+    path: str
+    tags: List[str]
+
+
+class ProjectRef(_ProjectRefBase, total=False):
+    description: str
+
+
+class WorkspaceSettings(TypedDict, total=False):
+    parallelism: Union[int, Literal["All", "Infinite", "None"]]
+    defaultSelector: Any
+    selectors: Dict[str, Any]
+    logLevel: LogLevel
+
+
+class Workspace(TypedDict):
+    root: str
+    configurationFileFormat: ConfigurationFileFormat
+    configurationFilePath: str
+    name: str
+    projects: Dict[str, ProjectRef]
+    settings: WorkspaceSettings
+
+
+class Project(TypedDict):
+    root: str
+    configurationFileFormat: ConfigurationFileFormat
+    configurationFilePath: str
+    name: str
+    targets: Dict[str, Dict[str, Any]]
+
+
+class Logger(Protocol):
+    """
+    Writes messages through Blaze logging system.
+    """
+
+    def log(self, message: str, level: LogLevel) -> None: ...
+
+    def debug(self, message: str) -> None: ...
+
+    def info(self, message: str) -> None: ...
+
+    def warn(self, message: str) -> None: ...
+
+    def error(self, message: str) -> None: ...
+
+
+class ExecutorContext(Protocol):
+    """
+    Information about the current target execution, and functions for communicating with Blaze.
+    """
+
+    workspace: Workspace
+    project: Project
+    target: str
+    logger: Logger
+
+    def is_canceled(self) -> bool:
+        """
+        Returns `True` when the target execution was canceled.
+        Long running executors should check it regularly and return early.
+        """
+        ...
+
+    def progress(
+        self,
+        message: Optional[str] = None,
+        completed: Optional[int] = None,
+        total: Optional[int] = None,
+    ) -> None:
+        """
+        Report the progress of the target execution.
+        """
+        ...
+
+    def resolve_path(self, path: str) -> str:
+        """
+        Resolve a path from the project root.
+        An error is raised if the resolved path is outside of the workspace.
+        """
+        ...
+
+
+ExecutorResult = Union[Value, Awaitable[Value]]
+
+Executor = Callable[[ExecutorContext, Value], ExecutorResult]
@@ -1,22 +1,34 @@
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use blaze_common::{
-    error::Result,
+    bridge::{
+        CancelParams, ExecuteResult, ExecutorErrorData, InitializeParams, LogParams,
+        ProgressParams, ResolvePathParams, ResolvePathResult, RpcError, RpcMessage, RpcMessageKind,
+        CANCEL_METHOD, EXECUTE_METHOD, EXECUTOR_ERROR, INITIALIZE_METHOD, INTERNAL_ERROR,
+        LOG_METHOD, METHOD_NOT_FOUND_ERROR, PROGRESS_METHOD, PROTOCOL_VERSION, RESOLVE_PATH_METHOD,
+        SHUTDOWN_METHOD,
+    },
+    error::{Error, Result},
     logger::{LogLevel, Logger, LoggingStrategy},
     project::Project,
-    value::Value,
+    value::{to_value, Value},
     workspace::Workspace,
 };
-use blaze_devkit::{ExecutorContext, ExecutorFn};
+use blaze_devkit::{check_abi_version, ExecutorContext, ExecutorFn, ExecutorHost};
 use interprocess::local_socket::{
-    traits::Stream as StreamTrait, GenericFilePath, Stream, ToFsName,
+    traits::Stream as StreamTrait, GenericFilePath, RecvHalf, SendHalf, Stream, ToFsName,
 };
 use libloading::{Library, Symbol};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
-    io::Write,
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
     panic::catch_unwind,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex, MutexGuard,
+    },
 };
 
 #[derive(Deserialize)]
@@ -24,12 +36,11 @@ struct BridgeContext {
     workspace: Workspace,
     project: Project,
     target: String,
-    logger: PathBuf,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct BridgeInputMessage {
+struct ExecuteParams {
     metadata: RustExecutorMetadata,
     executor_params: (BridgeContext, Value),
 }
@@ -41,95 +52,366 @@ struct RustExecutorMetadata {
     exported_symbol_name: String,
 }
 
-pub struct BridgedLoggingStrategy {
-    connection: Arc<Mutex<Stream>>,
+type RpcResult = std::result::Result<Value, RpcError>;
+
+/// Message received from Blaze, which must be handled by the main thread.
+enum Incoming {
+    Execute { id: u64, params: Value },
+    Shutdown,
 }
 
-impl BridgedLoggingStrategy {
-    pub fn connect(path: &Path) -> Result<Self> {
-        Ok(Self {
-            connection: Arc::new(Mutex::new(Stream::connect(ToFsName::to_fs_name::<
-                GenericFilePath,
-            >(path)?)?)),
-        })
+/// Connection to Blaze, using the bridge protocol.
+struct BridgeConnection {
+    sender: Mutex<SendHalf>,
+    pending: Mutex<HashMap<u64, Sender<RpcResult>>>,
+    next_id: AtomicU64,
+    /// Cancellation flags of running executions, indexed by their `execute` request ID.
+    canceled: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+}
+
+impl BridgeConnection {
+    /// Connect to the Blaze socket and start receiving messages.
+    /// Messages which must be handled by the main thread are forwarded to the returned channel.
+    fn connect(path: &Path) -> Result<(Arc<Self>, Receiver<Incoming>)> {
+        let (receiver, sender) =
+            Stream::connect(ToFsName::to_fs_name::<GenericFilePath>(path)?)?.split();
+
+        let connection = Arc::new(Self {
+            sender: Mutex::new(sender),
+            pending: Mutex::default(),
+            next_id: AtomicU64::default(),
+            canceled: Mutex::default(),
+        });
+
+        let (incoming_sender, incoming_receiver) = channel();
+
+        let receiving_connection = connection.clone();
+        std::thread::spawn(move || {
+            if let Err(err) = receiving_connection.receive(receiver, incoming_sender) {
+                receiving_connection.log_error(format!("bridge connection error: {err:?}"));
+            }
+        });
+
+        Ok((connection, incoming_receiver))
+    }
+
+    fn receive(&self, receiver: RecvHalf, incoming: Sender<Incoming>) -> Result<()> {
+        for line in BufReader::new(receiver).lines() {
+            let line = line.context("could not read message from Blaze.")?;
+            let message = serde_json::from_str::<RpcMessage>(&line)
+                .with_context(|| format!("could not parse message from Blaze: {line}"))?;
+
+            match message.into_kind() {
+                RpcMessageKind::Request { id, method, params } if method == EXECUTE_METHOD => {
+                    self.lock_canceled()?.insert(id, Arc::default());
+                    let _ = incoming.send(Incoming::Execute { id, params });
+                }
+                RpcMessageKind::Request { id, method, .. } => {
+                    self.send(&RpcMessage::response(
+                        id,
+                        Err(RpcError::new(
+                            METHOD_NOT_FOUND_ERROR,
+                            format!("unknown method {method}"),
+                        )),
+                    ))?;
+                }
+                RpcMessageKind::Notification { method, params } if method == CANCEL_METHOD => {
+                    let CancelParams { execution } = CancelParams::deserialize(params)?;
+                    if let Some(canceled) = self.lock_canceled()?.get(&execution) {
+                        canceled.store(true, Ordering::SeqCst);
+                    }
+                }
+                RpcMessageKind::Notification { method, .. } if method == SHUTDOWN_METHOD => {
+                    let _ = incoming.send(Incoming::Shutdown);
+                }
+                RpcMessageKind::Response { id, result } => {
+                    if let Some(pending) = self.lock_pending()?.remove(&id) {
+                        let _ = pending.send(result);
+                    }
+                }
+                RpcMessageKind::Notification { .. } | RpcMessageKind::Invalid => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&self, message: &RpcMessage) -> Result<()> {
+        let line = serde_json::to_string(message).context("could not serialize message.")?;
+        let mut sender = self
+            .sender
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on bridge sender)."))?;
+        writeln!(sender, "{line}").context("could not send message to Blaze.")
+    }
+
+    fn notify<T: Serialize>(&self, method: &str, params: T) -> Result<()> {
+        self.send(&RpcMessage::notification(method, to_value(params)?))
+    }
+
+    /// Report an error of the bridge itself, which is not related to a specific execution.
+    /// Standard error is not used, since it is not displayed when the bridge runs as a persistent worker.
+    fn log_error(&self, message: String) {
+        let _ = self.notify(
+            LOG_METHOD,
+            LogParams {
+                message,
+                level: LogLevel::Error,
+                execution: None,
+            },
+        );
+    }
+
+    fn request<T: Serialize, R: DeserializeOwned>(&self, method: &str, params: T) -> Result<R> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = channel();
+        self.lock_pending()?.insert(id, sender);
+
+        self.send(&RpcMessage::request(id, method, to_value(params)?))?;
+
+        let result = receiver
+            .recv()
+            .with_context(|| format!("no response received for {method} request."))?
+            .map_err(|error| anyhow!("{method} request failed: {}.", error.message))?;
+
+        Ok(R::deserialize(result)?)
+    }
+
+    fn lock_canceled(&self) -> Result<MutexGuard<'_, HashMap<u64, Arc<AtomicBool>>>> {
+        self.canceled
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on canceled executions)."))
+    }
+
+    fn lock_pending(&self) -> Result<MutexGuard<'_, HashMap<u64, Sender<RpcResult>>>> {
+        self.pending
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on pending requests)."))
     }
 }
 
-#[derive(Serialize)]
-pub struct Log<'a> {
-    message: &'a str,
-    level: LogLevel,
+pub struct BridgedLoggingStrategy {
+    connection: Arc<BridgeConnection>,
+    execution: u64,
 }
 
 impl LoggingStrategy for BridgedLoggingStrategy {
-    fn log(&self, message: &str, level: blaze_common::logger::LogLevel)
+    fn log(&self, message: &str, level: LogLevel)
     where
         Self: Sized,
     {
-        let log_entry = Log { message, level };
-        let log_entry_json = serde_json::to_string(&log_entry).unwrap();
-        let _ = writeln!(self.connection.lock().unwrap(), "{}", log_entry_json);
+        let _ = self.connection.notify(
+            LOG_METHOD,
+            LogParams {
+                message: message.to_owned(),
+                level,
+                execution: Some(self.execution),
+            },
+        );
+    }
+}
+
+struct BridgeHost {
+    connection: Arc<BridgeConnection>,
+    execution: u64,
+    canceled: Arc<AtomicBool>,
+}
+
+impl ExecutorHost for BridgeHost {
+    fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::SeqCst)
+    }
+
+    fn progress(&self, progress: ProgressParams) {
+        let _ = self.connection.notify(
+            PROGRESS_METHOD,
+            ProgressParams {
+                execution: Some(self.execution),
+                ..progress
+            },
+        );
+    }
+
+    fn resolve_path(
+        &self,
+        path: &Path,
+    ) -> std::result::Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .request::<_, ResolvePathResult>(
+                RESOLVE_PATH_METHOD,
+                ResolvePathParams {
+                    path: path.to_owned(),
+                    execution: Some(self.execution),
+                },
+            )?
+            .path)
+    }
+}
+
+/// Executor libraries, which are loaded once and kept until the bridge process exits.
+#[derive(Default)]
+struct Libraries(Mutex<HashMap<PathBuf, Arc<Library>>>);
+
+impl Libraries {
+    fn get(&self, path: &Path) -> Result<Arc<Library>> {
+        let mut libraries = self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on executor libraries)."))?;
+        if let Some(library) = libraries.get(path) {
+            return Ok(library.clone());
+        }
+        let library = unsafe {
+            Library::new(path)
+                .with_context(|| format!("could not load library at {}.", path.display()))?
+        };
+        check_abi_version(&library)
+            .with_context(|| format!("cannot use executor library at {}.", path.display()))?;
+        let library = Arc::new(library);
+        libraries.insert(path.to_owned(), library.clone());
+        Ok(library)
     }
 }
 
 fn main() -> Result<()> {
-    let bridge_message_value = &std::env::args()
-        .nth(1)
-        .ok_or_else(|| anyhow!("bridge input message not found in args"))?;
+    let socket_path = std::env::args()
+        .last()
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("bridge socket path not found in args"))?;
 
-    let bridge_message: BridgeInputMessage = serde_json::from_str(bridge_message_value)
-        .context("could not parse bridge input message.")?;
+    let (connection, incoming) = BridgeConnection::connect(&socket_path)
+        .with_context(|| format!("could not connect to Blaze ({}).", socket_path.display()))?;
 
-    let metadata = bridge_message.metadata;
-    let (context, options) = bridge_message.executor_params;
+    connection
+        .request::<_, Value>(
+            INITIALIZE_METHOD,
+            InitializeParams {
+                protocol_version: PROTOCOL_VERSION,
+            },
+        )
+        .context("bridge initialization failed.")?;
 
-    let logger = Logger::new(
-        BridgedLoggingStrategy::connect(&context.logger).with_context(|| {
-            format!(
-                "could not connect to logger ({}).",
-                context.logger.display()
-            )
-        })?,
-    );
+    let libraries = Libraries::default();
+
+    // executions run concurrently, until Blaze asks for shutdown or closes the connection.
+    let failures = std::thread::scope(|scope| {
+        let mut executions = vec![];
+
+        while let Ok(Incoming::Execute { id, params }) = incoming.recv() {
+            let connection = connection.clone();
+            let libraries = &libraries;
+            executions.push(scope.spawn(move || run_execution(&connection, libraries, id, params)));
+        }
 
-    let library: Library;
-    let executor: Symbol<ExecutorFn>;
+        executions
+            .into_iter()
+            .map(|execution| execution.join().unwrap_or(false))
+            .filter(|success| !success)
+            .count()
+    });
+
+    if failures > 0 {
+        bail!("{failures} execution(s) failed.")
+    }
 
-    unsafe {
-        library = Library::new(&metadata.library_path).with_context(|| {
-            format!(
-                "could not load library at {}.",
-                metadata.library_path.display()
+    Ok(())
+}
+
+/// Run the executor requested by an `execute` request, and send its result to Blaze.
+/// Returns whether the execution was successful.
+fn run_execution(
+    connection: &Arc<BridgeConnection>,
+    libraries: &Libraries,
+    id: u64,
+    params: Value,
+) -> bool {
+    let logger = Logger::new(BridgedLoggingStrategy {
+        connection: connection.clone(),
+        execution: id,
+    });
+
+    let result = connection
+        .lock_canceled()
+        .map(|canceled| canceled.get(&id).cloned().unwrap_or_default())
+        .and_then(|canceled| {
+            let host = BridgeHost {
+                connection: connection.clone(),
+                execution: id,
+                canceled,
+            };
+            let params = ExecuteParams::deserialize(params)
+                .context("could not parse execute request params.")?;
+            let (context, options) = params.executor_params;
+            // persistent workers run executions of several projects, which are never running at the same time.
+            std::env::set_current_dir(context.project.root()).with_context(|| {
+                format!(
+                    "could not change working directory to {}.",
+                    context.project.root().display()
+                )
+            })?;
+            load_and_execute(
+                libraries,
+                &params.metadata,
+                ExecutorContext {
+                    project: &context.project,
+                    workspace: &context.workspace,
+                    target: &context.target,
+                    logger: &logger,
+                    host: &host,
+                },
+                options,
             )
-        })?;
-        executor = library
+        });
+
+    if let Err(err) = connection
+        .lock_canceled()
+        .map(|mut canceled| canceled.remove(&id))
+    {
+        logger.error(format!("{err:?}"));
+    }
+
+    let response = match &result {
+        Ok(output) => to_value(ExecuteResult {
+            output: output.clone(),
+        })
+        .map_err(|err| RpcError::new(INTERNAL_ERROR, err.to_string())),
+        Err(error) => {
+            logger.error(format!("executor error: {error:?}."));
+            Err(executor_error(error))
+        }
+    };
+
+    if let Err(err) = connection.send(&RpcMessage::response(id, response)) {
+        logger.error(format!("could not send execution result: {err:?}"));
+        return false;
+    }
+
+    result.is_ok()
+}
+
+fn load_and_execute(
+    libraries: &Libraries,
+    metadata: &RustExecutorMetadata,
+    context: ExecutorContext,
+    options: Value,
+) -> Result<Value> {
+    let library = libraries.get(&metadata.library_path)?;
+
+    let executor: Symbol<ExecutorFn> = unsafe {
+        library
             .get::<ExecutorFn>(metadata.exported_symbol_name.as_bytes())
             .with_context(|| {
                 format!(
                     "could not load executor exported function \"{}\".",
                     metadata.exported_symbol_name
                 )
-            })?;
-    }
+            })?
+    };
 
-    if let Err(error) = execute(
-        *executor,
-        ExecutorContext {
-            project: &context.project,
-            workspace: &context.workspace,
-            target: &context.target,
-            logger: &logger,
-        },
-        options,
-    ) {
-        logger.error(format!("executor error: {error:?}."));
-        return Err(error);
-    }
-
-    Ok(())
+    execute(*executor, context, options)
 }
 
-fn execute(executor: ExecutorFn, context: ExecutorContext, options: Value) -> Result<()> {
+fn execute(executor: ExecutorFn, context: ExecutorContext, options: Value) -> Result<Value> {
     catch_unwind(|| executor(context, options))
         .map_err(|panic_error| {
             if panic_error.is::<String>() {
@@ -148,3 +430,14 @@ fn execute(executor: ExecutorFn, context: ExecutorContext, options: Value) -> Re
         })?
         .map_err(|executor_error| anyhow!(executor_error))
 }
+
+/// Convert an executor error into a structured error for Blaze.
+fn executor_error(error: &Error) -> RpcError {
+    RpcError {
+        data: to_value(ExecutorErrorData {
+            causes: error.chain().map(|cause| cause.to_string()).collect(),
+        })
+        .ok(),
+        ..RpcError::new(EXECUTOR_ERROR, error.to_string())
+    }
+}
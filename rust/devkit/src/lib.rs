@@ -1,17 +1,87 @@
+use blaze_common::bridge::ProgressParams;
 use blaze_common::logger::Logger;
 use blaze_common::project::Project;
 use blaze_common::workspace::Workspace;
 use std::error::Error;
+use std::path::{Path, PathBuf};
 use value::Value;
 
+/// Version of the interface between Blaze and executor libraries.
+/// It must be bumped whenever [`ExecutorContext`], [`ExecutorHost`] or [`ExecutorFn`] change, since libraries built against another version cannot be called safely.
+pub const ABI_VERSION: u32 = 2;
+
+/// Name of the symbol holding the ABI version in executor libraries.
+pub const ABI_VERSION_SYMBOL: &str = "BLAZE_DEVKIT_ABI_VERSION";
+
+/// Exported by every executor library, so that Blaze can check it before calling executor functions.
+#[no_mangle]
+pub static BLAZE_DEVKIT_ABI_VERSION: u32 = ABI_VERSION;
+
+/// Check that a loaded executor library was built against a devkit whose ABI matches this one.
+#[cfg(feature = "loader")]
+pub fn check_abi_version(library: &libloading::Library) -> blaze_common::error::Result<()> {
+    use blaze_common::error::Error;
+
+    // SAFETY: the symbol is declared as a `u32` static above, in every library built with this devkit.
+    let version = unsafe {
+        library
+            .get::<*const u32>(ABI_VERSION_SYMBOL.as_bytes())
+            .map(|symbol| **symbol)
+            .map_err(|_| {
+                Error::msg("library was built with a version of blaze-devkit which is too old, it must be rebuilt")
+            })?
+    };
+    if version != ABI_VERSION {
+        return Err(Error::msg(format!("library was built with an incompatible version of blaze-devkit (ABI version {version}, expected {ABI_VERSION}), it must be rebuilt")));
+    }
+    Ok(())
+}
+
 pub struct ExecutorContext<'a> {
     pub workspace: &'a Workspace,
     pub project: &'a Project,
     pub target: &'a str,
     pub logger: &'a Logger,
+    pub host: &'a dyn ExecutorHost,
+}
+
+impl ExecutorContext<'_> {
+    /// Whether the target execution was canceled. The executor should return as soon as possible when it is the case.
+    pub fn is_canceled(&self) -> bool {
+        self.host.is_canceled()
+    }
+
+    /// Report the progress of the target execution.
+    pub fn progress(&self, completed: Option<u64>, total: Option<u64>, message: Option<&str>) {
+        self.host.progress(ProgressParams {
+            message: message.map(str::to_owned),
+            completed,
+            total,
+            ..Default::default()
+        })
+    }
+
+    /// Resolve a path from the project root. An error is returned if the path is outside of the workspace.
+    pub fn resolve_path<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+        self.host.resolve_path(path.as_ref())
+    }
+}
+
+/// Communication with Blaze while an executor is running.
+pub trait ExecutorHost: Send + Sync {
+    fn is_canceled(&self) -> bool;
+
+    fn progress(&self, progress: ProgressParams);
+
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, Box<dyn Error + Send + Sync>>;
 }
 
-pub type ExecutorResult = Result<(), Box<dyn Error + Send + Sync>>;
+/// Result of an executor function.
+/// The returned value is the target output, which can be referenced by dependent targets (use [`Value::Null`] if there is none).
+pub type ExecutorResult = Result<Value, Box<dyn Error + Send + Sync>>;
 
 pub type ExecutorFn = fn(ctx: ExecutorContext, options: Value) -> ExecutorResult;
 
@@ -0,0 +1,38 @@
+import { httpAuthenticationSchema, httpTransportProperties } from './executor.js'
+import { Schema, strictObject } from './utils.js'
+
+export const cacheBackendSchema = {
+    oneOf: [
+        strictObject({
+            description: 'Cache entries are stored in the workspace `.blaze/cache` directory.',
+            properties: {
+                type: {
+                    const: 'Local'
+                }
+            },
+            required: ['type']
+        }),
+        strictObject({
+            description: 'Cache entries are stored on a remote HTTP server, which must support GET, HEAD, PUT and DELETE requests.',
+            properties: {
+                type: {
+                    const: 'Http'
+                },
+                url: {
+                    type: 'string',
+                    description: 'Base URL of the remote cache. Entries are located right under this URL.',
+                    pattern: '^https?://.+$'
+                },
+                authentication: {
+                    ...httpAuthenticationSchema,
+                    description: 'Authentication to use when reaching the remote cache.'
+                },
+                ...httpTransportProperties
+            },
+            required: ['type', 'url']
+        })
+    ],
+    default: {
+        type: 'Local'
+    }
+} as const satisfies Schema
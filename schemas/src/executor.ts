@@ -2,7 +2,7 @@ import { fileChangesMatcherSchema } from './matchers.js'
 import { Schema, notEmptyString, strictObject } from './utils.js'
 
 const executorKindSchema = {
-    enum: ['Rust', 'Node']
+    enum: ['Rust', 'Node', 'Python', 'Wasm']
 } satisfies Schema
 
 const gitPlainAuthentication = strictObject({
@@ -12,7 +12,7 @@ const gitPlainAuthentication = strictObject({
     }
 })
 
-const httpTransportProperties: Record<string, Schema> = {
+export const httpTransportProperties: Record<string, Schema> = {
     insecure: {
         description: 'Disable SSL/TLS certificate verification. Only for debugging purpose.',
         type: 'boolean',
@@ -72,6 +72,43 @@ const gitOptionsProperties: Record<string, Schema> = {
     }
 }
 
+export const httpAuthenticationSchema = {
+    oneOf: [
+        strictObject({
+            description: 'HTTP basic authentication.',
+            properties: {
+                mode: {
+                    const: 'Basic'
+                },
+                username: notEmptyString,
+                password: notEmptyString
+            },
+            required: ['mode', 'username', 'password']
+        }),
+        strictObject({
+            description: 'HTTP digest authentication.',
+            properties: {
+                mode: {
+                    const: 'Digest'
+                },
+                username: notEmptyString,
+                password: notEmptyString
+            },
+            required: ['mode', 'username', 'password']
+        }),
+        strictObject({
+            description: 'HTTP bearer token authentication.',
+            properties: {
+                mode: {
+                    const: 'Bearer'
+                },
+                token: notEmptyString
+            },
+            required: ['mode', 'token']
+        })
+    ]
+} as const satisfies Schema
+
 const sshAuthentication = {
     oneOf: [
         strictObject({
@@ -159,6 +196,37 @@ export const executorSchema = {
             },
             required: ['url', 'format'],
         }),
+        strictObject({
+            properties: {
+                url: {
+                    description: 'Tar archive HTTP URL',
+                    type: 'string',
+                    pattern: '^https?://.+$'
+                },
+                format: {
+                    description: 'Tells Blaze that the HTTP resource is a tar archive.',
+                    const: 'Tarball'
+                },
+                authentication: {
+                    ...httpAuthenticationSchema,
+                    description: 'Authentication to use when downloading the archive.'
+                },
+                compression: {
+                    enum: ['Gzip', 'Xz', 'Bzip2', 'Deflate', 'Zlib'],
+                    description: 'Archive compression. Inferred from the URL file extension if not provided.'
+                },
+                path: {
+                    ...notEmptyString,
+                    description: 'Path to the executor within the archive. Defaults to the archive root directory if not provided.'
+                },
+                kind: {
+                    ...executorKindSchema,
+                    description: 'Specify executor type if Blaze cannot infer it.'
+                },
+                ...httpTransportProperties
+            },
+            required: ['url', 'format'],
+        }),
         strictObject({
             properties: {
                 url: {
@@ -173,6 +241,64 @@ export const executorSchema = {
                 ...gitOptionsProperties,
                 ...sshTransportProperties
             }
+        }),
+        strictObject({
+            description: 'NPM package executor configuration.',
+            properties: {
+                url: {
+                    type: 'string',
+                    description: 'NPM package URL, with an optional registry host.',
+                    pattern: '^npm://.*/.+$'
+                },
+                version: {
+                    ...notEmptyString,
+                    description: 'Version requirement for the package. Defaults to the latest version.'
+                },
+                token: {
+                    ...notEmptyString,
+                    description: 'Access token to use when authenticating to the registry.'
+                },
+                plainHttp: {
+                    type: 'boolean',
+                    description: 'Reach the registry using plain HTTP instead of HTTPS. Only for debugging purpose.',
+                    default: false
+                },
+                insecure: {
+                    type: 'boolean',
+                    description: 'Disable SSL/TLS certificate verification. Only for debugging purpose.',
+                    default: false
+                }
+            },
+            required: ['url']
+        }),
+        strictObject({
+            description: 'Cargo crate executor configuration.',
+            properties: {
+                url: {
+                    type: 'string',
+                    description: 'Crate URL, with an optional registry host and index path.',
+                    pattern: '^cargo://.*/.+$'
+                },
+                version: {
+                    ...notEmptyString,
+                    description: 'Version requirement for the crate. Defaults to the latest version.'
+                },
+                token: {
+                    ...notEmptyString,
+                    description: 'Token to use when authenticating to the registry.'
+                },
+                plainHttp: {
+                    type: 'boolean',
+                    description: 'Reach the registry using plain HTTP instead of HTTPS. Only for debugging purpose.',
+                    default: false
+                },
+                insecure: {
+                    type: 'boolean',
+                    description: 'Disable SSL/TLS certificate verification. Only for debugging purpose.',
+                    default: false
+                }
+            },
+            required: ['url']
         })
     ]
 } as const satisfies Schema
\ No newline at end of file
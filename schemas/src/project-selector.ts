@@ -47,6 +47,20 @@ export const projectSelectorSchema = {
                 }
             },
             required: ['include']
+        }),
+        strictObject({
+            description: 'Select projects with files changed since a git revision, along with the projects depending on them.',
+            properties: {
+                base: {
+                    description: 'The base git revision (for example `origin/main`). Changes are computed from the merge base of this revision and the head revision.',
+                    ...notEmptyString
+                },
+                head: {
+                    description: 'The head git revision. Changes are compared with the working directory if not provided.',
+                    ...notEmptyString
+                }
+            },
+            required: ['base']
         })
     ]
 } as const satisfies Schema
\ No newline at end of file
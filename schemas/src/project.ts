@@ -36,7 +36,97 @@ export const projectSchema: Schema = {
                             type: 'boolean',
                             description: 'Can the target run concurrently (in multiple Blaze processes) ?',
                             default: false
-                        }
+                        },
+                        timeout: strictObject({
+                            description: 'Maximum duration of the target execution. When it expires, all processes started by the executor are killed and the target fails.',
+                            properties: {
+                                unit: {
+                                    description: 'The time unit to use.',
+                                    enum: [
+                                        'Milliseconds',
+                                        'Seconds',
+                                        'Minutes',
+                                        'Hours',
+                                        'Days'
+                                    ]
+                                },
+                                amount: {
+                                    description: 'The timeout value to use.',
+                                    type: 'integer',
+                                    minimum: 1
+                                }
+                            },
+                            required: ['unit', 'amount']
+                        }),
+                        retry: strictObject({
+                            description: 'Policy for executing the target again when it fails.',
+                            properties: {
+                                maxAttempts: {
+                                    description: 'Maximum number of attempts, including the first one.',
+                                    type: 'integer',
+                                    minimum: 1
+                                },
+                                delay: strictObject({
+                                    description: 'Delay to wait for between two attempts.',
+                                    properties: {
+                                        unit: {
+                                            description: 'The time unit to use.',
+                                            enum: [
+                                                'Milliseconds',
+                                                'Seconds',
+                                                'Minutes',
+                                                'Hours',
+                                                'Days'
+                                            ]
+                                        },
+                                        amount: {
+                                            description: 'The delay value to use.',
+                                            type: 'integer',
+                                            minimum: 1
+                                        }
+                                    },
+                                    required: ['unit', 'amount']
+                                }),
+                                backoff: {
+                                    description: 'How the delay evolves after each failed attempt.',
+                                    enum: ['Constant', 'Linear', 'Exponential'],
+                                    default: 'Constant'
+                                },
+                                exitCodes: {
+                                    description: 'Only retry when a process fails with one of these status codes.',
+                                    type: 'array',
+                                    items: {
+                                        type: 'integer'
+                                    },
+                                    uniqueItems: true
+                                }
+                            },
+                            required: ['maxAttempts']
+                        }),
+                        environment: strictObject({
+                            description: 'Environment variables of the processes started when executing the target.',
+                            properties: {
+                                passThrough: {
+                                    description: 'Variables of the Blaze process which are passed to the executor. When set, no other variable is inherited, and the cache is invalidated when one of these variables changes.',
+                                    type: 'array',
+                                    items: notEmptyString,
+                                    uniqueItems: true
+                                },
+                                variables: {
+                                    description: 'Static variables. They take precedence over variables loaded from files.',
+                                    type: 'object',
+                                    additionalProperties: {
+                                        type: 'string'
+                                    }
+                                },
+                                files: {
+                                    description: 'Files to load variables from (.env format), relative to the project root. Later files take precedence over earlier ones.',
+                                    type: 'array',
+                                    items: notEmptyString,
+                                    default: []
+                                }
+                            }
+                        })
                     }
                 })
             }
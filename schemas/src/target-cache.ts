@@ -4,6 +4,12 @@ import { notEmptyString, strictObject } from './utils.js'
 export const targetCacheSchema = strictObject({
     description: 'A target cache configuration object.',
     properties: {
+        outputs: {
+            type: 'array',
+            description: 'An array of output files matchers. Matched files will be stored after a successful execution and restored when cache is reused.',
+            items: fileChangesMatcherSchema,
+            uniqueItems: true
+        },
         invalidateWhen: strictObject({
             properties: {
                 inputChanges: {
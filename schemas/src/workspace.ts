@@ -1,3 +1,4 @@
+import { cacheBackendSchema } from './cache-backend.js'
 import { logLevelSchema } from './log-level.js'
 import { parallelismSchema } from './parallelism.js'
 import { projectSelectorSchema } from './project-selector.js'
@@ -69,6 +70,26 @@ export const workspaceSchema: Schema = {
                 resolutionParallelism: {
                     ...parallelismSchema,
                     description: 'The default parallelism level to use when resolving executors.'
+                },
+                cacheBackend: {
+                    ...cacheBackendSchema,
+                    description: 'Where target executions and executors cache is stored.'
+                },
+                failFast: {
+                    type: 'boolean',
+                    description: 'Stop executing targets as soon as one of them fails (for the `run` command). Defaults to `false`.'
+                },
+                persistentWorkers: {
+                    type: 'boolean',
+                    description: 'Keep bridge processes of custom executors alive during each run, in order to reuse them for several targets. Defaults to `false`.'
+                },
+                inProcessRustExecutors: {
+                    type: 'boolean',
+                    description: 'Load Rust executors libraries in the Blaze process instead of running them through the bridge executable. Defaults to `false`.'
+                },
+                allowUnenforcedCapabilities: {
+                    type: 'boolean',
+                    description: 'Run sandboxed executors even when their restrictions cannot be enforced on the system, instead of failing. Defaults to `false`.'
                 }
             }
         })
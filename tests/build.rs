@@ -18,6 +18,40 @@ fn main() {
         _ => {}
     };
 
+    // check if python is present or not
+    println!("cargo::rustc-check-cfg=cfg(python)");
+
+    match std::process::Command::new("python3")
+        .args(["--version"])
+        .spawn()
+    {
+        Ok(mut child) => {
+            child
+                .wait()
+                .expect("could not wait for python version process");
+            println!("cargo::rustc-cfg=python");
+        }
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            panic!("{err}");
+        }
+        _ => {}
+    };
+
+    // check if wasm components can be built
+    println!("cargo::rustc-check-cfg=cfg(wasm)");
+
+    if std::process::Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|target| target.trim() == "wasm32-wasip2")
+        })
+    {
+        println!("cargo::rustc-cfg=wasm");
+    }
+
     println!(
         "cargo::rustc-env=PROJECT_ROOT={}",
         std::env::var("CARGO_MANIFEST_DIR").unwrap()
@@ -0,0 +1,158 @@
+use blaze_core::{common::selector::ProjectSelector, run, GlobalOptions, RunOptions};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+use serde_json::json;
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn artifacts_are_restored() {
+    use blaze_core::SelectorSource;
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "mkdir -p '{{ project.root }}/dist'",
+                                    "echo 'some compiled code' >> '{{ project.root }}/dist/artifact.bin'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "outputs": ["dist/**"]
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let artifact_path = root.join("project-root/dist/artifact.bin");
+
+            let first_execution = run_cached_target();
+            first_execution.assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            std::fs::remove_dir_all(root.join("project-root/dist"))
+                .expect("could not remove test artifacts");
+
+            let second_execution = run_cached_target();
+            second_execution.assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(&artifact_path).expect("failed to read test file")
+            );
+
+            std::fs::write(&artifact_path, "tampered").expect("could not write test file");
+
+            let last_execution = run_cached_target();
+            last_execution.assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(&artifact_path).expect("failed to read test file")
+            );
+        },
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn artifacts_are_restored_with_their_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    use blaze_core::SelectorSource;
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "mkdir -p '{{ project.root }}/dist'",
+                                    "printf '#!/bin/sh\\necho executed\\n' > '{{ project.root }}/dist/run.sh'",
+                                    "chmod 755 '{{ project.root }}/dist/run.sh'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "outputs": ["dist/**"]
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let script_path = root.join("project-root/dist/run.sh");
+
+            let first_execution = run_cached_target();
+            first_execution.assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            std::fs::remove_dir_all(root.join("project-root/dist"))
+                .expect("could not remove test artifacts");
+
+            let second_execution = run_cached_target();
+            second_execution.assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            let mode = std::fs::metadata(&script_path)
+                .expect("could not read test file metadata")
+                .permissions()
+                .mode();
+            assert_eq!(0o755, mode & 0o777);
+
+            let output = std::process::Command::new(&script_path)
+                .output()
+                .expect("could not run restored script");
+            assert!(output.status.success());
+            assert_eq!(b"executed\n", output.stdout.as_slice());
+        },
+    )
+}
@@ -193,3 +193,93 @@ fn exclude_input_files() {
         },
     )
 }
+
+#[cfg(not(windows))]
+#[test]
+fn previous_input_state_is_cached() {
+    use blaze_core::SelectorSource;
+    use filetime::{set_file_mtime, FileTime};
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "target-name": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo 'Hello world!' >> {{ project.root }}/history.txt"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": [
+                                        "source.txt"
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [("files/source.txt", "project-root/")],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("target-name").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let source_path = root.join("project-root/source.txt");
+            let original_content =
+                std::fs::read_to_string(&source_path).expect("could not read source file");
+
+            let write_source = |content: &str| {
+                set_current_time(now() + Duration::from_secs(10));
+                std::fs::write(&source_path, content).expect("could not write to source file");
+                set_file_mtime(&source_path, FileTime::from_system_time(now()))
+                    .expect("could not change mtime of source file");
+            };
+
+            let executions = run_cached_target();
+            executions.assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+
+            write_source("modified");
+
+            let executions = run_cached_target();
+            executions.assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+
+            // switching back to the original content must reuse the first cached state
+            write_source(&original_content);
+
+            let executions = run_cached_target();
+            executions.assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            write_source("modified");
+
+            let executions = run_cached_target();
+            executions.assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "Hello world!\n".repeat(2),
+                std::fs::read_to_string(root.join("project-root/history.txt"))
+                    .expect("could not read history file")
+            );
+        },
+    )
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use blaze_core::{common::selector::ProjectSelector, run, GlobalOptions, RunOptions};
+use testing::{
+    start_http_server, with_test_workspace, Executions, ExpectedExecution, HttpResponse,
+    TestWorkspaceConfiguration,
+};
+
+use serde_json::json;
+
+mod testing;
+
+type Entries = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// Start a test server storing entries in memory, standing in for a remote cache.
+fn start_cache_server() -> (String, Entries) {
+    let entries = Entries::default();
+    let server_entries = entries.clone();
+
+    let address = start_http_server(move |request| {
+        let mut entries = server_entries.lock().unwrap();
+        match request.method.as_str() {
+            "GET" | "HEAD" => match entries.get(&request.path) {
+                Some(content) => HttpResponse::ok(content.clone()),
+                None => HttpResponse::status(404),
+            },
+            "PUT" => {
+                entries.insert(request.path.clone(), request.body.clone());
+                HttpResponse::status(204)
+            }
+            "DELETE" => {
+                entries.remove(&request.path);
+                HttpResponse::status(204)
+            }
+            _ => HttpResponse::status(405),
+        }
+    });
+
+    (format!("http://{address}/cache"), entries)
+}
+
+#[cfg(not(windows))]
+#[test]
+fn http_cache_backend() {
+    use blaze_core::SelectorSource;
+
+    let (url, entries) = start_cache_server();
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                },
+                "settings": {
+                    "cacheBackend": {
+                        "type": "Http",
+                        "url": url
+                    }
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo 'some compiled code' > '{{ project.root }}/artifact.bin'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "outputs": ["artifact.bin"]
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = |options: GlobalOptions| -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    options,
+                );
+                Executions::from_run_result(results)
+            };
+
+            let without_cache = run_cached_target(GlobalOptions::default().without_cache());
+            without_cache.assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            assert!(entries.lock().unwrap().is_empty());
+
+            let first_execution = run_cached_target(GlobalOptions::default());
+            first_execution.assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            // execution state and artifact
+            assert_eq!(entries.lock().unwrap().len(), 2);
+
+            std::fs::remove_file(root.join("project-root/artifact.bin"))
+                .expect("could not remove test artifact");
+
+            let second_execution = run_cached_target(GlobalOptions::default());
+            second_execution.assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(root.join("project-root/artifact.bin"))
+                    .expect("failed to read test file")
+            );
+        },
+    )
+}
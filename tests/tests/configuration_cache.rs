@@ -0,0 +1,326 @@
+mod testing;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+/// Project configuration writing *message* to an output file, when running the `greet` target.
+#[cfg(unix)]
+fn greet_project(message: &str) -> Value {
+    json!({
+        "targets": {
+            "greet": {
+                "executor": "std:commands",
+                "options": {
+                    "commands": [
+                        format!("echo '{message}' > '{{{{ project.root }}}}/output.txt'")
+                    ],
+                    "shell": true
+                }
+            }
+        }
+    })
+}
+
+/// Run the `greet` target, and get the content of its output file.
+#[cfg(unix)]
+fn greet(root: &Path) -> String {
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new("greet").with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    ))
+    .assert_targets([("project:greet", ExpectedExecution::success())]);
+
+    std::fs::read_to_string(root.join("project/output.txt")).expect("could not read output file")
+}
+
+/// Get the inode of each entry of the local cache. Since entries are written to a temporary file and then renamed, it changes every time an entry is written.
+#[cfg(unix)]
+fn cache_entries(root: &Path) -> BTreeMap<PathBuf, u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::read_dir(root.join(".blaze/cache"))
+        .expect("could not read cache directory")
+        .map(|entry| {
+            let entry = entry.expect("could not read cache entry");
+            let metadata = entry
+                .metadata()
+                .expect("could not read cache entry metadata");
+            (entry.path(), metadata.ino())
+        })
+        .filter(|(path, _)| path.is_file())
+        .collect()
+}
+
+#[cfg(unix)]
+#[test]
+fn imported_files_invalidate_configuration_cache() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({ "targets": {} }))],
+            [],
+        ),
+        |root| {
+            std::fs::remove_file(root.join("project/project.json"))
+                .expect("could not remove project file");
+            std::fs::write(
+                root.join("project/project.jsonnet"),
+                r#"
+                local lib = import 'lib.libsonnet';
+                {
+                    targets: {
+                        greet: {
+                            executor: 'std:commands',
+                            options: {
+                                commands: ["echo '" + lib.message + "' > '{{ project.root }}/output.txt'"],
+                                shell: true
+                            }
+                        }
+                    }
+                }
+                "#,
+            )
+            .expect("could not write project file");
+
+            let write_message = |message: &str| {
+                std::fs::write(
+                    root.join("project/lib.libsonnet"),
+                    format!("{{ message: '{message}' }}"),
+                )
+                .expect("could not write library file")
+            };
+
+            write_message("first");
+            assert_eq!(greet(root), "first\n");
+
+            let entries = cache_entries(root);
+            assert_eq!(greet(root), "first\n");
+            assert_eq!(
+                cache_entries(root),
+                entries,
+                "configuration must be restored from cache"
+            );
+
+            write_message("second");
+            assert_eq!(greet(root), "second\n");
+            assert_ne!(cache_entries(root), entries);
+
+            // entries which cannot be read are evaluated again.
+            for entry in cache_entries(root).keys() {
+                std::fs::write(entry, "corrupted").expect("could not corrupt cache entry");
+            }
+            assert_eq!(greet(root), "second\n");
+        },
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn variables_invalidate_configuration_cache() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", greet_project("{{ vars.message }}"))],
+            [],
+        ),
+        |root| {
+            std::fs::create_dir_all(root.join(".blaze"))
+                .expect("could not create .blaze directory");
+
+            let write_message = |message: &str| {
+                std::fs::write(
+                    root.join(".blaze/variables.json"),
+                    json!({ "vars": { "message": message } }).to_string(),
+                )
+                .expect("could not write variables file")
+            };
+
+            write_message("first");
+            assert_eq!(greet(root), "first\n");
+            assert_eq!(greet(root), "first\n");
+
+            write_message("second");
+            assert_eq!(greet(root), "second\n");
+        },
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn jsonnet_external_variables_invalidate_configuration_cache() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({ "targets": {} }))],
+            [],
+        ),
+        |root| {
+            std::fs::remove_file(root.join("project/project.json"))
+                .expect("could not remove project file");
+            std::fs::write(
+                root.join("project/project.jsonnet"),
+                r#"
+                {
+                    targets: {
+                        greet: {
+                            executor: 'std:commands',
+                            options: {
+                                commands: ["echo '" + std.extVar('blaze').vars.message + "' > '{{ project.root }}/output.txt'"],
+                                shell: true
+                            }
+                        }
+                    }
+                }
+                "#,
+            )
+            .expect("could not write project file");
+
+            std::fs::create_dir_all(root.join(".blaze"))
+                .expect("could not create .blaze directory");
+
+            let write_message = |message: &str| {
+                std::fs::write(
+                    root.join(".blaze/variables.json"),
+                    json!({ "vars": { "message": message } }).to_string(),
+                )
+                .expect("could not write variables file")
+            };
+
+            write_message("first");
+            assert_eq!(greet(root), "first\n");
+            assert_eq!(greet(root), "first\n");
+
+            write_message("second");
+            assert_eq!(greet(root), "second\n");
+        },
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn shadowing_imports_invalidate_configuration_cache() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({ "targets": {} }))],
+            [],
+        ),
+        |root| {
+            std::fs::remove_file(root.join("project/project.json"))
+                .expect("could not remove project file");
+            std::fs::write(
+                root.join("project/project.jsonnet"),
+                r#"
+                local lib = import 'message.libsonnet';
+                {
+                    targets: {
+                        greet: {
+                            executor: 'std:commands',
+                            options: {
+                                commands: ["echo '" + lib.message + "' > '{{ project.root }}/output.txt'"],
+                                shell: true
+                            }
+                        }
+                    }
+                }
+                "#,
+            )
+            .expect("could not write project file");
+
+            std::fs::create_dir_all(root.join(".blaze"))
+                .expect("could not create .blaze directory");
+            std::fs::create_dir_all(root.join("lib")).expect("could not create library directory");
+            std::fs::write(root.join(".blaze/.jpath"), "lib\n")
+                .expect("could not write jpath file");
+            std::fs::write(root.join("lib/message.libsonnet"), "{ message: 'library' }")
+                .expect("could not write library file");
+
+            assert_eq!(greet(root), "library\n");
+            assert_eq!(greet(root), "library\n");
+
+            // files located next to the importing file take precedence over library directories.
+            std::fs::write(
+                root.join("project/message.libsonnet"),
+                "{ message: 'project' }",
+            )
+            .expect("could not write project library file");
+
+            assert_eq!(greet(root), "project\n");
+        },
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn impure_configurations_are_not_cached() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({ "targets": {} }))],
+            [],
+        ),
+        |root| {
+            let counter = root.join("counter.txt");
+
+            std::fs::write(
+                root.join("project/project.json"),
+                greet_project(&format!(
+                    "{{{{ shell \"cat {}\" trim=true }}}} {{{{ random 16 }}}}",
+                    counter.display()
+                ))
+                .to_string(),
+            )
+            .expect("could not write project file");
+
+            std::fs::write(&counter, "1").expect("could not write counter file");
+            let first = greet(root);
+            assert!(first.starts_with("1 "));
+
+            let second = greet(root);
+            assert!(second.starts_with("1 "));
+            assert_ne!(first, second, "random values must be generated again");
+
+            std::fs::write(&counter, "2").expect("could not write counter file");
+            assert!(greet(root).starts_with("2 "));
+
+            assert!(
+                cache_entries(root).is_empty(),
+                "configuration must not be cached"
+            );
+        },
+    );
+}
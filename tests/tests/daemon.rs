@@ -0,0 +1,119 @@
+mod testing;
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use blaze_core::{
+    common::selector::ProjectSelector, daemon, run_with_daemon, stop_daemon, GlobalOptions,
+    RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+#[cfg(unix)]
+#[test]
+fn runs_with_daemon() {
+    let project = |message: &str| {
+        json!({
+            "targets": {
+                "greet": {
+                    "executor": "std:commands",
+                    "options": {
+                        "commands": [
+                            format!("echo '{message}' > '{{{{ project.root }}}}/output.txt'")
+                        ],
+                        "shell": true
+                    }
+                }
+            }
+        })
+    };
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", project("first"))],
+            [],
+        ),
+        |root| {
+            let options = || {
+                RunOptions::new("greet").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                ))
+            };
+
+            assert!(
+                run_with_daemon(root, &options(), &GlobalOptions::default())
+                    .expect("could not connect to daemon")
+                    .is_none(),
+                "no daemon should be running yet"
+            );
+
+            let daemon_root = root.to_owned();
+            let daemon_thread =
+                thread::spawn(move || daemon(daemon_root, GlobalOptions::default()));
+
+            while !root.join(".blaze/daemon.json").exists() {
+                assert!(!daemon_thread.is_finished(), "daemon exited early");
+                thread::sleep(Duration::from_millis(50));
+            }
+
+            let read_output = || {
+                std::fs::read_to_string(root.join("project/output.txt"))
+                    .expect("could not read output file")
+            };
+
+            let run = || {
+                run_with_daemon(root, &options(), &GlobalOptions::default())
+                    .expect("daemon run failed")
+                    .expect("daemon should be running")
+            };
+
+            assert!(run().root_failures().is_empty());
+            assert_eq!(read_output(), "first\n");
+
+            std::fs::write(
+                root.join("project/project.json"),
+                project("second").to_string(),
+            )
+            .expect("could not write project file");
+
+            // the configuration is loaded again once the daemon file watcher reports the change.
+            let deadline = Instant::now() + Duration::from_secs(10);
+            loop {
+                assert!(run().root_failures().is_empty());
+                if read_output() == "second\n" {
+                    break;
+                }
+                assert!(
+                    Instant::now() < deadline,
+                    "daemon did not load the project configuration again"
+                );
+                thread::sleep(Duration::from_millis(50));
+            }
+
+            assert!(
+                run_with_daemon(root, &options(), &GlobalOptions::default().without_cache())
+                    .expect("could not run")
+                    .is_none(),
+                "global options must not be sent to the daemon"
+            );
+
+            assert!(stop_daemon(root).expect("could not stop daemon"));
+            daemon_thread
+                .join()
+                .expect("daemon thread panicked")
+                .expect("daemon failed");
+
+            assert!(!root.join(".blaze/daemon.json").exists());
+            assert!(!stop_daemon(root).expect("could not check daemon"));
+        },
+    );
+}
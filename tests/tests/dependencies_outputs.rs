@@ -0,0 +1,152 @@
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{
+    get_fixtures_root, with_test_workspace, Executions, ExpectedExecution,
+    TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+#[cfg(node)]
+#[test]
+fn dependencies_outputs_are_replayed_from_cache() {
+    let executor_url = format!(
+        "file://{}",
+        get_fixtures_root().join("executors/npm-writer").display()
+    );
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "lib": "lib",
+                    "app": "app"
+                }
+            }),
+            [
+                (
+                    "lib",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": executor_url,
+                                "options": {
+                                    "message": "hello"
+                                },
+                                "cache": {}
+                            }
+                        }
+                    }),
+                ),
+                (
+                    "app",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": executor_url,
+                                "options": {
+                                    "message": "{{ deps.lib.build.message }} from {{ project.name }}"
+                                },
+                                "dependencies": [
+                                    {
+                                        "projects": ["lib"],
+                                        "target": "build"
+                                    }
+                                ]
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let execute = || {
+                run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["app"]),
+                    )),
+                    Default::default(),
+                )
+            };
+
+            let read_output = || {
+                std::fs::read_to_string(root.join("app/executed"))
+                    .expect("could not read executor output")
+            };
+
+            Executions::from_run_result(execute()).assert_targets([
+                ("lib:build", ExpectedExecution::success()),
+                ("app:build", ExpectedExecution::success()),
+            ]);
+            assert_eq!(read_output(), "hello from app");
+
+            std::fs::remove_file(root.join("app/executed")).expect("could not remove output");
+
+            Executions::from_run_result(execute()).assert_targets([
+                ("lib:build", ExpectedExecution::cached()),
+                ("app:build", ExpectedExecution::success()),
+            ]);
+            assert_eq!(read_output(), "hello from app");
+        },
+    );
+}
+
+#[test]
+fn missing_dependency_output() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "a": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": []
+                            }
+                        },
+                        "b": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    {
+                                        "program": "echo",
+                                        "arguments": ["{{ deps.project.a.artifact }}"]
+                                    }
+                                ]
+                            },
+                            "dependencies": [
+                                {
+                                    "target": "a"
+                                }
+                            ]
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            Executions::from_run_result(run(
+                root,
+                RunOptions::new("b").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            ))
+            .assert_targets([
+                ("project:a", ExpectedExecution::success()),
+                ("project:b", ExpectedExecution::failure()),
+            ]);
+        },
+    );
+}
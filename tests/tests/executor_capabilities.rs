@@ -0,0 +1,188 @@
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{
+    cmd, get_fixtures_root, start_http_server, with_test_workspace, HttpResponse,
+    TestWorkspaceConfiguration,
+};
+
+use crate::testing::{Executions, ExpectedExecution};
+
+mod testing;
+
+/// Whether Landlock is enabled on the running kernel.
+#[cfg(all(node, target_os = "linux"))]
+fn landlock_enabled() -> bool {
+    std::fs::read_to_string("/sys/kernel/security/lsm")
+        .is_ok_and(|modules| modules.split(',').any(|module| module.trim() == "landlock"))
+}
+
+#[cfg(all(node, target_os = "linux"))]
+#[test]
+fn writes_are_restricted_to_declared_paths() {
+    let executor_root = get_fixtures_root().join("executors/node-sandboxed");
+    let executor_url = format!("file://{}", executor_root.to_str().unwrap());
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "allowUnenforcedCapabilities": true
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "allowed": {
+                            "executor": executor_url,
+                            "options": {
+                                "file": "out/allowed.txt"
+                            }
+                        },
+                        "denied": {
+                            "executor": executor_url,
+                            "options": {
+                                "file": "denied.txt"
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let execute = |target: &str| {
+                run(
+                    root,
+                    RunOptions::new(target).with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                )
+            };
+
+            Executions::from_run_result(execute("allowed"))
+                .assert_targets([("project:allowed", ExpectedExecution::success())]);
+
+            assert!(root.join("project/out/allowed.txt").is_file());
+
+            if !landlock_enabled() {
+                return;
+            }
+
+            Executions::from_run_result(execute("denied"))
+                .assert_targets([("project:denied", ExpectedExecution::failure())]);
+
+            assert!(!root.join("project/denied.txt").exists());
+        },
+    );
+}
+
+#[cfg(all(node, target_os = "linux"))]
+#[test]
+fn remote_executors_are_sandboxed_by_default() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let archive_root = root.join("archive");
+            cmd(format!(
+                "mkdir -p '{0}' && cp -r '{1}/.' '{0}'",
+                archive_root.display(),
+                get_fixtures_root()
+                    .join("executors/node-sandboxed")
+                    .display()
+            ));
+
+            // the executor must not declare any capability.
+            let manifest_path = archive_root.join("package.json");
+            let mut manifest: serde_json::Value = serde_json::from_str(
+                &std::fs::read_to_string(&manifest_path).expect("could not read package.json"),
+            )
+            .expect("could not parse package.json");
+            manifest["blaze"]
+                .as_object_mut()
+                .expect("blaze metadata must be an object")
+                .remove("capabilities");
+            std::fs::write(&manifest_path, manifest.to_string())
+                .expect("could not write package.json");
+
+            cmd(format!(
+                "tar -czf '{0}.tar.gz' -C '{0}' .",
+                archive_root.display()
+            ));
+            let tarball =
+                std::fs::read(root.join("archive.tar.gz")).expect("could not read tarball");
+            let address = start_http_server(move |_| HttpResponse::ok(tarball.clone()));
+
+            let executor = json!({
+                "url": format!("http://{address}/executor.tar.gz"),
+                "format": "Tarball"
+            });
+
+            std::fs::write(
+                root.join("project/project.json"),
+                json!({
+                    "targets": {
+                        "output": {
+                            "executor": executor,
+                            "options": {
+                                "file": "dist/output.txt"
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "outputChanges": ["dist/**"]
+                                }
+                            }
+                        },
+                        "denied": {
+                            "executor": executor,
+                            "options": {
+                                "file": "denied.txt"
+                            }
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect("could not write project file");
+
+            let execute = |target: &str| {
+                run(
+                    root,
+                    RunOptions::new(target).with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                )
+            };
+
+            // when restrictions cannot be enforced, remote executors are not run at all.
+            Executions::from_run_result(execute("denied"))
+                .assert_targets([("project:denied", ExpectedExecution::failure())]);
+
+            assert!(!root.join("project/denied.txt").exists());
+
+            if !landlock_enabled() {
+                return;
+            }
+
+            Executions::from_run_result(execute("output"))
+                .assert_targets([("project:output", ExpectedExecution::success())]);
+
+            assert!(root.join("project/dist/output.txt").is_file());
+        },
+    );
+}
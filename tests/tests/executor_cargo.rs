@@ -0,0 +1,177 @@
+use std::sync::{Arc, Mutex};
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use testing::{
+    cmd, get_fixtures_root, start_http_server, with_test_workspace, Executions, ExpectedExecution,
+    HttpResponse, TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+#[cfg(all(not(target_env = "musl"), not(windows)))]
+#[derive(Default)]
+struct Registry {
+    versions: Vec<String>,
+    downloads: Vec<String>,
+}
+
+/// Start a sparse registry stand-in, serving the same crate for every published version.
+#[cfg(all(not(target_env = "musl"), not(windows)))]
+fn start_registry(krate: Vec<u8>, registry: Arc<Mutex<Registry>>) -> String {
+    let checksum = format!("{:x}", Sha256::digest(&krate));
+    start_http_server(move |request| {
+        let host = &request.headers["host"];
+        let mut registry = registry.lock().unwrap();
+        match request.path.as_str() {
+            "/index/config.json" => HttpResponse::ok(
+                json!({
+                    "dl": format!("http://{host}/crates")
+                })
+                .to_string(),
+            ),
+            "/index/ru/st/rust-checker" => HttpResponse::ok(
+                registry
+                    .versions
+                    .iter()
+                    .map(|version| {
+                        json!({
+                            "name": "rust-checker",
+                            "vers": version,
+                            "deps": [],
+                            "cksum": checksum,
+                            "features": {},
+                            "yanked": false
+                        })
+                        .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            path => match path
+                .strip_prefix("/crates/rust-checker/")
+                .and_then(|file| file.strip_suffix("/download"))
+            {
+                Some(version) => {
+                    registry.downloads.push(version.to_owned());
+                    HttpResponse::ok(krate.clone())
+                }
+                None => HttpResponse::status(404),
+            },
+        }
+    })
+}
+
+#[cfg(all(not(target_env = "musl"), not(windows)))]
+#[test]
+fn cargo_executor_updates() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "logLevel": "Debug",
+                    "allowUnenforcedCapabilities": true
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let executor_root = get_fixtures_root().join("executors/rust-checker");
+            let package_root = root.join("crate");
+            let crate_root = package_root.join("rust-checker-0.1.0");
+            cmd(format!(
+                "mkdir -p '{0}' && cp -r '{1}' '{2}' && rm -rf '{2}/target' '{2}/build_hash'",
+                package_root.display(),
+                executor_root.display(),
+                crate_root.display()
+            ));
+
+            // the devkit dependency path must not depend on where the crate is unpacked.
+            let manifest_path = crate_root.join("Cargo.toml");
+            let manifest =
+                std::fs::read_to_string(&manifest_path).expect("could not read crate manifest");
+            std::fs::write(
+                &manifest_path,
+                manifest.replace(
+                    "path = \"../../../../../rust/devkit\"",
+                    &format!(
+                        "path = \"{}/../../../../../rust/devkit\"",
+                        executor_root.display()
+                    ),
+                ),
+            )
+            .expect("could not write crate manifest");
+
+            cmd(format!(
+                "tar -czf '{0}/rust-checker.crate' -C '{0}' rust-checker-0.1.0",
+                package_root.display()
+            ));
+            let krate = std::fs::read(package_root.join("rust-checker.crate"))
+                .expect("could not read crate");
+
+            let registry = Arc::new(Mutex::new(Registry {
+                versions: vec!["0.1.0".into(), "0.1.1".into(), "0.2.0".into()],
+                ..Default::default()
+            }));
+            let address = start_registry(krate, registry.clone());
+
+            std::fs::write(
+                root.join("project/project.json"),
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("cargo://{address}/index/rust-checker"),
+                                "version": "0.1",
+                                "plainHttp": true
+                            },
+                            "options": {
+                                "number": 1,
+                                "string": "hello",
+                                "bool": true,
+                                "array": [1, 2, 3],
+                                "null": null,
+                                "float": 1.0
+                            }
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect("could not write project file");
+
+            let execute = || {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                ))
+                .assert_targets([("project:target", ExpectedExecution::success())]);
+            };
+            let downloads = || registry.lock().unwrap().downloads.clone();
+
+            execute();
+            assert_eq!(downloads(), ["0.1.1"]);
+            assert!(root
+                .join(".blaze/cargo/rust-checker/0.1.1/Cargo.toml")
+                .is_file());
+
+            execute();
+            assert_eq!(downloads(), ["0.1.1"]);
+
+            registry.lock().unwrap().versions.push("0.1.2".into());
+
+            execute();
+            assert_eq!(downloads(), ["0.1.1", "0.1.2"]);
+            assert!(!root.join(".blaze/cargo/rust-checker/0.1.1").exists());
+        },
+    );
+}
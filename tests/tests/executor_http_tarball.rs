@@ -0,0 +1,202 @@
+use std::sync::{Arc, Mutex};
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::{json, Value};
+use testing::{
+    cmd, declare_capabilities, get_fixtures_root, start_http_server, with_test_workspace,
+    Executions, ExpectedExecution, HttpResponse, TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+/// Create a gzipped tarball of the npm-writer executor, located at `prefix` in the archive.
+#[cfg(all(node, not(windows)))]
+fn create_tarball(root: &std::path::Path, prefix: &str) -> Vec<u8> {
+    let archive_root = root.join("archive");
+    cmd(format!(
+        "mkdir -p '{0}/{1}' && cp -r '{2}/.' '{0}/{1}'",
+        archive_root.display(),
+        prefix,
+        get_fixtures_root().join("executors/npm-writer").display()
+    ));
+    // executors resolved from HTTP archives can only write to declared paths.
+    declare_capabilities(
+        &archive_root.join(prefix),
+        json!({ "writablePaths": ["."] }),
+    );
+    cmd(format!(
+        "tar -czf '{0}.tar.gz' -C '{0}' .",
+        archive_root.display()
+    ));
+    std::fs::read(root.join("archive.tar.gz")).expect("could not read tarball")
+}
+
+#[cfg(all(node, not(windows)))]
+fn execute(root: &std::path::Path, executor: Value) {
+    std::fs::write(
+        root.join("project/project.json"),
+        json!({
+            "targets": {
+                "target": {
+                    "executor": executor,
+                    "options": {
+                        "message": "hello"
+                    }
+                }
+            }
+        })
+        .to_string(),
+    )
+    .expect("could not write project file");
+
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    ))
+    .assert_targets([("project:target", ExpectedExecution::success())]);
+
+    assert_eq!(
+        std::fs::read_to_string(root.join("project/executed"))
+            .expect("could not read executor output"),
+        "hello"
+    );
+}
+
+#[cfg(all(node, not(windows)))]
+#[test]
+fn tarball_executor_updates() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "allowUnenforcedCapabilities": true
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let tarball = create_tarball(root, "");
+            let etag = Arc::new(Mutex::new(String::from("\"v1\"")));
+            let downloads = Arc::new(Mutex::new(0_usize));
+
+            let address = {
+                let etag = etag.clone();
+                let downloads = downloads.clone();
+                start_http_server(move |request| {
+                    if request.path != "/executor.tar.gz" {
+                        return HttpResponse::status(404);
+                    }
+                    let etag = etag.lock().unwrap().clone();
+                    if request.headers.get("if-none-match") == Some(&etag) {
+                        return HttpResponse::status(304);
+                    }
+                    *downloads.lock().unwrap() += 1;
+                    HttpResponse::ok(tarball.clone()).with_header("ETag", etag)
+                })
+            };
+
+            let executor = json!({
+                "url": format!("http://{address}/executor.tar.gz"),
+                "format": "Tarball"
+            });
+            let downloads = || *downloads.lock().unwrap();
+            let unpacked_archives = || {
+                std::fs::read_dir(root.join(".blaze/tarballs"))
+                    .expect("could not read tarballs directory")
+                    .count()
+            };
+
+            execute(root, executor.clone());
+            assert_eq!(downloads(), 1);
+
+            execute(root, executor.clone());
+            assert_eq!(downloads(), 1);
+
+            *etag.lock().unwrap() = "\"v2\"".into();
+
+            execute(root, executor);
+            assert_eq!(downloads(), 2);
+            assert_eq!(unpacked_archives(), 1);
+        },
+    );
+}
+
+#[cfg(all(node, not(windows)))]
+#[test]
+fn tarball_executor_digest_authentication() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "allowUnenforcedCapabilities": true
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let tarball = create_tarball(root, "executor");
+            let authorizations = Arc::new(Mutex::new(vec![]));
+
+            let address = {
+                let authorizations = authorizations.clone();
+                start_http_server(move |request| match request.headers.get("authorization") {
+                    Some(authorization) => {
+                        authorizations.lock().unwrap().push(authorization.clone());
+                        HttpResponse::ok(tarball.clone())
+                    }
+                    None => HttpResponse::status(401).with_header(
+                        "WWW-Authenticate",
+                        "Digest realm=\"blaze\", nonce=\"abcdef\", qop=\"auth\", opaque=\"xyz\"",
+                    ),
+                })
+            };
+
+            execute(
+                root,
+                json!({
+                    "url": format!("http://{address}/download"),
+                    "format": "Tarball",
+                    "compression": "Gzip",
+                    "path": "executor",
+                    "authentication": {
+                        "mode": "Digest",
+                        "username": "user",
+                        "password": "password"
+                    }
+                }),
+            );
+
+            let authorizations = authorizations.lock().unwrap();
+            assert_eq!(authorizations.len(), 1);
+            let authorization = &authorizations[0];
+            for expected in [
+                "Digest ",
+                "username=\"user\"",
+                "realm=\"blaze\"",
+                "nonce=\"abcdef\"",
+                "uri=\"/download\"",
+                "qop=auth",
+                "opaque=\"xyz\"",
+            ] {
+                assert!(
+                    authorization.contains(expected),
+                    "{authorization} does not contain {expected}"
+                );
+            }
+            assert!(!authorization.contains("password"));
+        },
+    );
+}
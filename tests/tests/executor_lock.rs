@@ -0,0 +1,164 @@
+use std::sync::{Arc, Mutex};
+
+use blaze_core::{
+    common::selector::ProjectSelector, run, update_executors, RunOptions, RunResult, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{
+    cmd, declare_capabilities, get_fixtures_root, start_http_server, with_test_workspace,
+    Executions, ExpectedExecution, HttpResponse, TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+/// Create a gzipped tarball of the npm-writer executor, with an extra file to make its digest unique.
+#[cfg(all(node, not(windows)))]
+fn create_tarball(root: &std::path::Path, revision: &str) -> Vec<u8> {
+    let archive_root = root.join(format!("archive-{revision}"));
+    cmd(format!(
+        "mkdir -p '{0}' && cp -r '{1}/.' '{0}' && echo '{2}' > '{0}/revision'",
+        archive_root.display(),
+        get_fixtures_root().join("executors/npm-writer").display(),
+        revision
+    ));
+    // executors resolved from HTTP archives can only write to declared paths.
+    declare_capabilities(&archive_root, json!({ "writablePaths": ["."] }));
+    cmd(format!(
+        "tar -czf '{0}.tar.gz' -C '{0}' .",
+        archive_root.display()
+    ));
+    std::fs::read(root.join(format!("archive-{revision}.tar.gz"))).expect("could not read tarball")
+}
+
+#[cfg(all(node, not(windows)))]
+fn execute(root: &std::path::Path, locked: bool) -> RunResult {
+    let mut options = RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+        ProjectSelector::array(["project"]),
+    ));
+    if locked {
+        options = options.as_locked();
+    }
+    run(root, options, Default::default())
+}
+
+#[cfg(all(node, not(windows)))]
+fn read_lock(root: &std::path::Path) -> Value {
+    serde_json::from_str(
+        &std::fs::read_to_string(root.join("blaze.lock")).expect("could not read lockfile"),
+    )
+    .expect("invalid lockfile")
+}
+
+#[cfg(all(node, not(windows)))]
+#[test]
+fn executor_lock() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "allowUnenforcedCapabilities": true
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let served = Arc::new(Mutex::new(create_tarball(root, "v1")));
+            let downloads = Arc::new(Mutex::new(0_usize));
+
+            let address = {
+                let served = served.clone();
+                let downloads = downloads.clone();
+                start_http_server(move |_| {
+                    *downloads.lock().unwrap() += 1;
+                    HttpResponse::ok(served.lock().unwrap().clone())
+                })
+            };
+
+            std::fs::write(
+                root.join("project/project.json"),
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("http://{address}/executor.tar.gz"),
+                                "format": "Tarball",
+                                "headers": {
+                                    "Authorization": "Bearer lock-secret"
+                                }
+                            },
+                            "options": {
+                                "message": "hello"
+                            }
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect("could not write project file");
+
+            let downloads = || *downloads.lock().unwrap();
+            let pins = |lock: &Value| {
+                lock["executors"]
+                    .as_object()
+                    .expect("lockfile executors should be an object")
+                    .values()
+                    .map(|locked| locked["pin"].as_str().unwrap().to_owned())
+                    .collect::<Vec<_>>()
+            };
+
+            // the lockfile is created on first resolution
+            Executions::from_run_result(execute(root, false))
+                .assert_targets([("project:target", ExpectedExecution::success())]);
+            let v1_lock = read_lock(root);
+            let v1_pins = pins(&v1_lock);
+            assert_eq!(v1_pins.len(), 1);
+            assert!(v1_pins[0].starts_with("sha256:"));
+            // entries are keyed by location, without any credentials
+            assert!(v1_lock["executors"]
+                .get(format!("http://{address}/executor.tar.gz"))
+                .is_some());
+            assert!(!std::fs::read_to_string(root.join("blaze.lock"))
+                .expect("could not read lockfile")
+                .contains("lock-secret"));
+            assert_eq!(downloads(), 1);
+
+            // a new archive is published, but the locked version is still used
+            *served.lock().unwrap() = create_tarball(root, "v2");
+            Executions::from_run_result(execute(root, true))
+                .assert_targets([("project:target", ExpectedExecution::success())]);
+            assert_eq!(downloads(), 1);
+            assert_eq!(read_lock(root), v1_lock);
+
+            // without any cached resolution, the locked archive cannot be downloaded anymore
+            std::fs::remove_dir_all(root.join(".blaze")).expect("could not remove .blaze");
+            let err = execute(root, false).expect_err("run should fail with a stale lock");
+            assert!(
+                format!("{err:#}").contains("does not match the locked digest"),
+                "unexpected error: {err:#}"
+            );
+
+            // updating executors locks the new archive
+            update_executors(root, Default::default()).expect("could not update executors");
+            let v2_pins = pins(&read_lock(root));
+            assert_eq!(v2_pins.len(), 1);
+            assert_ne!(v2_pins, v1_pins);
+
+            Executions::from_run_result(execute(root, true))
+                .assert_targets([("project:target", ExpectedExecution::success())]);
+
+            // missing lockfile entries are rejected in locked mode
+            std::fs::remove_file(root.join("blaze.lock")).expect("could not remove lockfile");
+            let err = execute(root, true).expect_err("run should fail without a lockfile");
+            assert!(
+                format!("{err:#}").contains("blaze.lock is not up to date"),
+                "unexpected error: {err:#}"
+            );
+            assert!(!root.join("blaze.lock").exists());
+        },
+    );
+}
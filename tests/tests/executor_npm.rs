@@ -0,0 +1,195 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{
+    cmd, declare_capabilities, get_fixtures_root, start_http_server, with_test_workspace,
+    Executions, ExpectedExecution, HttpResponse, TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+#[cfg(all(node, not(windows)))]
+#[derive(Default)]
+struct Registry {
+    versions: Vec<String>,
+    downloads: Vec<String>,
+}
+
+/// Start an npm registry stand-in, serving the same tarball for every published version.
+#[cfg(all(node, not(windows)))]
+fn start_registry(tarball: Vec<u8>, registry: Arc<Mutex<Registry>>) -> String {
+    start_http_server(move |request| {
+        let host = &request.headers["host"];
+        let mut registry = registry.lock().unwrap();
+        match request.path.as_str() {
+            "/npm-writer" => HttpResponse::ok(
+                json!({
+                    "name": "npm-writer",
+                    "versions": registry
+                        .versions
+                        .iter()
+                        .map(|version| (version.clone(), json!({
+                            "dist": {
+                                "tarball": format!("http://{host}/npm-writer/-/npm-writer-{version}.tgz")
+                            }
+                        })))
+                        .collect::<serde_json::Map<_, _>>()
+                })
+                .to_string(),
+            ),
+            path => match path
+                .strip_prefix("/npm-writer/-/npm-writer-")
+                .and_then(|file| file.strip_suffix(".tgz"))
+            {
+                Some(version) => {
+                    registry.downloads.push(version.to_owned());
+                    HttpResponse::ok(tarball.clone())
+                }
+                None => HttpResponse::status(404),
+            },
+        }
+    })
+}
+
+/// Pack the npm executor fixture as a registry tarball.
+#[cfg(all(node, not(windows)))]
+fn pack_fixture(root: &Path) -> Vec<u8> {
+    let package_root = root.join("tarball");
+    cmd(format!(
+        "mkdir -p '{0}' && cp -r '{1}' '{0}/package'",
+        package_root.display(),
+        get_fixtures_root().join("executors/npm-writer").display()
+    ));
+    // executors resolved from npm registries can only write to declared paths.
+    declare_capabilities(
+        &package_root.join("package"),
+        json!({ "writablePaths": ["."] }),
+    );
+    cmd(format!(
+        "tar -czf '{0}/package.tgz' -C '{0}' package",
+        package_root.display()
+    ));
+    std::fs::read(package_root.join("package.tgz")).expect("could not read tarball")
+}
+
+#[cfg(all(node, not(windows)))]
+fn write_project(root: &Path, address: &str, version: &str) {
+    std::fs::write(
+        root.join("project/project.json"),
+        json!({
+            "targets": {
+                "target": {
+                    "executor": {
+                        "url": format!("npm://{address}/npm-writer"),
+                        "version": version,
+                        "plainHttp": true
+                    },
+                    "options": {
+                        "message": "hello"
+                    }
+                }
+            }
+        })
+        .to_string(),
+    )
+    .expect("could not write project file");
+}
+
+#[cfg(all(node, not(windows)))]
+fn execute(root: &Path) {
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    ))
+    .assert_targets([("project:target", ExpectedExecution::success())]);
+}
+
+#[cfg(all(node, not(windows)))]
+fn workspace_configuration() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            },
+            "settings": {
+                "allowUnenforcedCapabilities": true
+            }
+        }),
+        [("project", json!({}))],
+        [],
+    )
+}
+
+#[cfg(all(node, not(windows)))]
+#[test]
+fn npm_executor_updates() {
+    with_test_workspace(workspace_configuration(), |root| {
+        let registry = Arc::new(Mutex::new(Registry {
+            versions: vec!["1.0.0".into(), "1.1.0".into(), "2.0.0".into()],
+            ..Default::default()
+        }));
+        let address = start_registry(pack_fixture(root), registry.clone());
+        write_project(root, &address, "^1.0.0");
+
+        let downloads = || registry.lock().unwrap().downloads.clone();
+
+        execute(root);
+        assert_eq!(downloads(), ["1.1.0"]);
+        assert_eq!(
+            std::fs::read_to_string(root.join("project/executed"))
+                .expect("could not read executor output"),
+            "hello"
+        );
+        assert!(root
+            .join(".blaze/npm/npm-writer/1.1.0/package.json")
+            .is_file());
+
+        execute(root);
+        assert_eq!(downloads(), ["1.1.0"]);
+
+        registry.lock().unwrap().versions.push("1.2.0".into());
+
+        execute(root);
+        assert_eq!(downloads(), ["1.1.0", "1.2.0"]);
+    });
+}
+
+#[cfg(all(node, not(windows)))]
+#[test]
+fn npm_executor_version_ranges() {
+    for (range, expected) in [
+        ("1.0.0", "1.0.0"),
+        ("1.x", "1.2.0"),
+        ("~1.1", "1.1.1"),
+        ("1.0.0 - 1.1", "1.1.1"),
+        (">= 1.1.0 <1.2.0 || 3.x", "3.0.0"),
+        ("<1.1.0 || =1.1.1", "1.1.1"),
+        ("*", "3.0.0"),
+    ] {
+        with_test_workspace(workspace_configuration(), |root| {
+            let registry = Arc::new(Mutex::new(Registry {
+                versions: ["1.0.0", "1.1.0", "1.1.1", "1.2.0", "2.0.0-beta.1", "3.0.0"]
+                    .map(str::to_owned)
+                    .to_vec(),
+                ..Default::default()
+            }));
+            let address = start_registry(pack_fixture(root), registry.clone());
+            write_project(root, &address, range);
+
+            execute(root);
+            assert_eq!(
+                registry.lock().unwrap().downloads,
+                [expected],
+                "range \"{range}\" selected the wrong version"
+            );
+        });
+    }
+}
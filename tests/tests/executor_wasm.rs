@@ -0,0 +1,188 @@
+#[cfg(wasm)]
+use std::{
+    net::TcpListener,
+    path::{Path, PathBuf},
+};
+
+#[cfg(wasm)]
+use blaze_core::RunResult;
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+#[cfg(wasm)]
+use once_cell::sync::Lazy;
+use serde_json::json;
+#[cfg(wasm)]
+use serde_json::Value;
+#[cfg(wasm)]
+use testing::{cmd, get_fixtures_root, ExpectedExecution};
+use testing::{with_test_workspace, Executions, TestWorkspaceConfiguration};
+
+mod testing;
+
+/// The fixture component, which is built once for all tests.
+#[cfg(wasm)]
+static COMPONENT: Lazy<PathBuf> = Lazy::new(|| {
+    let fixture_root = get_fixtures_root().join("executors/wasm-checker");
+    cmd(format!(
+        "cargo build --release --target wasm32-wasip2 --manifest-path '{}'",
+        fixture_root.join("Cargo.toml").display()
+    ));
+    fixture_root.join("target/wasm32-wasip2/release/wasm_checker.wasm")
+});
+
+#[cfg(wasm)]
+fn workspace_configuration() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            }
+        }),
+        [("project", json!({}))],
+        [],
+    )
+}
+
+/// Run a target using the fixture component with the provided options.
+#[cfg(wasm)]
+fn execute_component(root: &Path, options: Value) -> RunResult {
+    let executor_root = root.join("executor");
+    std::fs::create_dir_all(&executor_root).expect("could not create executor directory");
+    std::fs::copy(&*COMPONENT, executor_root.join("executor.wasm"))
+        .expect("could not copy executor component");
+
+    std::fs::write(
+        root.join("project/project.json"),
+        json!({
+            "targets": {
+                "target": {
+                    "executor": format!("file://{}", executor_root.display()),
+                    "options": options
+                }
+            }
+        })
+        .to_string(),
+    )
+    .expect("could not write project configuration");
+
+    run(
+        root,
+        RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    )
+}
+
+#[cfg(wasm)]
+#[test]
+fn wasm_executor_runs_component() {
+    with_test_workspace(workspace_configuration(), |root| {
+        let path = root.join("project/written.txt");
+
+        Executions::from_run_result(execute_component(
+            root,
+            json!({
+                "action": "write",
+                "path": path
+            }),
+        ))
+        .assert_targets([("project:target", ExpectedExecution::success())]);
+
+        assert_eq!(
+            std::fs::read_to_string(path).expect("could not read written file"),
+            "written"
+        );
+    });
+}
+
+#[cfg(wasm)]
+#[test]
+fn wasm_executor_cannot_write_outside_of_preopened_directories() {
+    with_test_workspace(workspace_configuration(), |root| {
+        let path = root.join("outside.txt");
+
+        Executions::from_run_result(execute_component(
+            root,
+            json!({
+                "action": "write",
+                "path": path
+            }),
+        ))
+        .assert_targets([("project:target", ExpectedExecution::failure())]);
+
+        assert!(!path.exists());
+    });
+}
+
+#[cfg(wasm)]
+#[test]
+fn wasm_executor_cannot_access_network() {
+    with_test_workspace(workspace_configuration(), |root| {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind listener");
+        listener
+            .set_nonblocking(true)
+            .expect("could not make listener non-blocking");
+
+        Executions::from_run_result(execute_component(
+            root,
+            json!({
+                "action": "connect",
+                "address": listener.local_addr().expect("could not get listener address").to_string()
+            }),
+        ))
+        .assert_targets([("project:target", ExpectedExecution::failure())]);
+
+        assert!(
+            listener.accept().is_err(),
+            "no connection should be accepted"
+        );
+    });
+}
+
+#[test]
+fn wasm_executor_must_be_a_valid_component() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", json!({}))],
+            [],
+        ),
+        |root| {
+            let executor_root = root.join("executor");
+            std::fs::create_dir_all(&executor_root).expect("could not create executor directory");
+            std::fs::write(
+                executor_root.join("executor.wasm"),
+                b"\0asm not a component",
+            )
+            .expect("could not write executor component");
+
+            std::fs::write(
+                root.join("project/project.json"),
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": format!("file://{}", executor_root.display())
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect("could not write project configuration");
+
+            Executions::from_run_result(run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            ))
+            .assert_global_failure();
+        },
+    );
+}
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{
+    get_fixtures_root, with_test_workspace, Executions, ExpectedExecution,
+    TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+#[cfg(node)]
+fn writer_executor() -> String {
+    format!(
+        "file://{}",
+        get_fixtures_root().join("executors/npm-writer").display()
+    )
+}
+
+#[cfg(node)]
+fn writer_target(options: Value) -> Value {
+    json!({
+        "targets": {
+            "build": {
+                "executor": writer_executor(),
+                "options": options
+            }
+        }
+    })
+}
+
+#[cfg(node)]
+#[test]
+fn persistent_workers_are_reused() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "a": "a",
+                    "b": "b",
+                    "app": "app"
+                }
+            }),
+            [
+                ("a", writer_target(json!({ "message": "a" }))),
+                ("b", writer_target(json!({ "message": "b" }))),
+                (
+                    "app",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": writer_executor(),
+                                "options": {
+                                    "message": "{{ deps.a.build.pid }} {{ deps.b.build.pid }}"
+                                },
+                                "dependencies": [
+                                    {
+                                        "projects": ["a", "b"],
+                                        "target": "build"
+                                    }
+                                ]
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let execute = |persistent_workers: bool| {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("build")
+                        .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                            "app",
+                        ])))
+                        .with_parallelism(Parallelism::None)
+                        .with_persistent_workers(persistent_workers),
+                    Default::default(),
+                ))
+                .assert_targets([
+                    ("a:build", ExpectedExecution::success()),
+                    ("b:build", ExpectedExecution::success()),
+                    ("app:build", ExpectedExecution::success()),
+                ]);
+
+                let pids = std::fs::read_to_string(root.join("app/executed"))
+                    .expect("could not read executor output");
+                let (a, b) = pids.split_once(' ').expect("unexpected executor output");
+                a == b
+            };
+
+            assert!(execute(true), "executions should run in the same process");
+            assert!(
+                !execute(false),
+                "executions should run in separate processes"
+            );
+        },
+    );
+}
+
+#[cfg(node)]
+#[test]
+fn persistent_workers_run_in_project_root() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "a": "a",
+                    "b": "b",
+                    "app": "app"
+                }
+            }),
+            [
+                ("a", writer_target(json!({ "message": "a" }))),
+                ("b", writer_target(json!({ "message": "b" }))),
+                (
+                    "app",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": writer_executor(),
+                                "options": {
+                                    "message": "{{ deps.a.build.cwd }}\n{{ deps.b.build.cwd }}"
+                                },
+                                "dependencies": [
+                                    {
+                                        "projects": ["a", "b"],
+                                        "target": "build"
+                                    }
+                                ]
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            Executions::from_run_result(run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array(["app"])))
+                    .with_persistent_workers(true),
+                Default::default(),
+            ))
+            .assert_targets([
+                ("a:build", ExpectedExecution::success()),
+                ("b:build", ExpectedExecution::success()),
+                ("app:build", ExpectedExecution::success()),
+            ]);
+
+            let canonicalize =
+                |path: &Path| std::fs::canonicalize(path).expect("could not canonicalize path");
+
+            let cwds = std::fs::read_to_string(root.join("app/executed"))
+                .expect("could not read executor output");
+            let (a, b) = cwds.split_once('\n').expect("unexpected executor output");
+
+            assert_eq!(canonicalize(Path::new(a)), canonicalize(&root.join("a")));
+            assert_eq!(canonicalize(Path::new(b)), canonicalize(&root.join("b")));
+        },
+    );
+}
+
+#[cfg(node)]
+#[test]
+fn persistent_worker_crash_is_isolated() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "crashing": "crashing",
+                    "working": "working"
+                }
+            }),
+            [
+                (
+                    "crashing",
+                    writer_target(json!({ "message": "crashing", "crash": true })),
+                ),
+                ("working", writer_target(json!({ "message": "working" }))),
+            ],
+            [],
+        ),
+        |root| {
+            Executions::from_run_result(run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                        "crashing", "working",
+                    ])))
+                    .with_parallelism(Parallelism::None)
+                    .with_persistent_workers(true),
+                Default::default(),
+            ))
+            .assert_targets([
+                ("crashing:build", ExpectedExecution::failure()),
+                ("working:build", ExpectedExecution::success()),
+            ]);
+
+            assert_eq!(
+                std::fs::read_to_string(root.join("working/executed"))
+                    .expect("could not read executor output"),
+                "working"
+            );
+        },
+    );
+}
@@ -1,9 +1,9 @@
 import { Executor } from '@blaze-repo/node-devkit'
-import { deepEqual, equal, ok } from 'assert'
+import { deepEqual, equal, ok, rejects } from 'assert'
 import { isAbsolute, join } from 'path'
 import { Chance } from 'chance'
 
-const executor: Executor = (ctx, options) => {
+const executor: Executor = async (ctx, options) => {
 
     const chance = new Chance()
 
@@ -41,6 +41,12 @@ const executor: Executor = (ctx, options) => {
     ctx.logger.warn('warning from node')
     ctx.logger.debug('debug from node')
 
+    // host check
+    ok(!ctx.isCanceled())
+    ctx.progress({ completed: 1, total: 1, message: 'checked' })
+    equal(await ctx.resolvePath('file.txt'), join(ctx.project.root, 'file.txt'))
+    await rejects(ctx.resolvePath('../..'))
+
     if (chance.bool())
         return Promise.resolve()
 }
@@ -0,0 +1,6 @@
+import { writeFileSync } from 'fs'
+import { join } from 'path'
+
+export default function executor(ctx, options) {
+    writeFileSync(join(ctx.project.root, options.file), 'written by a sandboxed executor')
+}
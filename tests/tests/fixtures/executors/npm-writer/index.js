@@ -0,0 +1,10 @@
+import { writeFileSync } from 'fs'
+import { join } from 'path'
+
+export default (ctx, options) => {
+    if (options.crash)
+        process.exit(1)
+    const file = join(ctx.project.root, 'executed')
+    writeFileSync(file, options.message)
+    return { file, message: options.message, pid: process.pid, cwd: process.cwd() }
+}
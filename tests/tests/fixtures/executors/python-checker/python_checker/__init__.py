@@ -0,0 +1,49 @@
+import asyncio
+import os
+
+
+async def check(ctx, options):
+    # options check
+    assert options == {
+        "number": 1,
+        "string": "hello",
+        "bool": True,
+        "array": [1, 2, 3],
+        "null": None,
+        "float": 1.0,
+    }
+
+    # workspace check
+    assert os.path.isabs(ctx.workspace["root"])
+    assert ctx.workspace["name"] == "workspace"
+    assert ctx.workspace["configurationFileFormat"] == "Json"
+    assert ctx.workspace["configurationFilePath"] == os.path.join(ctx.workspace["root"], "workspace.json")
+    assert ctx.workspace["projects"] == {"project": {"path": "project", "tags": []}}
+
+    # project check
+    assert ctx.project["name"] == "project"
+    assert ctx.project["configurationFileFormat"] == "Json"
+    assert ctx.project["root"] == os.path.join(ctx.workspace["root"], "project")
+    assert ctx.project["configurationFilePath"] == os.path.join(ctx.workspace["root"], "project/project.json")
+
+    assert ctx.target == "target"
+
+    ctx.logger.info("hello from python")
+    ctx.logger.error("error from python")
+    ctx.logger.warn("warning from python")
+    ctx.logger.debug("debug from python")
+
+    # host check
+    assert not ctx.is_canceled()
+    ctx.progress(completed=1, total=1, message="checked")
+    assert ctx.resolve_path("file.txt") == os.path.join(ctx.project["root"], "file.txt")
+    try:
+        ctx.resolve_path("../..")
+    except RuntimeError:
+        pass
+    else:
+        raise AssertionError("path outside of the workspace was resolved")
+
+    await asyncio.sleep(0)
+
+    return {"checked": True}
@@ -47,5 +47,11 @@ pub fn execute(ctx: &ExecutorContext, options: &Value) -> ExecutorResult {
         ctx.logger.log("hello world!", log_level);
     }
 
-    Ok(())
+    // host check
+    assert!(!ctx.is_canceled());
+    ctx.progress(Some(1), Some(1), Some("checked"));
+    assert_eq!(ctx.project.root().join("file.txt"), ctx.resolve_path("file.txt")?);
+    assert!(ctx.resolve_path("../..").is_err());
+
+    Ok(Value::Null)
 }
\ No newline at end of file
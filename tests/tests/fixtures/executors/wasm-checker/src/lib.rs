@@ -0,0 +1,41 @@
+wit_bindgen::generate!({
+    path: "../../../../../core/wit",
+    world: "executor",
+});
+
+use blaze::executor::{host, types::ExecutorError};
+use serde_json::Value;
+
+struct Component;
+
+fn error<E: ToString>(message: &str, cause: E) -> ExecutorError {
+    ExecutorError {
+        message: message.to_owned(),
+        causes: vec![cause.to_string()],
+    }
+}
+
+impl Guest for Component {
+    fn execute(_context: String, options: String) -> Result<String, ExecutorError> {
+        let options =
+            serde_json::from_str::<Value>(&options).map_err(|err| error("invalid options", err))?;
+        let argument = |name: &str| options[name].as_str().unwrap_or_default().to_owned();
+
+        match options["action"].as_str() {
+            Some("write") => std::fs::write(argument("path"), "written")
+                .map_err(|err| error("could not write file", err))?,
+            Some("connect") => {
+                std::net::TcpStream::connect(argument("address"))
+                    .map_err(|err| error("could not connect", err))?;
+            }
+            action => return Err(error("unknown action", format!("{action:?}"))),
+        }
+
+        host::log(host::LogLevel::Info, "action was executed");
+        println!("wasm-checker output");
+
+        Ok("null".into())
+    }
+}
+
+export!(Component);
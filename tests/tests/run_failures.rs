@@ -0,0 +1,136 @@
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn fail_fast() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "failing": "failing",
+                    "slow": "slow"
+                }
+            }),
+            [
+                (
+                    "failing",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["sleep 1 && exit 1"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+                (
+                    "slow",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["sleep 3 && touch '{{ project.root }}/done'"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let results = run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+                    .with_parallelism(Parallelism::Infinite)
+                    .failing_fast(),
+                Default::default(),
+            );
+
+            Executions::from_run_result(results).assert_targets([
+                ("failing:build", ExpectedExecution::failure()),
+                ("slow:build", ExpectedExecution::failure()),
+            ]);
+
+            assert!(!root.join("slow/done").exists());
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn keep_going() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "failing": "failing",
+                    "slow": "slow"
+                }
+            }),
+            [
+                (
+                    "failing",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["sleep 1 && exit 1"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+                (
+                    "slow",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["sleep 3 && touch '{{ project.root }}/done'"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let results = run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+                    .with_parallelism(Parallelism::Infinite)
+                    .keeping_going(),
+                Default::default(),
+            );
+
+            Executions::from_run_result(results).assert_targets([
+                ("failing:build", ExpectedExecution::failure()),
+                ("slow:build", ExpectedExecution::success()),
+            ]);
+
+            assert!(root.join("slow/done").exists());
+        },
+    );
+}
@@ -0,0 +1,200 @@
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn undeclared_file_accesses_are_reported() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "mkdir -p out",
+                                    "cat src/input.txt undeclared.txt > out/result.txt",
+                                    "touch stray.txt"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": ["src/**"],
+                                    "outputChanges": ["out/**"]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let project_root = root.join("project-root");
+            std::fs::create_dir_all(project_root.join("src")).expect("could not create src");
+            std::fs::write(project_root.join("src/input.txt"), "input")
+                .expect("could not write input file");
+            std::fs::write(project_root.join("undeclared.txt"), "undeclared")
+                .expect("could not write undeclared file");
+
+            let report_path = root.join("report.json");
+
+            let _ = run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                        "project-name",
+                    ])))
+                    .with_hermetic_check()
+                    .with_report_file(&report_path),
+                Default::default(),
+            )
+            .expect("run error");
+
+            let report: Value = serde_json::from_str(
+                &std::fs::read_to_string(report_path).expect("could not read report file"),
+            )
+            .expect("invalid report");
+
+            let target = &report["targets"]["project-name:build"];
+
+            assert_eq!(target["status"], "executed");
+
+            let violations = |key: &str| {
+                target["hermeticViolations"][key]
+                    .as_array()
+                    .expect("violations should be an array")
+                    .iter()
+                    .map(|path| path.as_str().expect("path should be a string").to_owned())
+                    .collect::<Vec<_>>()
+            };
+
+            let writes = violations("undeclaredWrites");
+            assert_eq!(writes.len(), 1);
+            assert!(writes[0].ends_with("project-root/stray.txt"));
+
+            #[cfg(target_os = "linux")]
+            {
+                let reads = violations("undeclaredReads");
+                assert_eq!(reads.len(), 1);
+                assert!(reads[0].ends_with("project-root/undeclared.txt"));
+            }
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn concurrent_targets_are_checked_separately() {
+    let checked_target = |name: &str| {
+        json!({
+            "executor": "std:commands",
+            "options": {
+                "commands": [
+                    "sleep 1",
+                    format!("cat {name}-undeclared.txt > /dev/null"),
+                    format!("touch {name}-stray.txt"),
+                    "sleep 1"
+                ],
+                "shell": true
+            },
+            "cache": {
+                "invalidateWhen": {
+                    "inputChanges": ["src/**"]
+                }
+            }
+        })
+    };
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "first": checked_target("first"),
+                        "second": checked_target("second"),
+                        "build": {
+                            "dependencies": [{ "target": "first" }, { "target": "second" }]
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let project_root = root.join("project-root");
+            for name in ["first", "second"] {
+                std::fs::write(project_root.join(format!("{name}-undeclared.txt")), name)
+                    .expect("could not write undeclared file");
+            }
+
+            let report_path = root.join("report.json");
+
+            let _ = run(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                        "project-name",
+                    ])))
+                    .with_parallelism(Parallelism::All)
+                    .with_hermetic_check()
+                    .with_report_file(&report_path),
+                Default::default(),
+            )
+            .expect("run error");
+
+            let report: Value = serde_json::from_str(
+                &std::fs::read_to_string(report_path).expect("could not read report file"),
+            )
+            .expect("invalid report");
+
+            for name in ["first", "second"] {
+                let target = &report["targets"][format!("project-name:{name}")];
+
+                assert_eq!(target["status"], "executed");
+
+                let violations = |key: &str| {
+                    target["hermeticViolations"][key]
+                        .as_array()
+                        .expect("violations should be an array")
+                        .iter()
+                        .map(|path| path.as_str().expect("path should be a string").to_owned())
+                        .collect::<Vec<_>>()
+                };
+
+                let writes = violations("undeclaredWrites");
+                assert_eq!(writes.len(), 1);
+                assert!(writes[0].ends_with(&format!("project-root/{name}-stray.txt")));
+
+                #[cfg(target_os = "linux")]
+                {
+                    let reads = violations("undeclaredReads");
+                    assert_eq!(reads.len(), 1);
+                    assert!(reads[0].ends_with(&format!("project-root/{name}-undeclared.txt")));
+                }
+            }
+        },
+    );
+}
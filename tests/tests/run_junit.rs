@@ -0,0 +1,72 @@
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn junit_report_file() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "lint": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["echo 'lint <ok> & done'"],
+                                "shell": true
+                            }
+                        },
+                        "test": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["echo 'some test failed' && exit 1"],
+                                "shell": true
+                            },
+                            "dependencies": [
+                                {
+                                    "target": "lint"
+                                }
+                            ]
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let report_path = root.join("junit.xml");
+
+            let _ = run(
+                root,
+                RunOptions::new("test")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                        "project-name",
+                    ])))
+                    .with_junit_report_file(&report_path),
+                Default::default(),
+            )
+            .expect("run error");
+
+            let report = std::fs::read_to_string(report_path).expect("could not read report file");
+
+            assert!(report.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+            assert!(report.contains(r#"<testsuite name="project-name" tests="2" failures="1""#));
+            assert!(report.contains(r#"<testcase name="lint" classname="project-name""#));
+            assert!(report.contains("<system-out>lint &lt;ok&gt; &amp; done\n</system-out>"));
+            assert!(report.contains(r#"<testcase name="test" classname="project-name""#));
+            assert!(report
+                .contains(r#"<failure message="executor failed for target project-name:test">"#));
+            assert!(report.contains("<system-out>some test failed\n</system-out>"));
+        },
+    );
+}
@@ -0,0 +1,172 @@
+use std::sync::{Arc, Mutex};
+
+use blaze_core::{
+    common::selector::ProjectSelector, run, OutputRedirection, OutputStream, RunOptions,
+    RunOutputFormat, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn json_report_file() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "prepare": {},
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["exit 1"],
+                                "shell": true
+                            },
+                            "dependencies": [
+                                {
+                                    "target": "prepare"
+                                }
+                            ]
+                        },
+                        "deploy": {
+                            "dependencies": [
+                                {
+                                    "target": "build"
+                                }
+                            ]
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let report_path = root.join("report.json");
+
+            let _ = run(
+                root,
+                RunOptions::new("deploy")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                        "project-name",
+                    ])))
+                    .with_report_file(&report_path),
+                Default::default(),
+            )
+            .expect("run error");
+
+            let report: Value = serde_json::from_str(
+                &std::fs::read_to_string(report_path).expect("could not read report file"),
+            )
+            .expect("invalid report");
+
+            let targets = &report["targets"];
+
+            assert_eq!(targets["project-name:prepare"]["status"], "noop");
+            assert_eq!(targets["project-name:prepare"]["root"], false);
+
+            assert_eq!(targets["project-name:build"]["status"], "failed");
+            assert_eq!(
+                targets["project-name:build"]["dependencies"],
+                json!(["project-name:prepare"])
+            );
+            assert!(!targets["project-name:build"]["errors"]
+                .as_array()
+                .expect("errors should be an array")
+                .is_empty());
+
+            assert_eq!(targets["project-name:deploy"]["status"], "ignored");
+            assert_eq!(targets["project-name:deploy"]["root"], true);
+
+            assert_eq!(
+                report["stats"],
+                json!({
+                    "executed": 1,
+                    "cached": 0,
+                    "failed": 1,
+                    "pending": 1
+                })
+            );
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn json_output_format() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["echo 'some build output'"],
+                                "shell": true
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let stdout = Arc::new(Mutex::new(Vec::<u8>::new()));
+            let stderr = Arc::new(Mutex::new(Vec::<u8>::new()));
+
+            let (redirected_stdout, redirected_stderr) = (stdout.clone(), stderr.clone());
+            let redirection = OutputRedirection::new(move |stream, data| {
+                match stream {
+                    OutputStream::Stdout => &redirected_stdout,
+                    OutputStream::Stderr => &redirected_stderr,
+                }
+                .lock()
+                .expect("could not lock output")
+                .extend_from_slice(data)
+            });
+
+            redirection
+                .redirecting(|| {
+                    run(
+                        root,
+                        RunOptions::new("build")
+                            .with_selector_source(SelectorSource::Provided(ProjectSelector::array(
+                                ["project-name"],
+                            )))
+                            .with_output_format(RunOutputFormat::Json),
+                        Default::default(),
+                    )
+                })
+                .expect("run error");
+
+            let stdout = String::from_utf8(stdout.lock().expect("could not lock output").clone())
+                .expect("stdout is not valid UTF-8");
+            let stderr = String::from_utf8(stderr.lock().expect("could not lock output").clone())
+                .expect("stderr is not valid UTF-8");
+
+            let report: Value = serde_json::from_str(&stdout).expect("stdout is not a JSON report");
+
+            assert_eq!(
+                report["targets"]["project-name:build"]["status"],
+                "executed"
+            );
+            assert!(stderr.contains("some build output"));
+        },
+    );
+}
@@ -0,0 +1,200 @@
+use std::time::{Duration, Instant};
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+/// A target that fails with the provided exit code until it has been executed *succeed_at* times.
+fn flaky_workspace(succeed_at: usize, exit_code: i32, retry: Value) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace-name",
+            "projects": {
+                "project-name": "project-root"
+            }
+        }),
+        [(
+            "project-root",
+            json!({
+                "targets": {
+                    "test": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": [
+                                format!("echo attempt >> '{{{{ project.root }}}}/attempts' && [ $(wc -l < '{{{{ project.root }}}}/attempts') -ge {succeed_at} ] || exit {exit_code}")
+                            ],
+                            "shell": true
+                        },
+                        "retry": retry
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+fn run_options() -> RunOptions {
+    RunOptions::new("test").with_selector_source(SelectorSource::Provided(ProjectSelector::array(
+        ["project-name"],
+    )))
+}
+
+fn count_attempts(root: &std::path::Path) -> usize {
+    std::fs::read_to_string(root.join("project-root/attempts"))
+        .expect("could not read attempts file")
+        .lines()
+        .count()
+}
+
+#[cfg(not(windows))]
+#[test]
+fn retry_until_success() {
+    with_test_workspace(
+        flaky_workspace(
+            3,
+            1,
+            json!({
+                "maxAttempts": 3,
+                "delay": {
+                    "unit": "Milliseconds",
+                    "amount": 10
+                },
+                "backoff": "Exponential"
+            }),
+        ),
+        |root| {
+            Executions::from_run_result(run(root, run_options(), Default::default()))
+                .assert_targets([("project-name:test", ExpectedExecution::success())]);
+
+            assert_eq!(count_attempts(root), 3);
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn fail_after_max_attempts() {
+    with_test_workspace(
+        flaky_workspace(
+            5,
+            1,
+            json!({
+                "maxAttempts": 2
+            }),
+        ),
+        |root| {
+            let result = run(root, run_options(), Default::default()).expect("run error");
+
+            let error = result
+                .execution()
+                .get("project-name:test")
+                .and_then(|node| node.result.as_ref())
+                .expect("target should have been executed")
+                .as_ref()
+                .expect_err("target should have failed");
+            assert!(error.to_string().contains("failed after 2 attempts"));
+
+            assert_eq!(count_attempts(root), 2);
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn retry_only_on_exit_codes() {
+    with_test_workspace(
+        flaky_workspace(
+            2,
+            2,
+            json!({
+                "maxAttempts": 3,
+                "exitCodes": [1]
+            }),
+        ),
+        |root| {
+            Executions::from_run_result(run(root, run_options(), Default::default()))
+                .assert_targets([("project-name:test", ExpectedExecution::failure())]);
+
+            assert_eq!(count_attempts(root), 1);
+        },
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn retry_delay_is_interrupted_by_fail_fast() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root",
+                    "failing": "failing"
+                }
+            }),
+            [
+                (
+                    "project-root",
+                    json!({
+                        "targets": {
+                            "test": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["echo attempt >> '{{ project.root }}/attempts' && exit 1"],
+                                    "shell": true
+                                },
+                                "retry": {
+                                    "maxAttempts": 2,
+                                    "delay": {
+                                        "unit": "Hours",
+                                        "amount": 1
+                                    }
+                                }
+                            }
+                        }
+                    }),
+                ),
+                (
+                    "failing",
+                    json!({
+                        "targets": {
+                            "test": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["sleep 1 && exit 1"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let start = Instant::now();
+
+            Executions::from_run_result(run(
+                root,
+                RunOptions::new("test")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+                    .with_parallelism(Parallelism::Infinite)
+                    .failing_fast(),
+                Default::default(),
+            ))
+            .assert_targets([
+                ("project-name:test", ExpectedExecution::failure()),
+                ("failing:test", ExpectedExecution::failure()),
+            ]);
+
+            assert!(start.elapsed() < Duration::from_secs(60));
+            assert_eq!(count_attempts(root), 1);
+        },
+    );
+}
@@ -126,6 +126,82 @@ fn include_exclude() {
     })
 }
 
+#[cfg(not(windows))]
+#[test]
+fn affected() {
+    use crate::testing::cmd;
+
+    let build_target = |dependencies: serde_json::Value| {
+        json!({
+            "targets": {
+                "build": {
+                    "dependencies": dependencies
+                }
+            }
+        })
+    };
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "lib": "lib",
+                    "app": "app",
+                    "other": "other"
+                }
+            }),
+            [
+                ("lib", build_target(json!([]))),
+                (
+                    "app",
+                    build_target(json!([{ "projects": ["lib"], "target": "build" }])),
+                ),
+                ("other", build_target(json!([]))),
+            ],
+            [],
+        ),
+        |root| {
+            let root_str = root.display();
+            let git = "git -c user.name=blaze -c user.email=blaze@example.com";
+
+            cmd(format!(
+                "cd {root_str} && git init -q && git add -A && {git} commit -q -m initial"
+            ));
+
+            std::fs::write(root.join("lib/source.txt"), "changed").expect("could not write file");
+
+            let results = run(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::affected("HEAD", None),
+                )),
+                Default::default(),
+            );
+
+            Executions::from_run_result(results).assert_targets([
+                ("lib:build", ExpectedExecution::success()),
+                ("app:build", ExpectedExecution::success()),
+            ]);
+
+            cmd(format!(
+                "cd {root_str} && git add -A && {git} commit -q -m lib && echo changed > other/source.txt && git add -A && {git} commit -q -m other"
+            ));
+
+            let results = run(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::affected("HEAD~1", Some("HEAD")),
+                )),
+                Default::default(),
+            );
+
+            Executions::from_run_result(results)
+                .assert_targets([("other:build", ExpectedExecution::success())]);
+        },
+    )
+}
+
 fn run_and_verify_selected_projects<const N: usize>(
     root: &Path,
     source: Option<SelectorSource>,
@@ -0,0 +1,64 @@
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn timeout_kills_processes() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["(sleep 3 && touch '{{ project.root }}/done') & wait"],
+                                "shell": true
+                            },
+                            "timeout": {
+                                "unit": "Seconds",
+                                "amount": 1
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let result = run(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project-name"]),
+                )),
+                Default::default(),
+            )
+            .expect("run error");
+
+            let error = result
+                .execution()
+                .get("project-name:build")
+                .and_then(|node| node.result.as_ref())
+                .expect("target should have been executed")
+                .as_ref()
+                .expect_err("target should have failed");
+            assert!(error.to_string().contains("timed out after 1 seconds"));
+
+            Executions::from_run_result(Ok(result))
+                .assert_targets([("project-name:build", ExpectedExecution::failure())]);
+
+            std::thread::sleep(std::time::Duration::from_secs(3));
+            assert!(!root.join("project-root/done").exists());
+        },
+    );
+}
@@ -0,0 +1,98 @@
+use std::{thread, time::Duration};
+
+use blaze_core::{
+    common::selector::ProjectSelector, watch, GlobalOptions, RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn only_changed_targets_run_again() {
+    let project = || {
+        json!({
+            "targets": {
+                "build": {
+                    "executor": "std:commands",
+                    "options": {
+                        "commands": [
+                            "echo 'Hello world!' >> '{{ project.root }}/output.txt'"
+                        ],
+                        "shell": true
+                    },
+                    "cache": {
+                        "invalidateWhen": {
+                            "inputChanges": [
+                                {
+                                    "pattern": "source.txt",
+                                    "behavior": "Hash"
+                                }
+                            ]
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "first": "first",
+                    "second": "second"
+                }
+            }),
+            [("first", project()), ("second", project())],
+            [("files/source.txt", "first/")],
+        ),
+        |root| {
+            std::fs::copy(
+                root.join("first/source.txt"),
+                root.join("second/source.txt"),
+            )
+            .expect("could not copy source file");
+
+            let mut runs = vec![];
+
+            watch(
+                root,
+                RunOptions::new("build")
+                    .with_selector_source(SelectorSource::Provided(ProjectSelector::all())),
+                GlobalOptions::default(),
+                |run_result| {
+                    runs.push(Executions::from_run_result(run_result));
+                    if runs.len() == 1 {
+                        let source = root.join("first/source.txt");
+                        thread::spawn(move || {
+                            thread::sleep(Duration::from_millis(500));
+                            std::fs::write(source, "Content has changed !\n")
+                                .expect("failure to write to source file");
+                        });
+                    }
+                    runs.len() < 2
+                },
+            )
+            .expect("watch failed");
+
+            runs[0].assert_targets([
+                ("first:build", ExpectedExecution::success()),
+                ("second:build", ExpectedExecution::success()),
+            ]);
+            runs[1].assert_targets([
+                ("first:build", ExpectedExecution::success()),
+                ("second:build", ExpectedExecution::cached()),
+            ]);
+
+            let read_output = |project: &str| {
+                std::fs::read_to_string(root.join(project).join("output.txt"))
+                    .expect("could not read output file")
+            };
+            assert_eq!(read_output("first"), "Hello world!\nHello world!\n");
+            assert_eq!(read_output("second"), "Hello world!\n");
+        },
+    );
+}
@@ -0,0 +1,96 @@
+mod testing;
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+#[cfg(unix)]
+#[test]
+fn target_environment() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo \"ALLOWED=$TARGET_ENV_ALLOWED\" > {{ project.root }}/test.txt",
+                                    "echo \"LEAKED=$TARGET_ENV_LEAKED\" >> {{ project.root }}/test.txt",
+                                    "echo \"FROM_FILE=$TARGET_ENV_FROM_FILE\" >> {{ project.root }}/test.txt",
+                                    "echo \"STATIC=$TARGET_ENV_STATIC\" >> {{ project.root }}/test.txt"
+                                ],
+                                "shell": true
+                            },
+                            "environment": {
+                                "passThrough": ["PATH", "TARGET_ENV_ALLOWED"],
+                                "files": [".env.target"],
+                                "variables": {
+                                    "TARGET_ENV_STATIC": "static"
+                                }
+                            },
+                            "cache": {}
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            std::fs::write(
+                root.join("project/.env.target"),
+                "TARGET_ENV_FROM_FILE=file\nTARGET_ENV_STATIC=overridden\n",
+            )
+            .expect("could not write env file");
+
+            std::env::set_var("TARGET_ENV_ALLOWED", "1");
+            std::env::set_var("TARGET_ENV_LEAKED", "1");
+
+            let run_target = || {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                ))
+            };
+
+            run_target().assert_targets([("project:target", ExpectedExecution::success())]);
+
+            assert_eq!(
+                "ALLOWED=1\nLEAKED=\nFROM_FILE=file\nSTATIC=static\n",
+                std::fs::read_to_string(root.join("project/test.txt"))
+                    .expect("could not read test file")
+            );
+
+            // variables which are not passed through do not invalidate the cache.
+            std::env::set_var("TARGET_ENV_LEAKED", "2");
+            run_target().assert_targets([("project:target", ExpectedExecution::cached())]);
+
+            std::env::set_var("TARGET_ENV_ALLOWED", "2");
+            run_target().assert_targets([("project:target", ExpectedExecution::success())]);
+
+            std::fs::write(
+                root.join("project/.env.target"),
+                "TARGET_ENV_FROM_FILE=changed\n",
+            )
+            .expect("could not write env file");
+            run_target().assert_targets([("project:target", ExpectedExecution::success())]);
+
+            assert_eq!(
+                "ALLOWED=2\nLEAKED=\nFROM_FILE=changed\nSTATIC=static\n",
+                std::fs::read_to_string(root.join("project/test.txt"))
+                    .expect("could not read test file")
+            );
+        },
+    );
+}
@@ -108,7 +108,7 @@ impl Executions {
                             let unwrapped_result = actual_result.as_ref().unwrap();
                             assert_eq!(
                                 *cached,
-                                matches!(unwrapped_result, ExecutionDetails::Cached),
+                                matches!(unwrapped_result, ExecutionDetails::Cached { .. }),
                                 "{expected_double} must have cache state: {cached}"
                             );
                         }
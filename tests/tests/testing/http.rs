@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+};
+
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercased.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn ok<B: Into<Vec<u8>>>(body: B) -> Self {
+        Self {
+            status: 200,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Start a minimal HTTP server on a random local port, and return its address (`host:port`).
+/// Each request is handled in its own thread, and connections are closed after each response.
+pub fn start_http_server<H>(handler: H) -> String
+where
+    H: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind test server");
+    let address = listener
+        .local_addr()
+        .expect("could not get test server address");
+    let handler = Arc::new(handler);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let handler = handler.clone();
+            std::thread::spawn(move || {
+                handle_connection(stream.expect("could not accept connection"), &*handler)
+            });
+        }
+    });
+
+    address.to_string()
+}
+
+fn handle_connection<H: Fn(&HttpRequest) -> HttpResponse>(mut stream: TcpStream, handler: &H) {
+    let mut reader = BufReader::new(stream.try_clone().expect("could not clone stream"));
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .expect("could not read request line");
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .expect("could not read header");
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .map(|length| length.parse().expect("invalid content length"))
+        .unwrap_or(0);
+    let mut body = vec![0_u8; content_length];
+    reader.read_exact(&mut body).expect("could not read body");
+
+    let response = handler(&HttpRequest {
+        method: method.clone(),
+        path,
+        headers,
+        body,
+    });
+
+    let mut head = format!("HTTP/1.1 {} Test\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+
+    stream
+        .write_all(head.as_bytes())
+        .expect("could not write response");
+    if method != "HEAD" {
+        stream
+            .write_all(&response.body)
+            .expect("could not write response body");
+    }
+}
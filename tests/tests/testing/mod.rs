@@ -2,10 +2,12 @@
 
 mod commands;
 mod executions;
+mod http;
 mod util;
 mod workspace;
 
 pub use commands::cmd;
 pub use executions::*;
-pub use util::get_fixtures_root;
+pub use http::*;
+pub use util::{declare_capabilities, get_fixtures_root};
 pub use workspace::*;
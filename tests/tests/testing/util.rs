@@ -9,3 +9,14 @@ pub fn get_fixtures_root() -> PathBuf {
 pub fn get_fixtures_root() -> PathBuf {
     Path::new(env!("PROJECT_ROOT")).join("tests/fixtures")
 }
+
+/// Declare capabilities in the `package.json` file of a Node.js executor located at `package_root`.
+pub fn declare_capabilities(package_root: &Path, capabilities: serde_json::Value) {
+    let manifest_path = package_root.join("package.json");
+    let mut manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&manifest_path).expect("could not read package.json"),
+    )
+    .expect("could not parse package.json");
+    manifest["blaze"]["capabilities"] = capabilities;
+    std::fs::write(&manifest_path, manifest.to_string()).expect("could not write package.json");
+}
@@ -135,7 +135,9 @@ const sidebars = {
                     label: 'Writing executors',
                     items: [
                         'rust',
-                        'node'
+                        'node',
+                        'python',
+                        'wasm'
                     ].map(name => `executors/languages/${name}`)
                 }
             ]